@@ -125,6 +125,26 @@ fn benchmark_reading_other(c: &mut Criterion) {
         })
     });
 
+    group.bench_function("read_ascii_str_long", |b| {
+        b.iter(|| {
+            read_ascii_str(
+                black_box(b"SOME_VERY_LONG_SYMBOL_NAME_FOR_TESTING"),
+                "Symbol",
+                55,
+            )
+        })
+    });
+
+    group.bench_function("try_read_str_long", |b| {
+        b.iter(|| {
+            try_read_str(
+                black_box(b"SOME_VERY_LONG_SYMBOL_NAME_FOR_TESTING"),
+                "Symbol",
+                55,
+            )
+        })
+    });
+
     group.finish();
 }
 
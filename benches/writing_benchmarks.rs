@@ -76,6 +76,7 @@ fn benchmark_writing_integers(c: &mut Criterion) {
     group.finish();
 }
 
+#[allow(clippy::excessive_precision)]
 fn benchmark_writing_floats(c: &mut Criterion) {
     let mut group = c.benchmark_group("writing_floats");
     let mut buf = vec![0u8; 100];
@@ -212,6 +213,28 @@ fn benchmark_tag_writing(c: &mut Criterion) {
         })
     });
 
+    group.bench_function("write_tag_num_and_u32", |b| {
+        b.iter(|| {
+            write_tag_num_and_u32(
+                black_box(&mut buf),
+                0,
+                black_box(34),
+                black_box(12345),
+            )
+        })
+    });
+
+    group.bench_function("write_tag_num_and_str", |b| {
+        b.iter(|| {
+            write_tag_num_and_str(
+                black_box(&mut buf),
+                0,
+                black_box(5001),
+                black_box("EURUSD"),
+            )
+        })
+    });
+
     group.finish();
 }
 
@@ -321,6 +344,111 @@ fn benchmark_forge_buffer(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_header_template(c: &mut Criterion) {
+    let mut group = c.benchmark_group("header_template");
+    let mut buffer = [0u8; FORGE_BUFFER_SIZE];
+    let template = HeaderTemplate::new("FIX.4.4", b"D", "SENDER", "TARGET");
+    let now = libc::timespec {
+        tv_sec: 1_700_000_000,
+        tv_nsec: 123_456_789,
+    };
+
+    // Baseline: forge header plus per-field writers
+    group.bench_function("header_field_by_field", |b| {
+        let mut seq = 0u32;
+        b.iter(|| {
+            seq = seq.wrapping_add(1);
+            buffer[..FORGE_WRITE_START].copy_from_slice(b"8=FIX.4.4\x019=0000\x0135=");
+            let mut pos = FORGE_WRITE_START;
+            black_box(&mut buffer)[pos] = b'D';
+            pos += 1;
+            buffer[pos] = 0x01;
+            pos += 1;
+            pos += write_tag_and_str(black_box(&mut buffer), pos, b"49=", black_box("SENDER"));
+            pos += write_tag_and_str(black_box(&mut buffer), pos, b"56=", black_box("TARGET"));
+            pos += write_tag_and_u32(black_box(&mut buffer), pos, b"34=", black_box(seq));
+            pos += format_timestamp_from_timespec(black_box(&mut buffer), pos, b"52=", black_box(&now));
+            black_box(pos)
+        })
+    });
+
+    // One memcpy plus digit patches
+    group.bench_function("header_template_stamp", |b| {
+        let mut seq = 0u32;
+        b.iter(|| {
+            seq = seq.wrapping_add(1);
+            black_box(template.stamp(black_box(&mut buffer), black_box(seq), black_box(&now)))
+        })
+    });
+
+    group.bench_function("header_template_stamp_and_finalize", |b| {
+        let mut seq = 0u32;
+        b.iter(|| {
+            seq = seq.wrapping_add(1);
+            let mut pos = template.stamp(black_box(&mut buffer), black_box(seq), black_box(&now));
+            pos += write_tag_and_str(black_box(&mut buffer), pos, b"11=", black_box("ORD-1"));
+            black_box(finalize_message(black_box(&mut buffer), pos))
+        })
+    });
+
+    group.finish();
+}
+
+fn benchmark_message_template(c: &mut Criterion) {
+    let mut group = c.benchmark_group("message_template");
+    let secs = 1_700_000_000;
+
+    // Baseline: NewOrderSingle written field by field into a forge buffer
+    group.bench_function("new_order_single_forge", |b| {
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut seq = 0u32;
+        b.iter(|| {
+            seq = seq.wrapping_add(1);
+            let mut pos = FORGE_WRITE_START;
+            buffer[pos] = b'D';
+            buffer[pos + 1] = 0x01;
+            pos += 2;
+            pos += write_tag_and_str(&mut buffer, pos, b"49=", "SENDER");
+            pos += write_tag_and_str(&mut buffer, pos, b"56=", "TARGET");
+            pos += write_tag_and_u32(&mut buffer, pos, b"34=", black_box(seq));
+            pos += format_timestamp(&mut buffer, pos, b"52=", black_box(secs), 0);
+            pos += write_tag_and_str(&mut buffer, pos, b"11=", black_box("0000000000ABC"));
+            pos += write_tag_and_str(&mut buffer, pos, b"55=", "MSFT");
+            pos += write_tag_and_str(&mut buffer, pos, b"54=", "1");
+            pos += format_timestamp(&mut buffer, pos, b"60=", black_box(secs), 0);
+            pos += write_tag_and_u64(&mut buffer, pos, b"38=", black_box(1_500));
+            pos += write_tag_and_str(&mut buffer, pos, b"40=", "2");
+            pos += write_tag_and_f64(&mut buffer, pos, b"44=", black_box(123.45));
+            black_box(finalize_message(&mut buffer, pos))
+        })
+    });
+
+    // Same message from a template: only the slots are rewritten
+    let mut builder = MessageTemplate::builder(HeaderTemplate::new("FIX.4.4", b"D", "SENDER", "TARGET"));
+    let cl_ord_id = builder.slot("ClOrdID", b"11=", 13);
+    builder.field(b"55=", b"MSFT").field(b"54=", b"1");
+    let transact_time = builder.timestamp_slot("TransactTime", b"60=");
+    let qty = builder.slot("OrderQty", b"38=", 9);
+    builder.field(b"40=", b"2");
+    let price = builder.slot("Price", b"44=", 12);
+    let mut template = builder.build();
+
+    group.bench_function("new_order_single_template", |b| {
+        let mut seq = 0u32;
+        b.iter(|| {
+            seq = seq.wrapping_add(1);
+            template.stamp_at(black_box(seq), black_box(secs), 0);
+            template.set_bytes(cl_ord_id, black_box(b"0000000000ABC")).unwrap();
+            template.set_timestamp(transact_time, black_box(secs), 0);
+            template.set_u64(qty, black_box(1_500)).unwrap();
+            template.set_decimal(price, black_box(12_345), 2).unwrap();
+            black_box(template.as_bytes().len())
+        })
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_writing_integers,
@@ -328,6 +456,8 @@ criterion_group!(
     benchmark_tag_writing,
     benchmark_special_functions,
     benchmark_mixed_writing,
-    benchmark_forge_buffer
+    benchmark_forge_buffer,
+    benchmark_header_template,
+    benchmark_message_template
 );
 criterion_main!(benches);
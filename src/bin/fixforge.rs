@@ -0,0 +1,567 @@
+//! `fixforge` - decode, validate and pretty-print FIX logs.
+//!
+//! Reads FIX log files (or stdin), finds every message in them with
+//! [`FrameSplitter`], checks BodyLength(9) and CheckSum(10), and prints the
+//! messages that pass the filters. See `fixforge --help`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+use fix44_forge_helpers::*;
+
+const USAGE: &str = "\
+usage: fixforge [OPTIONS] [FILE...]
+
+Reads FIX messages from each FILE (or stdin when none is given, or for `-`),
+validates BodyLength(9) and CheckSum(10) and prints the messages.
+Messages may be SOH or `|` delimited and prefixed by timestamps or other text.
+
+Options:
+  -f, --format FORMAT   pretty (default), line or json
+  -t, --msg-type TYPE   keep messages with MsgType(35)=TYPE; repeatable
+      --tag TAG=VALUE   keep messages where TAG (number or name) has VALUE;
+                        repeatable, all must match
+      --from TIME       keep messages with SendingTime(52) at or after TIME
+      --to TIME         keep messages with SendingTime(52) at or before TIME
+                        (TIME is YYYYMMDD-HH:MM:SS[.sss] or any prefix of it)
+      --invalid         keep only messages that fail validation
+  -d, --dict FILE       QuickFIX XML dictionary for names, descriptions and groups
+      --no-mask         show Password(554) and NewPassword(925)
+  -h, --help            print this help
+
+Exit status is 0 when every message is valid, 1 when some are not and 2 on errors.";
+
+const READ_CHUNK: usize = 64 * 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Pretty,
+    Line,
+    Json,
+}
+
+struct Args {
+    format: Format,
+    msg_types: Vec<String>,
+    tags: Vec<(u32, String)>,
+    from: Option<String>,
+    to: Option<String>,
+    invalid_only: bool,
+    dict: Option<String>,
+    mask: bool,
+    files: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args {
+        format: Format::Pretty,
+        msg_types: Vec::new(),
+        tags: Vec::new(),
+        from: None,
+        to: None,
+        invalid_only: false,
+        dict: None,
+        mask: true,
+        files: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("{name} needs a value"))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-f" | "--format" => {
+                parsed.format = match value(&arg)?.as_str() {
+                    "pretty" => Format::Pretty,
+                    "line" => Format::Line,
+                    "json" => Format::Json,
+                    other => return Err(format!("unknown format `{other}`")),
+                }
+            }
+            "-t" | "--msg-type" => parsed.msg_types.push(value(&arg)?),
+            "--tag" => {
+                let filter = value(&arg)?;
+                let (tag, wanted) = filter
+                    .split_once('=')
+                    .ok_or_else(|| format!("--tag expects TAG=VALUE, got `{filter}`"))?;
+                let tag = tag
+                    .parse()
+                    .ok()
+                    .or_else(|| fix44_tag_number(tag))
+                    .ok_or_else(|| format!("unknown tag `{tag}`"))?;
+                parsed.tags.push((tag, wanted.to_owned()));
+            }
+            "--from" => parsed.from = Some(value(&arg)?),
+            "--to" => parsed.to = Some(value(&arg)?),
+            "--invalid" => parsed.invalid_only = true,
+            "-d" | "--dict" => parsed.dict = Some(value(&arg)?),
+            "--no-mask" => parsed.mask = false,
+            "-" => parsed.files.push(arg),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ => parsed.files.push(arg),
+        }
+    }
+    if parsed.format == Format::Json && !cfg!(feature = "json") {
+        return Err("--format json needs fixforge built with the `json` feature".to_owned());
+    }
+    Ok(Some(parsed))
+}
+
+impl Args {
+    /// Whether a SOH-delimited message passes the MsgType, tag and time filters.
+    fn keeps(
+        &self,
+        msg: &[u8],
+    ) -> bool {
+        let header = MessageHeader::parse(msg);
+        if !self.msg_types.is_empty()
+            && !self
+                .msg_types
+                .iter()
+                .any(|t| t.as_bytes() == header.msg_type)
+        {
+            return false;
+        }
+        let matches = |tag: u32, wanted: &str| {
+            TagValueIter::new(msg).any(|(t, v)| t == tag && v == wanted.as_bytes())
+        };
+        if !self
+            .tags
+            .iter()
+            .all(|(tag, wanted)| matches(*tag, wanted))
+        {
+            return false;
+        }
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        let Some((_, sent)) = TagValueIter::new(msg).find(|&(tag, _)| tag == 52) else {
+            return false;
+        };
+        // FIX UTCTimestamps sort lexicographically; `--to` compares by prefix
+        // so that `--to 20240102-12` includes the whole hour
+        self.from
+            .as_ref()
+            .is_none_or(|from| sent >= from.as_bytes())
+            && self.to.as_ref().is_none_or(|to| {
+                &sent[..sent.len().min(to.len())] <= to.as_bytes()
+            })
+    }
+}
+
+struct Printer<'a, W> {
+    args: &'a Args,
+    dictionary: Option<&'a Dictionary>,
+    out: W,
+    invalid: u64,
+    soh: Vec<u8>,
+}
+
+impl<W: Write> Printer<'_, W> {
+    fn frame(
+        &mut self,
+        source: &str,
+        offset: usize,
+        frame: &Frame<'_>,
+    ) -> io::Result<()> {
+        let result = frame.validate();
+        if let Err(e) = &result {
+            self.invalid += 1;
+            eprintln!("{source}:{offset}: {e}");
+        }
+        if self.args.invalid_only && result.is_ok() {
+            return Ok(());
+        }
+        self.soh.clear();
+        self.soh.extend(frame.bytes.iter().map(|&b| {
+            if b == frame.delimiter { 0x01 } else { b }
+        }));
+        if !self.args.keeps(&self.soh) {
+            return Ok(());
+        }
+
+        let masked_tags = if self.args.mask {
+            DEFAULT_MASKED_TAGS
+        } else {
+            &[]
+        };
+        let options = DisplayOptions {
+            masked_tags,
+            dictionary: self.dictionary,
+            ..DisplayOptions::default()
+        };
+        match self.args.format {
+            Format::Pretty => {
+                let options = DisplayOptions {
+                    delimiter: "\n",
+                    tag_names: true,
+                    value_descriptions: true,
+                    ..options
+                };
+                match result {
+                    Ok(()) => writeln!(self.out, "# {source}:{offset}")?,
+                    Err(e) => writeln!(self.out, "# {source}:{offset} INVALID: {e}")?,
+                }
+                writeln!(self.out, "{}", FixDisplay::with_options(&self.soh, options))
+            }
+            Format::Line => {
+                writeln!(self.out, "{}", FixDisplay::with_options(&self.soh, options))
+            }
+            Format::Json => self.json(source, offset, masked_tags),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn json(
+        &mut self,
+        source: &str,
+        offset: usize,
+        masked_tags: &[u32],
+    ) -> io::Result<()> {
+        let options = JsonOptions {
+            tag_names: true,
+            dictionary: self.dictionary,
+            ..JsonOptions::default()
+        };
+        match fix_to_json(&mask_values(&self.soh, masked_tags), &options) {
+            Ok(json) => writeln!(self.out, "{json}"),
+            Err(e) => {
+                eprintln!("{source}:{offset}: cannot convert to JSON: {e}");
+                Ok(())
+            }
+        }
+    }
+
+    #[cfg(not(feature = "json"))]
+    fn json(
+        &mut self,
+        _source: &str,
+        _offset: usize,
+        _masked_tags: &[u32],
+    ) -> io::Result<()> {
+        unreachable!("rejected by parse_args")
+    }
+}
+
+/// Copy of a SOH-delimited message with the values of `masked_tags` replaced,
+/// walking the fields as [`FixDisplay`] does.
+#[cfg(feature = "json")]
+fn mask_values(
+    msg: &[u8],
+    masked_tags: &[u32],
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity(msg.len());
+    let mut copied = 0;
+    for field in LenientFieldIter::new(msg) {
+        if field.tag.is_some_and(|tag| masked_tags.contains(&tag)) {
+            out.extend_from_slice(&msg[copied..field.offset]);
+            out.extend_from_slice(MASKED_VALUE.as_bytes());
+            copied = field.offset + field.value.len();
+        }
+    }
+    out.extend_from_slice(&msg[copied..]);
+    out
+}
+
+/// Split `input` into frames as it is read and hand each one to `printer`.
+fn process(
+    printer: &mut Printer<'_, impl Write>,
+    source: &str,
+    mut input: impl Read,
+) -> io::Result<()> {
+    let mut buf = Vec::with_capacity(READ_CHUNK);
+    let mut base = 0;
+    loop {
+        let len = buf.len();
+        buf.resize(len + READ_CHUNK, 0);
+        let n = input.read(&mut buf[len..])?;
+        buf.truncate(len + n);
+
+        let mut frames = FrameSplitter::new(&buf);
+        for frame in frames.by_ref() {
+            printer.frame(source, base + frame.offset, &frame)?;
+        }
+        let consumed = frames.position();
+        if n == 0 {
+            let rest = &buf[consumed..];
+            let mut frames = FrameSplitter::complete(rest);
+            for frame in frames.by_ref() {
+                printer.frame(source, base + consumed + frame.offset, &frame)?;
+            }
+            let left = frames.remainder();
+            if left.windows(5).any(|w| w == b"8=FIX") {
+                let offset = base + rest.len() - left.len() + consumed;
+                eprintln!("{source}:{offset}: truncated message at end of input");
+                printer.invalid += 1;
+            }
+            return Ok(());
+        }
+        buf.drain(..consumed);
+        base += consumed;
+    }
+}
+
+fn run(args: &Args) -> Result<u64, String> {
+    let dictionary = match &args.dict {
+        Some(path) => {
+            let xml =
+                std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+            Some(Dictionary::parse(&xml).map_err(|e| format!("{path}: {e}"))?)
+        }
+        None => None,
+    };
+    let mut printer = Printer {
+        args,
+        dictionary: dictionary.as_ref(),
+        out: BufWriter::new(io::stdout().lock()),
+        invalid: 0,
+        soh: Vec::new(),
+    };
+    let stdin = ["-".to_owned()];
+    let files = if args.files.is_empty() {
+        &stdin[..]
+    } else {
+        &args.files[..]
+    };
+    for path in files {
+        let result = if path == "-" {
+            process(&mut printer, "<stdin>", io::stdin().lock())
+        } else {
+            File::open(path).and_then(|file| process(&mut printer, path, file))
+        };
+        match result {
+            Ok(()) => {}
+            // Output piped into `head` and closed
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(printer.invalid),
+            Err(e) => return Err(format!("{path}: {e}")),
+        }
+    }
+    match printer.out.flush() {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(format!("stdout: {e}")),
+        _ => Ok(printer.invalid),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("fixforge: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("fixforge: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(list.iter().map(|s| s.to_string()))
+    }
+
+    /// A `|`-delimited message around `body` with correct BodyLength and CheckSum.
+    fn piped(body: &str) -> Vec<u8> {
+        let mut msg = format!("8=FIX.4.4|9={}|{body}", body.len()).into_bytes();
+        let sum = msg
+            .iter()
+            .map(|&b| if b == b'|' { 1u32 } else { b as u32 })
+            .sum::<u32>();
+        msg.extend_from_slice(format!("10={:03}|", sum % 256).as_bytes());
+        msg
+    }
+
+    fn render(
+        args: &Args,
+        log: &[u8],
+    ) -> String {
+        let mut printer = Printer {
+            args,
+            dictionary: None,
+            out: Vec::new(),
+            invalid: 0,
+            soh: Vec::new(),
+        };
+        process(&mut printer, "test", log).unwrap();
+        assert_eq!(printer.invalid, 0);
+        String::from_utf8(printer.out).unwrap()
+    }
+
+    #[test]
+    fn test_parse_args_defaults_and_options() {
+        let parsed = args(&[]).unwrap().unwrap();
+        assert!(parsed.format == Format::Pretty && parsed.mask && !parsed.invalid_only);
+        assert!(parsed.files.is_empty());
+
+        let parsed = args(&[
+            "-f", "line", "-t", "D", "--msg-type", "8", "--tag", "Symbol=EUR/USD", "--tag",
+            "11=A=B", "--from", "20240102", "--to", "20240103-12", "--invalid", "--no-mask",
+            "-d", "FIX44.xml", "a.log", "-",
+        ])
+        .unwrap()
+        .unwrap();
+        assert!(parsed.format == Format::Line);
+        assert_eq!(parsed.msg_types, ["D", "8"]);
+        assert_eq!(
+            parsed.tags,
+            [(55, "EUR/USD".to_owned()), (11, "A=B".to_owned())]
+        );
+        assert_eq!(parsed.from.as_deref(), Some("20240102"));
+        assert_eq!(parsed.to.as_deref(), Some("20240103-12"));
+        assert!(parsed.invalid_only && !parsed.mask);
+        assert_eq!(parsed.dict.as_deref(), Some("FIX44.xml"));
+        assert_eq!(parsed.files, ["a.log", "-"]);
+
+        assert!(args(&["-h"]).unwrap().is_none());
+        assert!(args(&["a.log", "--help"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        for (list, expected) in [
+            (&["--bogus"][..], "unknown option `--bogus`"),
+            (&["-t"][..], "-t needs a value"),
+            (&["--format", "xml"][..], "unknown format `xml`"),
+            (&["--tag", "55"][..], "--tag expects TAG=VALUE, got `55`"),
+            (&["--tag", "NoSuchField=1"][..], "unknown tag `NoSuchField`"),
+        ] {
+            assert_eq!(args(list).err().as_deref(), Some(expected));
+        }
+        assert_eq!(args(&["-f", "json"]).is_ok(), cfg!(feature = "json"));
+    }
+
+    #[test]
+    fn test_keeps_msg_type_and_tag_filters() {
+        let order = b"8=FIX.4.4\x019=9\x0135=D\x0155=IBM\x0154=1\x0110=000\x01";
+        let keeps = |list: &[&str]| args(list).unwrap().unwrap().keeps(order);
+        assert!(keeps(&[]));
+        assert!(keeps(&["-t", "8", "-t", "D"]));
+        assert!(!keeps(&["-t", "8"]));
+        assert!(keeps(&["--tag", "Symbol=IBM", "--tag", "54=1"]));
+        assert!(!keeps(&["--tag", "Symbol=IBM", "--tag", "54=2"]));
+        assert!(!keeps(&["--tag", "55=IB"]));
+    }
+
+    #[test]
+    fn test_keeps_time_range_by_prefix() {
+        let msg = b"35=0\x0152=20240102-12:30:00.000\x01";
+        let keeps = |list: &[&str]| args(list).unwrap().unwrap().keeps(msg);
+        assert!(keeps(&["--from", "20240102-12:30"]));
+        assert!(keeps(&["--from", "20240102-12:30:00.000"]));
+        assert!(!keeps(&["--from", "20240102-12:30:00.001"]));
+        assert!(keeps(&["--to", "20240102-12"]));
+        assert!(keeps(&["--to", "20240102-12:30:00.000"]));
+        assert!(!keeps(&["--to", "20240102-12:29"]));
+        assert!(keeps(&["--from", "20240102", "--to", "20240102"]));
+        // Without SendingTime a time filter never matches
+        assert!(!args(&["--to", "2099"]).unwrap().unwrap().keeps(b"35=0\x01"));
+    }
+
+    #[test]
+    fn test_line_and_pretty_output_mask_after_malformed_field() {
+        let mut log = b"12:00:00 IN ".to_vec();
+        log.extend(piped("35=A|garbage|553=me|554=hunter2|"));
+        log.push(b'\n');
+
+        let line = render(&args(&["-f", "line"]).unwrap().unwrap(), &log);
+        assert!(line.contains("|553=me|554=***|10="), "{line}");
+        let pretty = render(&args(&[]).unwrap().unwrap(), &log);
+        assert!(pretty.starts_with("# test:12\n"), "{pretty}");
+        assert!(pretty.contains("\nPassword(554)=***\n"), "{pretty}");
+        assert!(!line.contains("hunter2") && !pretty.contains("hunter2"));
+
+        let unmasked = render(&args(&["-f", "line", "--no-mask"]).unwrap().unwrap(), &log);
+        assert!(unmasked.contains("554=hunter2"));
+    }
+
+    #[test]
+    fn test_filters_select_frames() {
+        let mut log = piped("35=D|52=20240102-12:00:00|55=IBM|");
+        log.extend(piped("35=8|52=20240102-13:00:00|55=IBM|"));
+        log.extend(piped("35=D|52=20240102-14:00:00|55=MSFT|"));
+
+        let out = render(
+            &args(&["-f", "line", "-t", "D", "--tag", "55=IBM"]).unwrap().unwrap(),
+            &log,
+        );
+        assert_eq!(out.lines().count(), 1);
+        assert!(out.contains("55=IBM"));
+        let out = render(
+            &args(&["-f", "line", "--from", "20240102-13", "--to", "20240102-13"]).unwrap().unwrap(),
+            &log,
+        );
+        assert_eq!(out.lines().count(), 1);
+        assert!(out.contains("35=8"));
+    }
+
+    #[test]
+    fn test_last_message_with_bad_body_length_is_shown() {
+        let args = args(&["-f", "line"]).unwrap().unwrap();
+        let mut printer = Printer {
+            args: &args,
+            dictionary: None,
+            out: Vec::new(),
+            invalid: 0,
+            soh: Vec::new(),
+        };
+        let log = b"8=FIX.4.4|9=99|35=A|554=hunter2|10=000|\n8=FIX.4.4|9=5|35";
+        process(&mut printer, "test", &log[..]).unwrap();
+        // One bad BodyLength, one truncated message
+        assert_eq!(printer.invalid, 2);
+        let out = String::from_utf8(printer.out).unwrap();
+        assert_eq!(out, "8=FIX.4.4|9=99|35=A|554=***|10=000|\n");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_mask_values() {
+        let msg = b"35=A\x01553=me\x01554=hunter2\x01925=new\x0110=000\x01";
+        assert_eq!(
+            mask_values(msg, DEFAULT_MASKED_TAGS),
+            b"35=A\x01553=me\x01554=***\x01925=***\x0110=000\x01"
+        );
+        assert_eq!(mask_values(msg, &[]), msg);
+        // A malformed field does not stop masking of the fields after it
+        assert_eq!(
+            mask_values(b"35=A\x01garbage\x01554=hunter2\x01", DEFAULT_MASKED_TAGS),
+            b"35=A\x01garbage\x01554=***\x01"
+        );
+        assert_eq!(
+            mask_values(b"554=\x01x\x01925=\xff\x01", DEFAULT_MASKED_TAGS),
+            b"554=***\x01x\x01925=***\x01"
+        );
+        // An unterminated last field is masked too
+        assert_eq!(mask_values(b"35=A\x01554=hunt", DEFAULT_MASKED_TAGS), b"35=A\x01554=***");
+
+        // Same masking rules as FixDisplay
+        let unmasked = DisplayOptions {
+            masked_tags: &[],
+            ..DisplayOptions::default()
+        };
+        for msg in [
+            &b"35=A\x01garbage\x01554=hunter2\x01"[..],
+            b"95=9\x0196=ab\x01554=x\x01",
+            b"=\x01\x01554=x\x01925=y",
+            b"554",
+        ] {
+            assert_eq!(
+                FixDisplay::with_options(&mask_values(msg, DEFAULT_MASKED_TAGS), unmasked).to_string(),
+                FixDisplay::new(msg).to_string()
+            );
+        }
+    }
+}
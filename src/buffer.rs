@@ -142,6 +142,159 @@ pub fn update_body_length(
     }
 }
 
+/// Length of the CheckSum trailer field "10=XXX\x01".
+pub const CHECKSUM_FIELD_LEN: usize = 7;
+
+/// Compute the FIX CheckSum(10) value: the byte sum of `bytes` modulo 256.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::checksum;
+/// assert_eq!(checksum(b"8=FIX.4.4\x01"), 0x21);
+/// ```
+#[inline(always)]
+pub fn checksum(bytes: &[u8]) -> u8 {
+    let mut sum: u32 = 0;
+    for &b in bytes {
+        sum = sum.wrapping_add(b as u32);
+    }
+    sum as u8
+}
+
+/// Write the CheckSum(10) field for `buffer[..message_length]` at `message_length`.
+///
+/// Writes "10=XXX\x01" (zero-padded to 3 digits) and returns `CHECKSUM_FIELD_LEN`.
+///
+/// # Safety
+/// Caller must ensure the buffer has at least 7 bytes available after `message_length`.
+#[inline(always)]
+pub fn write_checksum(
+    buffer: &mut [u8],
+    message_length: usize,
+) -> usize {
+    let sum = checksum(&buffer[..message_length]);
+    unsafe {
+        let ptr = buffer
+            .as_mut_ptr()
+            .add(message_length);
+        *ptr = b'1';
+        *ptr.add(1) = b'0';
+        *ptr.add(2) = b'=';
+        *ptr.add(6) = 0x01;
+    }
+    write_checksum_digits(buffer, message_length, sum);
+    CHECKSUM_FIELD_LEN
+}
+
+/// CheckSum(10) values rendered as three digits.
+const CHECKSUM_DIGITS: [[u8; 3]; 256] = {
+    let mut table = [[0u8; 3]; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = [b'0' + (i / 100) as u8, b'0' + (i / 10 % 10) as u8, b'0' + (i % 10) as u8];
+        i += 1;
+    }
+    table
+};
+
+/// Overwrite the three CheckSum(10) digits of the field starting at `checksum_pos`.
+#[inline(always)]
+pub(crate) fn write_checksum_digits(
+    buffer: &mut [u8],
+    checksum_pos: usize,
+    sum: u8,
+) {
+    debug_assert!(buffer.len() >= checksum_pos + CHECKSUM_FIELD_LEN);
+    unsafe {
+        ptr::copy_nonoverlapping(
+            CHECKSUM_DIGITS[sum as usize].as_ptr(),
+            buffer
+                .as_mut_ptr()
+                .add(checksum_pos + 3),
+            3,
+        );
+    }
+}
+
+/// Overwrite `buffer[offset..offset + new_bytes.len()]` in a finished message
+/// and adjust its CheckSum(10) by the byte delta instead of re-summing.
+///
+/// `checksum_pos` is where the "10=XXX\x01" field starts, i.e. the message
+/// length minus `CHECKSUM_FIELD_LEN`. The patch must lie before it and must
+/// not change the message length, so BodyLength(9) stays valid; use
+/// fixed-width values (zero-padded numbers, same-length identifiers).
+///
+/// Returns the new checksum.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let mut buffer = forge_out_buffer("FIX.4.4");
+/// let mut pos = FORGE_WRITE_START;
+/// buffer[pos] = b'D';
+/// buffer[pos + 1] = 0x01;
+/// pos += 2;
+/// let qty_pos = pos + 3;
+/// pos += write_tag_and_str(&mut buffer, pos, b"38=", "000100");
+/// let len = finalize_message(&mut buffer, pos);
+///
+/// let sum = patch_field_with_checksum(&mut buffer, qty_pos, b"000250", len - CHECKSUM_FIELD_LEN);
+/// assert_eq!(&buffer[qty_pos..qty_pos + 6], b"000250");
+/// assert_eq!(sum, checksum(&buffer[..len - CHECKSUM_FIELD_LEN]));
+/// ```
+///
+/// # Safety
+/// Caller must ensure `buffer` holds a complete CheckSum field at `checksum_pos`.
+#[inline]
+pub fn patch_field_with_checksum(
+    buffer: &mut [u8],
+    offset: usize,
+    new_bytes: &[u8],
+    checksum_pos: usize,
+) -> u8 {
+    let end = offset + new_bytes.len();
+    debug_assert!(end <= checksum_pos, "patch overlaps CheckSum(10)");
+    debug_assert!(
+        &buffer[checksum_pos..checksum_pos + 3] == b"10=",
+        "no CheckSum(10) at checksum_pos"
+    );
+    let digits = &buffer[checksum_pos + 3..checksum_pos + 6];
+    let old_sum = (digits[0].wrapping_sub(b'0'))
+        .wrapping_mul(100)
+        .wrapping_add(digits[1].wrapping_sub(b'0').wrapping_mul(10))
+        .wrapping_add(digits[2].wrapping_sub(b'0'));
+    let sum = old_sum
+        .wrapping_sub(checksum(&buffer[offset..end]))
+        .wrapping_add(checksum(new_bytes));
+    buffer[offset..end].copy_from_slice(new_bytes);
+    write_checksum_digits(buffer, checksum_pos, sum);
+    sum
+}
+
+/// Finalize a forge buffer message: patch BodyLength and append CheckSum.
+///
+/// `message_length` is the position where CheckSum (10=XXX) will be written.
+/// Returns the total message length including the CheckSum field.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let mut buffer = forge_out_buffer("FIX.4.4");
+/// let mut pos = FORGE_WRITE_START;
+/// buffer[pos] = b'0'; pos += 1; // MsgType value (Heartbeat)
+/// buffer[pos] = 0x01; pos += 1;
+/// let len = finalize_message(&mut buffer, pos);
+/// assert_eq!(&buffer[..len], b"8=FIX.4.4\x019=0005\x0135=0\x0110=051\x01");
+/// ```
+#[inline(always)]
+pub fn finalize_message(
+    buffer: &mut [u8],
+    message_length: usize,
+) -> usize {
+    update_body_length(buffer, message_length);
+    message_length + write_checksum(buffer, message_length)
+}
+
 /// Get the starting position for writing MsgType value for FIX 4.x versions.
 /// All FIX 4.x versions have the same header length, so this returns the constant.
 ///
@@ -162,6 +315,7 @@ pub fn forge_write_start(_fix_version: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::xorshift;
     use crate::writing::write_tag_and_u32;
 
     #[test]
@@ -322,4 +476,76 @@ mod tests {
         assert_eq!(BODY_LENGTH_VALUE_POS, 12);
         assert_eq!(FORGE_HEADER_LEN, 20);
     }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(b""), 0);
+        assert_eq!(checksum(&[0xFF, 0x02]), 0x01);
+        let msg = b"8=FIX.4.4\x019=0005\x0135=0\x01";
+        let expected = msg
+            .iter()
+            .map(|&b| b as u32)
+            .sum::<u32>()
+            % 256;
+        assert_eq!(checksum(msg) as u32, expected);
+    }
+
+    #[test]
+    fn test_patch_field_with_checksum() {
+        // Random same-length patches anywhere in the body, checked against a full re-sum
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'D';
+        buffer[pos + 1] = 0x01;
+        pos += 2;
+        for tag in 100..140u32 {
+            pos += write_tag_and_u32(&mut buffer, pos, b"58=", tag * 7919);
+        }
+        let len = finalize_message(&mut buffer, pos);
+        let checksum_pos = len - CHECKSUM_FIELD_LEN;
+
+        let mut next = xorshift();
+        for _ in 0..10_000 {
+            let width = (next() % 16) as usize + 1;
+            let offset = FORGE_WRITE_START + (next() as usize % (checksum_pos - FORGE_WRITE_START - width + 1));
+            let mut patch = [0u8; 16];
+            for b in &mut patch[..width] {
+                *b = next() as u8;
+            }
+            let sum = patch_field_with_checksum(&mut buffer, offset, &patch[..width], checksum_pos);
+            assert_eq!(&buffer[offset..offset + width], &patch[..width]);
+            assert_eq!(sum, checksum(&buffer[..checksum_pos]));
+            assert_eq!(
+                crate::reading::read_u16(&buffer[checksum_pos + 3..checksum_pos + 6]),
+                sum as u16
+            );
+            assert_eq!(&buffer[checksum_pos..checksum_pos + 3], b"10=");
+            assert_eq!(buffer[len - 1], 0x01);
+        }
+    }
+
+    #[test]
+    fn test_finalize_message() {
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'0';
+        pos += 1;
+        buffer[pos] = 0x01;
+        pos += 1;
+        pos += write_tag_and_u32(&mut buffer, pos, b"34=", 1);
+
+        let len = finalize_message(&mut buffer, pos);
+        assert_eq!(len, pos + CHECKSUM_FIELD_LEN);
+        assert_eq!(
+            &buffer[..pos],
+            b"8=FIX.4.4\x019=0010\x0135=0\x0134=1\x01"
+        );
+        let sum = checksum(&buffer[..pos]);
+        assert_eq!(&buffer[pos..pos + 3], b"10=");
+        assert_eq!(
+            crate::reading::read_u16(&buffer[pos + 3..pos + 6]),
+            sum as u16
+        );
+        assert_eq!(buffer[len - 1], 0x01);
+    }
 }
@@ -0,0 +1,1151 @@
+//! Generation of typed message structs from a [`Dictionary`].
+//!
+//! [`generate`] emits Rust source with one struct per message and per repeating
+//! group entry, each with a `write(&self, buf, offset)` encoder built on the
+//! `write_tag_and_*` helpers and a `read(&[u8]) -> Result<Self, ReadError>`
+//! decoder built on [`TagValueIter`](crate::TagValueIter) and the `read_*`
+//! helpers. Missing required members are reported as
+//! `ReadError::MissingRequiredFields` against a static `META` table on each
+//! struct.
+//!
+//! The output is meant to be written from a build script and pulled in with
+//! `include!`:
+//!
+//! ```ignore
+//! // build.rs
+//! let xml = std::fs::read_to_string("FIX44.xml").unwrap();
+//! let dict = fix44_forge_helpers::Dictionary::parse(&xml).unwrap();
+//! let code = fix44_forge_helpers::generate(&dict, &Default::default()).unwrap();
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("fix44.rs");
+//! std::fs::write(out, code).unwrap();
+//!
+//! // src/lib.rs
+//! pub mod fix44 {
+//!     include!(concat!(env!("OUT_DIR"), "/fix44.rs"));
+//! }
+//! ```
+//!
+//! # Mapping
+//!
+//! - Components are flattened into the message (or group entry) that uses them.
+//!   Fields of an optional component are optional.
+//! - Required fields are plain values, optional fields are `Option`s, and
+//!   repeating groups are `Vec`s of entry structs. Groups with identical
+//!   definitions share one entry struct.
+//! - A `LENGTH` field directly followed by its `DATA`/`XMLDATA` field is folded
+//!   into the data member and written with `write_tag_and_data`.
+//! - `write` emits body fields only; header, BodyLength and CheckSum are left to
+//!   the forge buffer. `read` skips header, trailer and unknown tags.
+//!
+//! Requires the `alloc` feature; the generated code uses `Vec` from the prelude.
+
+use crate::dictionary::{Dictionary, FieldType, Member};
+use crate::errors::{MemberKind, MissingMask};
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+/// Options for [`generate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodegenOptions {
+    /// Path of this crate in the generated `use` declaration
+    pub crate_path: String,
+    /// Names of the messages to generate; empty for all
+    pub messages: Vec<String>,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        CodegenOptions {
+            crate_path: "fix44_forge_helpers".to_owned(),
+            messages: Vec::new(),
+        }
+    }
+}
+
+/// Error produced by [`generate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodegenError {
+    /// A message named in [`CodegenOptions::messages`] is not in the dictionary
+    UnknownMessage {
+        /// Requested message name
+        name: String,
+    },
+    /// A message or group entry has more required members than a `MissingMask` holds
+    TooManyRequiredMembers {
+        /// Message or group name
+        name: String,
+        /// Number of required members
+        count: usize,
+    },
+    /// A field tag does not fit the `u16` used by `ReadError` and the `META` tables
+    TagOutOfRange {
+        /// Field name
+        name: String,
+        /// Field tag
+        tag: u32,
+    },
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            CodegenError::UnknownMessage { name } => {
+                write!(
+                    f,
+                    "Unknown message {name}"
+                )
+            }
+            CodegenError::TooManyRequiredMembers { name, count } => {
+                write!(
+                    f,
+                    "{name} has {count} required members, at most {} are supported",
+                    MissingMask::CAPACITY
+                )
+            }
+            CodegenError::TagOutOfRange { name, tag } => {
+                write!(
+                    f,
+                    "Field {name} has tag {tag} above {}",
+                    u16::MAX
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for CodegenError {}
+
+/// Generate Rust source for the messages of `dict`.
+///
+/// # Errors
+/// See [`CodegenError`].
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let dict = Dictionary::parse(r#"
+/// <fix major="4" minor="4">
+///   <messages>
+///     <message name="TestRequest" msgtype="1" msgcat="admin">
+///       <field name="TestReqID" required="Y"/>
+///     </message>
+///   </messages>
+///   <fields><field number="112" name="TestReqID" type="STRING"/></fields>
+/// </fix>"#).unwrap();
+///
+/// let code = generate(&dict, &CodegenOptions::default()).unwrap();
+/// assert!(code.contains("pub struct TestRequest<'a>"));
+/// assert!(code.contains("pub test_req_id: &'a str,"));
+/// ```
+pub fn generate(
+    dict: &Dictionary,
+    options: &CodegenOptions,
+) -> Result<String, CodegenError> {
+    let mut messages = Vec::new();
+    if options.messages.is_empty() {
+        messages.extend(dict.messages());
+    } else {
+        for name in &options.messages {
+            let message = dict
+                .message_by_name(name)
+                .ok_or_else(|| CodegenError::UnknownMessage { name: name.clone() })?;
+            messages.push(message);
+        }
+    }
+
+    let mut model = Model::new(dict);
+    for message in &messages {
+        model.collect_groups(&message.members, &message.name);
+    }
+    let mut blocks = Vec::new();
+    for message in &messages {
+        let mut block = model.block(
+            &message.name,
+            &message.members,
+            &message.name,
+        )?;
+        block.msg_type = Some(message.msg_type.clone());
+        blocks.push(block);
+    }
+    let mut index = 0;
+    while index < model.entries.len() {
+        // Entry blocks may register further nested entries while being built
+        let (name, members, owner) = {
+            let entry = &model.entries[index];
+            (
+                entry.struct_name.clone(),
+                entry.members.clone(),
+                entry.group_name.clone(),
+            )
+        };
+        let mut block = model.block(&name, &members, &owner)?;
+        block.delimiter = block
+            .items
+            .first()
+            .map(Item::tag);
+        block.group_name = Some(owner);
+        blocks.push(block);
+        index += 1;
+    }
+
+    let borrowed = borrowed_blocks(&blocks);
+    let mut out = String::new();
+    emit_file(&mut out, options, &blocks, &borrowed).expect("writing to a String cannot fail");
+    Ok(out)
+}
+
+// -----------------------------------------------------------------------------------------
+// Intermediate model
+// -----------------------------------------------------------------------------------------
+
+/// How a field value is represented and converted.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Repr {
+    Str,
+    Bytes,
+    U32,
+    I64,
+    F64,
+    Char,
+    Bool,
+}
+
+impl Repr {
+    fn of(field_type: FieldType) -> Repr {
+        match field_type {
+            FieldType::Length
+            | FieldType::NumInGroup
+            | FieldType::SeqNum
+            | FieldType::TagNum
+            | FieldType::DayOfMonth => Repr::U32,
+            FieldType::Int => Repr::I64,
+            t if t.is_float() => Repr::F64,
+            FieldType::Char => Repr::Char,
+            FieldType::Boolean => Repr::Bool,
+            t if t.is_data() => Repr::Bytes,
+            _ => Repr::Str,
+        }
+    }
+
+    fn rust_type(self) -> &'static str {
+        match self {
+            Repr::Str => "&'a str",
+            Repr::Bytes => "&'a [u8]",
+            Repr::U32 => "u32",
+            Repr::I64 => "i64",
+            Repr::F64 => "f64",
+            Repr::Char => "u8",
+            Repr::Bool => "bool",
+        }
+    }
+
+    fn writer(self) -> &'static str {
+        match self {
+            Repr::Str => "write_tag_and_str",
+            Repr::Bytes => "write_tag_and_bytes",
+            Repr::U32 => "write_tag_and_u32",
+            Repr::I64 => "write_tag_and_i64",
+            Repr::F64 => "write_tag_and_f64",
+            Repr::Char => "write_tag_and_char",
+            Repr::Bool => "write_tag_and_bool",
+        }
+    }
+
+    fn borrowed(self) -> bool {
+        matches!(self, Repr::Str | Repr::Bytes)
+    }
+}
+
+struct FieldItem {
+    name: String,
+    ident: String,
+    tag: u32,
+    repr: Repr,
+    required: bool,
+    /// Tag of the folded LENGTH field for data fields
+    len_tag: Option<u32>,
+    len_name: String,
+}
+
+struct GroupItem {
+    name: String,
+    ident: String,
+    count_tag: u32,
+    required: bool,
+    entry: String,
+}
+
+enum Item {
+    Field(FieldItem),
+    Group(GroupItem),
+}
+
+impl Item {
+    fn tag(&self) -> u32 {
+        match self {
+            Item::Field(f) => f.tag,
+            Item::Group(g) => g.count_tag,
+        }
+    }
+
+    fn ident(&self) -> &str {
+        match self {
+            Item::Field(f) => &f.ident,
+            Item::Group(g) => &g.ident,
+        }
+    }
+
+    fn required(&self) -> bool {
+        match self {
+            Item::Field(f) => f.required,
+            Item::Group(g) => g.required,
+        }
+    }
+}
+
+/// One `META` row: missing if any of `items` is absent.
+struct MetaEntry {
+    name: String,
+    tag: u32,
+    kind: MemberKind,
+    items: Vec<usize>,
+}
+
+struct Block {
+    struct_name: String,
+    msg_type: Option<String>,
+    group_name: Option<String>,
+    delimiter: Option<u32>,
+    items: Vec<Item>,
+    meta: Vec<MetaEntry>,
+}
+
+/// A distinct repeating group definition with its entry struct name.
+struct GroupEntry {
+    group_name: String,
+    members: Vec<Member>,
+    struct_name: String,
+}
+
+struct Model<'d> {
+    dict: &'d Dictionary,
+    /// group name -> distinct definitions (members, owner)
+    definitions: BTreeMap<String, Vec<(Vec<Member>, String)>>,
+    entries: Vec<GroupEntry>,
+}
+
+impl<'d> Model<'d> {
+    fn new(dict: &'d Dictionary) -> Self {
+        Model {
+            dict,
+            definitions: BTreeMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Record every group definition reachable from `members` (first pass, for naming).
+    fn collect_groups(
+        &mut self,
+        members: &[Member],
+        owner: &str,
+    ) {
+        for member in members {
+            match member.kind {
+                MemberKind::Field => {}
+                MemberKind::Component => {
+                    let component = self
+                        .dict
+                        .component(&member.name)
+                        .expect("dictionary checks component references");
+                    self.collect_groups(&component.members, &component.name);
+                }
+                MemberKind::Group => {
+                    let defs = self
+                        .definitions
+                        .entry(member.name.clone())
+                        .or_default();
+                    if !defs
+                        .iter()
+                        .any(|(m, _)| *m == member.members)
+                    {
+                        defs.push((member.members.clone(), owner.to_owned()));
+                    }
+                    self.collect_groups(&member.members, &member.name);
+                }
+            }
+        }
+    }
+
+    /// Entry struct name for a group, registering the entry on first use.
+    fn entry_struct(
+        &mut self,
+        group: &Member,
+    ) -> String {
+        if let Some(entry) = self
+            .entries
+            .iter()
+            .find(|e| e.group_name == group.name && e.members == group.members)
+        {
+            return entry.struct_name.clone();
+        }
+        let defs = &self.definitions[&group.name];
+        let struct_name = if defs.len() == 1 {
+            format!("{}Entry", group.name)
+        } else {
+            let (_, owner) = defs
+                .iter()
+                .find(|(m, _)| *m == group.members)
+                .expect("group collected in first pass");
+            format!("{}{}Entry", owner, group.name)
+        };
+        self.entries.push(GroupEntry {
+            group_name: group.name.clone(),
+            members: group.members.clone(),
+            struct_name: struct_name.clone(),
+        });
+        struct_name
+    }
+
+    fn block(
+        &mut self,
+        struct_name: &str,
+        members: &[Member],
+        name: &str,
+    ) -> Result<Block, CodegenError> {
+        let mut block = Block {
+            struct_name: struct_name.to_owned(),
+            msg_type: None,
+            group_name: None,
+            delimiter: None,
+            items: Vec::new(),
+            meta: Vec::new(),
+        };
+        let mut seen = BTreeSet::new();
+        let required = self.flatten(members, true, &mut block.items, &mut seen)?;
+        for (member, items) in members.iter().zip(required) {
+            if !member.required || items.is_empty() {
+                continue;
+            }
+            let tag = block.items[items[0]].tag();
+            block.meta.push(MetaEntry {
+                name: member.name.clone(),
+                tag,
+                kind: member.kind,
+                items,
+            });
+        }
+        if block.meta.len() > MissingMask::CAPACITY {
+            return Err(CodegenError::TooManyRequiredMembers {
+                name: name.to_owned(),
+                count: block.meta.len(),
+            });
+        }
+        Ok(block)
+    }
+
+    /// Flatten `members` into `items`. Returns, per member, the indices of the
+    /// required items it contributed.
+    fn flatten(
+        &mut self,
+        members: &[Member],
+        parent_required: bool,
+        items: &mut Vec<Item>,
+        seen: &mut BTreeSet<u32>,
+    ) -> Result<Vec<Vec<usize>>, CodegenError> {
+        let dict = self.dict;
+        let mut out = Vec::with_capacity(members.len());
+        let mut pending_len: Option<(&Member, u32)> = None;
+        for (i, member) in members.iter().enumerate() {
+            let required = parent_required && member.required;
+            let mut contributed = Vec::new();
+            match member.kind {
+                MemberKind::Field => {
+                    let field = dict
+                        .field_by_name(&member.name)
+                        .expect("dictionary checks field references");
+                    check_tag(&field.name, field.number)?;
+                    let next_is_data = members
+                        .get(i + 1)
+                        .filter(|m| m.kind == MemberKind::Field)
+                        .and_then(|m| dict.field_by_name(&m.name))
+                        .is_some_and(|f| f.field_type.is_data());
+                    if field.field_type == FieldType::Length && next_is_data {
+                        pending_len = Some((member, field.number));
+                    } else if seen.insert(field.number) {
+                        let len = pending_len.take();
+                        let required = required
+                            || len.is_some_and(|(m, _)| parent_required && m.required);
+                        items.push(Item::Field(FieldItem {
+                            name: field.name.clone(),
+                            ident: snake_case(&field.name),
+                            tag: field.number,
+                            repr: Repr::of(field.field_type),
+                            required,
+                            len_tag: len.map(|(_, tag)| tag),
+                            len_name: len
+                                .map(|(m, _)| m.name.clone())
+                                .unwrap_or_default(),
+                        }));
+                        if required {
+                            contributed.push(items.len() - 1);
+                        }
+                    }
+                }
+                MemberKind::Component => {
+                    let component = dict
+                        .component(&member.name)
+                        .expect("dictionary checks component references");
+                    let nested = self.flatten(&component.members, required, items, seen)?;
+                    contributed.extend(nested.into_iter().flatten());
+                }
+                MemberKind::Group => {
+                    let count = dict
+                        .field_by_name(&member.name)
+                        .expect("dictionary checks field references");
+                    check_tag(&count.name, count.number)?;
+                    if seen.insert(count.number) {
+                        let entry = self.entry_struct(member);
+                        items.push(Item::Group(GroupItem {
+                            name: member.name.clone(),
+                            ident: snake_case(&member.name),
+                            count_tag: count.number,
+                            required,
+                            entry,
+                        }));
+                        if required {
+                            contributed.push(items.len() - 1);
+                        }
+                    }
+                }
+            }
+            out.push(contributed);
+        }
+        Ok(out)
+    }
+}
+
+fn check_tag(
+    name: &str,
+    tag: u32,
+) -> Result<(), CodegenError> {
+    if tag > u16::MAX as u32 {
+        return Err(CodegenError::TagOutOfRange {
+            name: name.to_owned(),
+            tag,
+        });
+    }
+    Ok(())
+}
+
+/// Struct names whose fields borrow from the message (and so carry `'a`).
+fn borrowed_blocks(blocks: &[Block]) -> BTreeSet<String> {
+    let mut borrowed = BTreeSet::new();
+    // Entries only refer to entries, so iterate to a fixpoint
+    loop {
+        let before = borrowed.len();
+        for block in blocks {
+            let borrows = block
+                .items
+                .iter()
+                .any(|item| match item {
+                    Item::Field(f) => f.repr.borrowed(),
+                    Item::Group(g) => borrowed.contains(&g.entry),
+                });
+            if borrows {
+                borrowed.insert(block.struct_name.clone());
+            }
+        }
+        if borrowed.len() == before {
+            return borrowed;
+        }
+    }
+}
+
+/// `ClOrdID` -> `cl_ord_id`, `NoPartyIDs` -> `no_party_ids`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1).copied();
+            // A lone trailing `s` after an acronym is a plural (`IDs`), not a new word
+            let plural = next == Some('s')
+                && chars
+                    .get(i + 2)
+                    .is_none_or(|c| c.is_ascii_uppercase());
+            let boundary = prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase()
+                    && next.is_some_and(|n| n.is_ascii_lowercase())
+                    && !plural);
+            if boundary {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    // Strict and reserved keywords of every edition; `self`, `super` and `crate`
+    // cannot be raw identifiers (`Self` lowercases to `self`)
+    const KEYWORDS: [&str; 48] = [
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+        "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+        "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override",
+        "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type",
+        "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    if matches!(out.as_str(), "self" | "super" | "crate") {
+        out.push('_');
+    } else if KEYWORDS.contains(&out.as_str()) {
+        out.insert_str(0, "r#");
+    }
+    out
+}
+
+// -----------------------------------------------------------------------------------------
+// Emission
+// -----------------------------------------------------------------------------------------
+
+fn emit_file(
+    out: &mut String,
+    options: &CodegenOptions,
+    blocks: &[Block],
+    borrowed: &BTreeSet<String>,
+) -> fmt::Result {
+    writeln!(out, "// @generated by fix44_forge_helpers::generate. Do not edit.")?;
+    writeln!(out)?;
+    writeln!(out, "#[allow(unused_imports)]")?;
+    writeln!(out, "use {}::{{", options.crate_path)?;
+    writeln!(
+        out,
+        "    MemberKind, MissingMask, ReadError, TagValueIter, read_char, read_f64, read_i64, read_u32,"
+    )?;
+    writeln!(
+        out,
+        "    try_read_bool, try_read_str, write_tag_and_bool, write_tag_and_bytes, write_tag_and_char,"
+    )?;
+    writeln!(
+        out,
+        "    write_tag_and_data, write_tag_and_f64, write_tag_and_i64, write_tag_and_str,"
+    )?;
+    writeln!(out, "    write_tag_and_u32,")?;
+    writeln!(out, "}};")?;
+    for block in blocks {
+        writeln!(out)?;
+        emit_block(out, block, borrowed)?;
+    }
+    Ok(())
+}
+
+fn emit_block(
+    out: &mut String,
+    block: &Block,
+    borrowed: &BTreeSet<String>,
+) -> fmt::Result {
+    let name = &block.struct_name;
+    let has_lifetime = borrowed.contains(name);
+    let generics = if has_lifetime { "<'a>" } else { "" };
+    let iter_lifetime = if has_lifetime { "'a" } else { "'_" };
+    let msg_lifetime = if has_lifetime { "&'a [u8]" } else { "&[u8]" };
+
+    // Struct
+    match (&block.msg_type, &block.group_name) {
+        (Some(msg_type), _) => writeln!(out, "/// {name} (MsgType = {msg_type}).")?,
+        (None, Some(group)) => writeln!(out, "/// One entry of the {group} repeating group.")?,
+        (None, None) => {}
+    }
+    writeln!(out, "#[derive(Debug, Clone, PartialEq, Default)]")?;
+    if block.items.is_empty() {
+        writeln!(out, "pub struct {name} {{}}")?;
+    } else {
+        writeln!(out, "pub struct {name}{generics} {{")?;
+        for item in &block.items {
+            match item {
+                Item::Field(f) => {
+                    match f.len_tag {
+                        Some(len_tag) => writeln!(
+                            out,
+                            "    /// {}({}), length in {}({len_tag})",
+                            f.name, f.tag, f.len_name
+                        )?,
+                        None => writeln!(out, "    /// {}({})", f.name, f.tag)?,
+                    }
+                    let ty = f.repr.rust_type();
+                    if f.required {
+                        writeln!(out, "    pub {}: {ty},", f.ident)?;
+                    } else {
+                        writeln!(out, "    pub {}: Option<{ty}>,", f.ident)?;
+                    }
+                }
+                Item::Group(g) => {
+                    writeln!(out, "    /// {}({}) entries", g.name, g.count_tag)?;
+                    let entry_generics = if borrowed.contains(&g.entry) {
+                        "<'a>"
+                    } else {
+                        ""
+                    };
+                    writeln!(out, "    pub {}: Vec<{}{entry_generics}>,", g.ident, g.entry)?;
+                }
+            }
+        }
+        writeln!(out, "}}")?;
+    }
+    writeln!(out)?;
+
+    // Constants
+    if has_lifetime {
+        writeln!(out, "impl<'a> {name}<'a> {{")?;
+    } else {
+        writeln!(out, "impl {name} {{")?;
+    }
+    if let Some(msg_type) = &block.msg_type {
+        writeln!(out, "    /// MsgType(35) value.")?;
+        writeln!(out, "    pub const MSG_TYPE: &'static str = {msg_type:?};")?;
+        writeln!(out)?;
+    }
+    if let Some(delimiter) = block.delimiter {
+        writeln!(out, "    /// First tag of every entry.")?;
+        writeln!(out, "    pub const DELIMITER: u32 = {delimiter};")?;
+        writeln!(out)?;
+    }
+    writeln!(
+        out,
+        "    /// Required members, indexed like the `MissingRequiredFields` mask."
+    )?;
+    if block.meta.is_empty() {
+        writeln!(
+            out,
+            "    pub const META: &'static [(&'static str, u16, MemberKind)] = &[];"
+        )?;
+    } else {
+        writeln!(
+            out,
+            "    pub const META: &'static [(&'static str, u16, MemberKind)] = &["
+        )?;
+        for meta in &block.meta {
+            let kind = match meta.kind {
+                MemberKind::Field => "Field",
+                MemberKind::Component => "Component",
+                MemberKind::Group => "Group",
+            };
+            writeln!(
+                out,
+                "        ({:?}, {}, MemberKind::{kind}),",
+                meta.name, meta.tag
+            )?;
+        }
+        writeln!(out, "    ];")?;
+    }
+    writeln!(out)?;
+
+    emit_write(out, block)?;
+    writeln!(out)?;
+    if block.delimiter.is_some() {
+        emit_read_group(out, iter_lifetime)?;
+        writeln!(out)?;
+        emit_read_entry(out, block, iter_lifetime)?;
+    } else {
+        emit_read(out, block, msg_lifetime)?;
+    }
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+fn emit_write(
+    out: &mut String,
+    block: &Block,
+) -> fmt::Result {
+    writeln!(
+        out,
+        "    /// Write the fields at `offset` (after MsgType for messages); returns bytes written."
+    )?;
+    writeln!(
+        out,
+        "    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {{"
+    )?;
+    if block.items.is_empty() {
+        writeln!(out, "        let _ = (buf, offset);")?;
+        writeln!(out, "        0")?;
+        writeln!(out, "    }}")?;
+        return Ok(());
+    }
+    writeln!(out, "        let mut pos = offset;")?;
+    for item in &block.items {
+        match item {
+            Item::Field(f) => {
+                let call = |value: &str| match f.len_tag {
+                    Some(len_tag) => format!(
+                        "write_tag_and_data(buf, pos, b\"{len_tag}=\", b\"{}=\", {value})",
+                        f.tag
+                    ),
+                    None => format!(
+                        "{}(buf, pos, b\"{}=\", {value})",
+                        f.repr.writer(),
+                        f.tag
+                    ),
+                };
+                if f.required {
+                    writeln!(
+                        out,
+                        "        pos += {};",
+                        call(&format!("self.{}", f.ident))
+                    )?;
+                } else {
+                    writeln!(out, "        if let Some(value) = self.{} {{", f.ident)?;
+                    writeln!(out, "            pos += {};", call("value"))?;
+                    writeln!(out, "        }}")?;
+                }
+            }
+            Item::Group(g) => {
+                writeln!(out, "        if !self.{}.is_empty() {{", g.ident)?;
+                writeln!(
+                    out,
+                    "            pos += write_tag_and_u32(buf, pos, b\"{}=\", self.{}.len() as u32);",
+                    g.count_tag, g.ident
+                )?;
+                writeln!(out, "            for entry in &self.{} {{", g.ident)?;
+                writeln!(out, "                pos += entry.write(buf, pos);")?;
+                writeln!(out, "            }}")?;
+                writeln!(out, "        }}")?;
+            }
+        }
+    }
+    writeln!(out, "        pos - offset")?;
+    writeln!(out, "    }}")?;
+    Ok(())
+}
+
+/// Read expression for a field value.
+fn read_expr(f: &FieldItem) -> String {
+    match f.repr {
+        Repr::Str => format!("try_read_str(value, {:?}, {})?", f.name, f.tag),
+        Repr::Bytes => "value".to_owned(),
+        Repr::U32 => "read_u32(value)".to_owned(),
+        Repr::I64 => "read_i64(value)".to_owned(),
+        Repr::F64 => "read_f64(value)".to_owned(),
+        Repr::Char => "read_char(value)".to_owned(),
+        Repr::Bool => format!("try_read_bool(value, {:?}, {})?", f.name, f.tag),
+    }
+}
+
+/// `let mut x = None;` for every item.
+fn emit_slots(
+    out: &mut String,
+    block: &Block,
+    indent: &str,
+) -> fmt::Result {
+    for item in &block.items {
+        writeln!(out, "{indent}let mut {} = None;", item.ident())?;
+    }
+    Ok(())
+}
+
+/// `match tag { ... }` storing each item, rejecting repeats.
+fn emit_match(
+    out: &mut String,
+    block: &Block,
+    indent: &str,
+    iter: &str,
+) -> fmt::Result {
+    if block.items.len() == 1 {
+        writeln!(out, "{indent}#[allow(clippy::single_match)]")?;
+    }
+    writeln!(out, "{indent}match tag {{")?;
+    for item in &block.items {
+        writeln!(out, "{indent}    {} => {{", item.tag())?;
+        writeln!(out, "{indent}        if {}.is_some() {{", item.ident())?;
+        writeln!(
+            out,
+            "{indent}            return Err(ReadError::DuplicateTag {{ offset, tag }});"
+        )?;
+        writeln!(out, "{indent}        }}")?;
+        match item {
+            Item::Field(f) => {
+                writeln!(out, "{indent}        {} = Some({});", f.ident, read_expr(f))?;
+            }
+            Item::Group(g) => {
+                writeln!(
+                    out,
+                    "{indent}        {} = Some({}::read_group({iter}, offset, tag, read_u32(value))?);",
+                    g.ident, g.entry
+                )?;
+            }
+        }
+        writeln!(out, "{indent}    }}")?;
+    }
+    writeln!(out, "{indent}    _ => {{}}")?;
+    writeln!(out, "{indent}}}")?;
+    Ok(())
+}
+
+/// Missing-member check and struct construction.
+fn emit_finish(
+    out: &mut String,
+    block: &Block,
+) -> fmt::Result {
+    if !block.meta.is_empty() {
+        writeln!(out, "        let mut missing = MissingMask::new();")?;
+        for (i, meta) in block.meta.iter().enumerate() {
+            let cond = meta
+                .items
+                .iter()
+                .map(|&item| format!("{}.is_none()", block.items[item].ident()))
+                .collect::<Vec<_>>()
+                .join(" || ");
+            writeln!(out, "        if {cond} {{")?;
+            writeln!(out, "            missing.set({i});")?;
+            writeln!(out, "        }}")?;
+        }
+        writeln!(out, "        if !missing.is_empty() {{")?;
+        writeln!(out, "            return Err(ReadError::MissingRequiredFields {{")?;
+        writeln!(out, "                missing_mask: missing,")?;
+        writeln!(out, "                meta: Self::META,")?;
+        writeln!(out, "                tag: None,")?;
+        writeln!(out, "            }});")?;
+        writeln!(out, "        }}")?;
+    }
+    if block.items.is_empty() {
+        writeln!(out, "        Ok(Self {{}})")?;
+        return Ok(());
+    }
+    writeln!(out, "        Ok(Self {{")?;
+    for item in &block.items {
+        let ident = item.ident();
+        if matches!(item, Item::Group(_)) || item.required() {
+            writeln!(out, "            {ident}: {ident}.unwrap_or_default(),")?;
+        } else {
+            writeln!(out, "            {ident},")?;
+        }
+    }
+    writeln!(out, "        }})")?;
+    Ok(())
+}
+
+fn emit_read(
+    out: &mut String,
+    block: &Block,
+    msg_lifetime: &str,
+) -> fmt::Result {
+    writeln!(
+        out,
+        "    /// Read the message from `msg`, skipping header, trailer and unknown tags."
+    )?;
+    writeln!(
+        out,
+        "    pub fn read(msg: {msg_lifetime}) -> Result<Self, ReadError> {{"
+    )?;
+    writeln!(out, "        let mut it = TagValueIter::new(msg);")?;
+    if block.items.is_empty() {
+        writeln!(out, "        while it.try_next()?.is_some() {{}}")?;
+        emit_finish(out, block)?;
+        writeln!(out, "    }}")?;
+        return Ok(());
+    }
+    emit_slots(out, block, "        ")?;
+    writeln!(out, "        loop {{")?;
+    writeln!(out, "            let offset = it.position();")?;
+    writeln!(out, "            let Some((tag, value)) = it.try_next()? else {{")?;
+    writeln!(out, "                break;")?;
+    writeln!(out, "            }};")?;
+    emit_match(out, block, "            ", "&mut it")?;
+    writeln!(out, "        }}")?;
+    emit_finish(out, block)?;
+    writeln!(out, "    }}")?;
+    Ok(())
+}
+
+fn emit_read_group(
+    out: &mut String,
+    iter_lifetime: &str,
+) -> fmt::Result {
+    writeln!(
+        out,
+        "    /// Read the entries following a NumInGroup field at `count_offset`,"
+    )?;
+    writeln!(out, "    /// checking them against the declared `count`.")?;
+    writeln!(out, "    pub fn read_group(")?;
+    writeln!(out, "        it: &mut TagValueIter<{iter_lifetime}>,")?;
+    writeln!(out, "        count_offset: usize,")?;
+    writeln!(out, "        count_tag: u32,")?;
+    writeln!(out, "        count: u32,")?;
+    writeln!(out, "    ) -> Result<Vec<Self>, ReadError> {{")?;
+    writeln!(out, "        let mut entries = Vec::new();")?;
+    writeln!(
+        out,
+        "        while let Some((Self::DELIMITER, _)) = it.clone().try_next()? {{"
+    )?;
+    writeln!(out, "            entries.push(Self::read_entry(it)?);")?;
+    writeln!(out, "        }}")?;
+    writeln!(out, "        if entries.len() != count as usize {{")?;
+    writeln!(out, "            return Err(ReadError::GroupCountMismatch {{")?;
+    writeln!(out, "                offset: count_offset,")?;
+    writeln!(out, "                tag: count_tag,")?;
+    writeln!(out, "                expected: count,")?;
+    writeln!(out, "                actual: entries.len() as u32,")?;
+    writeln!(out, "            }});")?;
+    writeln!(out, "        }}")?;
+    writeln!(out, "        Ok(entries)")?;
+    writeln!(out, "    }}")?;
+    Ok(())
+}
+
+fn emit_read_entry(
+    out: &mut String,
+    block: &Block,
+    iter_lifetime: &str,
+) -> fmt::Result {
+    // Tags that belong to an entry: stored items plus folded length fields
+    let mut member_tags = Vec::new();
+    for item in &block.items {
+        if let Item::Field(FieldItem {
+            len_tag: Some(len_tag),
+            ..
+        }) = item
+        {
+            member_tags.push(len_tag.to_owned());
+        }
+        member_tags.push(item.tag());
+    }
+    let member_tags = member_tags
+        .iter()
+        .map(|tag| format!("{tag}"))
+        .collect::<Vec<_>>()
+        .join(" | ");
+
+    writeln!(
+        out,
+        "    /// Read one entry, stopping before the next delimiter or a non-member tag."
+    )?;
+    writeln!(
+        out,
+        "    fn read_entry(it: &mut TagValueIter<{iter_lifetime}>) -> Result<Self, ReadError> {{"
+    )?;
+    emit_slots(out, block, "        ")?;
+    writeln!(out, "        let mut first = true;")?;
+    writeln!(out, "        loop {{")?;
+    writeln!(out, "            let mut peek = it.clone();")?;
+    writeln!(out, "            let offset = peek.position();")?;
+    writeln!(out, "            let Some((tag, value)) = peek.try_next()? else {{")?;
+    writeln!(out, "                break;")?;
+    writeln!(out, "            }};")?;
+    writeln!(
+        out,
+        "            if !matches!(tag, {member_tags}) || (tag == Self::DELIMITER && !first) {{"
+    )?;
+    writeln!(out, "                break;")?;
+    writeln!(out, "            }}")?;
+    writeln!(out, "            *it = peek;")?;
+    writeln!(out, "            first = false;")?;
+    emit_match(out, block, "            ", "it")?;
+    writeln!(out, "        }}")?;
+    emit_finish(out, block)?;
+    writeln!(out, "    }}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snake_case() {
+        let cases = [
+            ("ClOrdID", "cl_ord_id"),
+            ("NoPartyIDs", "no_party_ids"),
+            ("SecurityIDSource", "security_id_source"),
+            ("HeartBtInt", "heart_bt_int"),
+            ("MsgSeqNum", "msg_seq_num"),
+            ("PtysSubGrp", "ptys_sub_grp"),
+            ("NoSecurityAltID", "no_security_alt_id"),
+            ("Price2", "price2"),
+            ("Type", "r#type"),
+            ("Yield", "r#yield"),
+            ("Gen", "r#gen"),
+            ("Try", "r#try"),
+            ("Self", "self_"),
+            ("Super", "super_"),
+            ("Crate", "crate_"),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(snake_case(name), expected);
+        }
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        let dict = Dictionary::parse(
+            r#"<fix major="4" minor="4">
+                 <messages>
+                   <message name="Big" msgtype="U1"><field name="Huge" required="Y"/></message>
+                 </messages>
+                 <fields><field number="70000" name="Huge" type="INT"/></fields>
+               </fix>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            generate(&dict, &CodegenOptions::default()),
+            Err(CodegenError::TagOutOfRange {
+                name: "Huge".into(),
+                tag: 70000,
+            })
+        );
+        let options = CodegenOptions {
+            messages: alloc::vec!["Missing".into()],
+            ..CodegenOptions::default()
+        };
+        assert_eq!(
+            generate(&dict, &options),
+            Err(CodegenError::UnknownMessage {
+                name: "Missing".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_shapes() {
+        let dict = Dictionary::parse(
+            r#"<fix major="4" minor="4">
+                 <messages>
+                   <message name="Logon" msgtype="A" msgcat="admin">
+                     <field name="HeartBtInt" required="Y"/>
+                     <field name="RawDataLength" required="N"/>
+                     <field name="RawData" required="N"/>
+                   </message>
+                   <message name="Quote" msgtype="S">
+                     <component name="Inst" required="Y"/>
+                     <group name="NoLegs" required="N"><field name="LegSymbol" required="Y"/></group>
+                   </message>
+                 </messages>
+                 <components>
+                   <component name="Inst"><field name="Symbol" required="Y"/></component>
+                 </components>
+                 <fields>
+                   <field number="55" name="Symbol" type="STRING"/>
+                   <field number="95" name="RawDataLength" type="LENGTH"/>
+                   <field number="96" name="RawData" type="DATA"/>
+                   <field number="108" name="HeartBtInt" type="INT"/>
+                   <field number="555" name="NoLegs" type="NUMINGROUP"/>
+                   <field number="600" name="LegSymbol" type="STRING"/>
+                 </fields>
+               </fix>"#,
+        )
+        .unwrap();
+        let code = generate(&dict, &CodegenOptions::default()).unwrap();
+        assert!(code.contains("pub heart_bt_int: i64,"));
+        assert!(code.contains("/// RawData(96), length in RawDataLength(95)"));
+        assert!(code.contains("pub raw_data: Option<&'a [u8]>,"));
+        assert!(code.contains("write_tag_and_data(buf, pos, b\"95=\", b\"96=\", value)"));
+        assert!(!code.contains("raw_data_length"));
+        assert!(code.contains("(\"Inst\", 55, MemberKind::Component),"));
+        assert!(code.contains("pub no_legs: Vec<NoLegsEntry<'a>>,"));
+        assert!(code.contains("pub const DELIMITER: u32 = 600;"));
+        assert!(code.contains("if !matches!(tag, 600) || (tag == Self::DELIMITER && !first) {"));
+    }
+}
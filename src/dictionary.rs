@@ -0,0 +1,1548 @@
+//! QuickFIX-style XML data dictionary loader.
+//!
+//! This module parses `FIX44.xml`-style data dictionaries (as shipped with
+//! QuickFIX/J, QuickFIX/n and QuickFIX) into an in-memory [`Dictionary`]: field
+//! definitions with their types and enumerated values, components, repeating
+//! groups, messages, and the standard header and trailer.
+//!
+//! The same model backs runtime validation and code generation, so both agree
+//! on one source of truth. Parsing uses a small built-in XML reader (elements,
+//! attributes, comments, processing instructions and the predefined entities);
+//! no external XML dependency is pulled in.
+//!
+//! Requires the `alloc` feature.
+//!
+//! # Example
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let xml = r#"
+//! <fix type="FIX" major="4" minor="4" servicepack="0">
+//!   <header><field name="MsgType" required="Y"/></header>
+//!   <trailer/>
+//!   <messages>
+//!     <message name="Heartbeat" msgtype="0" msgcat="admin">
+//!       <field name="TestReqID" required="N"/>
+//!     </message>
+//!   </messages>
+//!   <components/>
+//!   <fields>
+//!     <field number="35" name="MsgType" type="STRING"/>
+//!     <field number="112" name="TestReqID" type="STRING"/>
+//!   </fields>
+//! </fix>"#;
+//!
+//! let dict = Dictionary::parse(xml).unwrap();
+//! assert_eq!(dict.begin_string(), "FIX.4.4");
+//! let heartbeat = dict.message_by_type("0").unwrap();
+//! assert_eq!(heartbeat.name, "Heartbeat");
+//! assert_eq!(dict.field_by_name("TestReqID").unwrap().number, 112);
+//! ```
+
+use crate::errors::MemberKind;
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// Maximum element nesting accepted by the XML reader.
+const MAX_XML_DEPTH: usize = 64;
+
+/// Error produced while loading a data dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictionaryError {
+    /// Malformed XML
+    Xml {
+        /// Byte offset in the document
+        offset: usize,
+        /// Description of what went wrong
+        msg: &'static str,
+    },
+    /// Element not allowed at this position of the dictionary
+    UnexpectedElement {
+        /// Byte offset of the element
+        offset: usize,
+        /// Element name
+        element: String,
+    },
+    /// Required attribute is absent
+    MissingAttribute {
+        /// Byte offset of the element
+        offset: usize,
+        /// Element name
+        element: String,
+        /// Attribute name
+        attribute: &'static str,
+    },
+    /// Attribute value cannot be interpreted
+    InvalidAttribute {
+        /// Byte offset of the element
+        offset: usize,
+        /// Element name
+        element: String,
+        /// Attribute name
+        attribute: &'static str,
+        /// Offending value
+        value: String,
+    },
+    /// Field declares a type this crate does not know
+    UnknownFieldType {
+        /// Field name
+        field: String,
+        /// Declared type
+        field_type: String,
+    },
+    /// Field name or number defined more than once
+    DuplicateField {
+        /// Field name
+        name: String,
+        /// Field number
+        number: u32,
+    },
+    /// Component defined more than once
+    DuplicateComponent {
+        /// Component name
+        name: String,
+    },
+    /// Message name or MsgType defined more than once
+    DuplicateMessage {
+        /// Message name
+        name: String,
+        /// MsgType value
+        msg_type: String,
+    },
+    /// Member references a field missing from `<fields>`
+    UndefinedField {
+        /// Referenced field name
+        name: String,
+        /// Message, component or section containing the reference
+        parent: String,
+    },
+    /// Member references a component missing from `<components>`
+    UndefinedComponent {
+        /// Referenced component name
+        name: String,
+        /// Message, component or section containing the reference
+        parent: String,
+    },
+    /// Component contains itself, directly or through other components
+    RecursiveComponent {
+        /// Component name
+        name: String,
+    },
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            DictionaryError::Xml { offset, msg } => {
+                write!(
+                    f,
+                    "XML error at offset {offset}: {msg}"
+                )
+            }
+            DictionaryError::UnexpectedElement { offset, element } => {
+                write!(
+                    f,
+                    "Unexpected element <{element}> at offset {offset}"
+                )
+            }
+            DictionaryError::MissingAttribute {
+                offset,
+                element,
+                attribute,
+            } => {
+                write!(
+                    f,
+                    "Element <{element}> at offset {offset} is missing attribute '{attribute}'"
+                )
+            }
+            DictionaryError::InvalidAttribute {
+                offset,
+                element,
+                attribute,
+                value,
+            } => {
+                write!(
+                    f,
+                    "Element <{element}> at offset {offset} has invalid {attribute}=\"{value}\""
+                )
+            }
+            DictionaryError::UnknownFieldType { field, field_type } => {
+                write!(
+                    f,
+                    "Field {field} has unknown type {field_type}"
+                )
+            }
+            DictionaryError::DuplicateField { name, number } => {
+                write!(
+                    f,
+                    "Duplicate field {name}({number})"
+                )
+            }
+            DictionaryError::DuplicateComponent { name } => {
+                write!(
+                    f,
+                    "Duplicate component {name}"
+                )
+            }
+            DictionaryError::DuplicateMessage { name, msg_type } => {
+                write!(
+                    f,
+                    "Duplicate message {name} (MsgType={msg_type})"
+                )
+            }
+            DictionaryError::UndefinedField { name, parent } => {
+                write!(
+                    f,
+                    "{parent} references undefined field {name}"
+                )
+            }
+            DictionaryError::UndefinedComponent { name, parent } => {
+                write!(
+                    f,
+                    "{parent} references undefined component {name}"
+                )
+            }
+            DictionaryError::RecursiveComponent { name } => {
+                write!(
+                    f,
+                    "Component {name} contains itself"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for DictionaryError {}
+
+/// FIX data type of a field, as named in the dictionary's `type` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    /// `INT`
+    Int,
+    /// `LENGTH`
+    Length,
+    /// `NUMINGROUP`
+    NumInGroup,
+    /// `SEQNUM`
+    SeqNum,
+    /// `TAGNUM`
+    TagNum,
+    /// `DAYOFMONTH`
+    DayOfMonth,
+    /// `FLOAT`
+    Float,
+    /// `QTY`
+    Qty,
+    /// `PRICE`
+    Price,
+    /// `PRICEOFFSET`
+    PriceOffset,
+    /// `AMT`
+    Amt,
+    /// `PERCENTAGE`
+    Percentage,
+    /// `CHAR`
+    Char,
+    /// `BOOLEAN`
+    Boolean,
+    /// `STRING`
+    String,
+    /// `MULTIPLEVALUESTRING` (FIX 4.4 name for space-separated char/string lists)
+    MultipleValueString,
+    /// `MULTIPLECHARVALUE`
+    MultipleCharValue,
+    /// `MULTIPLESTRINGVALUE`
+    MultipleStringValue,
+    /// `CURRENCY`
+    Currency,
+    /// `EXCHANGE`
+    Exchange,
+    /// `COUNTRY`
+    Country,
+    /// `UTCTIMESTAMP`
+    UtcTimestamp,
+    /// `UTCTIMEONLY`
+    UtcTimeOnly,
+    /// `UTCDATEONLY` (`UTCDATE` in older dictionaries)
+    UtcDateOnly,
+    /// `LOCALMKTDATE`
+    LocalMktDate,
+    /// `MONTHYEAR`
+    MonthYear,
+    /// `TZTIMEONLY`
+    TzTimeOnly,
+    /// `TZTIMESTAMP`
+    TzTimestamp,
+    /// `DATA`
+    Data,
+    /// `XMLDATA`
+    XmlData,
+    /// `LANGUAGE`
+    Language,
+}
+
+impl FieldType {
+    /// Dictionary spelling of the type (e.g. `"UTCTIMESTAMP"`).
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            FieldType::Int => "INT",
+            FieldType::Length => "LENGTH",
+            FieldType::NumInGroup => "NUMINGROUP",
+            FieldType::SeqNum => "SEQNUM",
+            FieldType::TagNum => "TAGNUM",
+            FieldType::DayOfMonth => "DAYOFMONTH",
+            FieldType::Float => "FLOAT",
+            FieldType::Qty => "QTY",
+            FieldType::Price => "PRICE",
+            FieldType::PriceOffset => "PRICEOFFSET",
+            FieldType::Amt => "AMT",
+            FieldType::Percentage => "PERCENTAGE",
+            FieldType::Char => "CHAR",
+            FieldType::Boolean => "BOOLEAN",
+            FieldType::String => "STRING",
+            FieldType::MultipleValueString => "MULTIPLEVALUESTRING",
+            FieldType::MultipleCharValue => "MULTIPLECHARVALUE",
+            FieldType::MultipleStringValue => "MULTIPLESTRINGVALUE",
+            FieldType::Currency => "CURRENCY",
+            FieldType::Exchange => "EXCHANGE",
+            FieldType::Country => "COUNTRY",
+            FieldType::UtcTimestamp => "UTCTIMESTAMP",
+            FieldType::UtcTimeOnly => "UTCTIMEONLY",
+            FieldType::UtcDateOnly => "UTCDATEONLY",
+            FieldType::LocalMktDate => "LOCALMKTDATE",
+            FieldType::MonthYear => "MONTHYEAR",
+            FieldType::TzTimeOnly => "TZTIMEONLY",
+            FieldType::TzTimestamp => "TZTIMESTAMP",
+            FieldType::Data => "DATA",
+            FieldType::XmlData => "XMLDATA",
+            FieldType::Language => "LANGUAGE",
+        }
+    }
+
+    /// Parse a dictionary `type` attribute (case-insensitive).
+    pub fn from_name(name: &str) -> Option<FieldType> {
+        const ALL: [FieldType; 31] = [
+            FieldType::Int,
+            FieldType::Length,
+            FieldType::NumInGroup,
+            FieldType::SeqNum,
+            FieldType::TagNum,
+            FieldType::DayOfMonth,
+            FieldType::Float,
+            FieldType::Qty,
+            FieldType::Price,
+            FieldType::PriceOffset,
+            FieldType::Amt,
+            FieldType::Percentage,
+            FieldType::Char,
+            FieldType::Boolean,
+            FieldType::String,
+            FieldType::MultipleValueString,
+            FieldType::MultipleCharValue,
+            FieldType::MultipleStringValue,
+            FieldType::Currency,
+            FieldType::Exchange,
+            FieldType::Country,
+            FieldType::UtcTimestamp,
+            FieldType::UtcTimeOnly,
+            FieldType::UtcDateOnly,
+            FieldType::LocalMktDate,
+            FieldType::MonthYear,
+            FieldType::TzTimeOnly,
+            FieldType::TzTimestamp,
+            FieldType::Data,
+            FieldType::XmlData,
+            FieldType::Language,
+        ];
+        if name.eq_ignore_ascii_case("UTCDATE") {
+            return Some(FieldType::UtcDateOnly);
+        }
+        ALL.into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Integer-valued types (`INT`, `LENGTH`, `NUMINGROUP`, `SEQNUM`, `TAGNUM`, `DAYOFMONTH`).
+    pub const fn is_int(self) -> bool {
+        matches!(
+            self,
+            FieldType::Int
+                | FieldType::Length
+                | FieldType::NumInGroup
+                | FieldType::SeqNum
+                | FieldType::TagNum
+                | FieldType::DayOfMonth
+        )
+    }
+
+    /// Decimal types (`FLOAT`, `QTY`, `PRICE`, `PRICEOFFSET`, `AMT`, `PERCENTAGE`).
+    pub const fn is_float(self) -> bool {
+        matches!(
+            self,
+            FieldType::Float
+                | FieldType::Qty
+                | FieldType::Price
+                | FieldType::PriceOffset
+                | FieldType::Amt
+                | FieldType::Percentage
+        )
+    }
+
+    /// Length-prefixed types that may contain SOH (`DATA`, `XMLDATA`).
+    pub const fn is_data(self) -> bool {
+        matches!(self, FieldType::Data | FieldType::XmlData)
+    }
+
+    /// Space-separated multi-value types.
+    pub const fn is_multi_value(self) -> bool {
+        matches!(
+            self,
+            FieldType::MultipleValueString
+                | FieldType::MultipleCharValue
+                | FieldType::MultipleStringValue
+        )
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One enumerated value of a field (`<value enum="1" description="BUY"/>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumValue {
+    /// Wire value
+    pub value: String,
+    /// Symbolic description
+    pub description: String,
+}
+
+/// Field definition from the `<fields>` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDef {
+    /// Tag number
+    pub number: u32,
+    /// Field name
+    pub name: String,
+    /// Data type
+    pub field_type: FieldType,
+    /// Enumerated values, empty if the field is unrestricted
+    pub values: Vec<EnumValue>,
+}
+
+impl FieldDef {
+    /// Look up an enumerated value by its wire representation.
+    pub fn value(
+        &self,
+        wire: &str,
+    ) -> Option<&EnumValue> {
+        self.values
+            .iter()
+            .find(|v| v.value == wire)
+    }
+
+    /// Whether `wire` is allowed for this field (always true for unrestricted fields).
+    pub fn allows(
+        &self,
+        wire: &[u8],
+    ) -> bool {
+        self.values.is_empty()
+            || self
+                .values
+                .iter()
+                .any(|v| v.value.as_bytes() == wire)
+    }
+}
+
+/// Reference to a field, component or group inside a message, component,
+/// group, header or trailer, in dictionary order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    /// Field or component name; for groups, the NumInGroup field name
+    pub name: String,
+    /// Field, component or group
+    pub kind: MemberKind,
+    /// `required="Y"`
+    pub required: bool,
+    /// Group entry members (empty for fields and components)
+    pub members: Vec<Member>,
+}
+
+/// Component definition from the `<components>` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDef {
+    /// Component name
+    pub name: String,
+    /// Members in dictionary order
+    pub members: Vec<Member>,
+}
+
+/// Message category (`msgcat` attribute).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageCategory {
+    /// Session-level message (`msgcat="admin"`)
+    Admin,
+    /// Application message (`msgcat="app"`)
+    App,
+}
+
+/// Message definition from the `<messages>` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageDef {
+    /// Message name (e.g. `NewOrderSingle`)
+    pub name: String,
+    /// MsgType(35) value (e.g. `D`)
+    pub msg_type: String,
+    /// Admin or application message
+    pub category: MessageCategory,
+    /// Body members in dictionary order
+    pub members: Vec<Member>,
+}
+
+/// In-memory FIX data dictionary.
+///
+/// Built by [`Dictionary::parse`]. All member references are checked while
+/// loading, so every field and component named by a message, component, group,
+/// header or trailer resolves through the lookup methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    fix_type: String,
+    major: u32,
+    minor: u32,
+    service_pack: u32,
+    header: Vec<Member>,
+    trailer: Vec<Member>,
+    fields: Vec<FieldDef>,
+    components: Vec<ComponentDef>,
+    messages: Vec<MessageDef>,
+    fields_by_tag: BTreeMap<u32, usize>,
+    fields_by_name: BTreeMap<String, usize>,
+    components_by_name: BTreeMap<String, usize>,
+    messages_by_type: BTreeMap<String, usize>,
+}
+
+impl Dictionary {
+    /// Parse a QuickFIX-style XML data dictionary.
+    ///
+    /// # Errors
+    /// Returns a [`DictionaryError`] for malformed XML, missing or invalid
+    /// attributes, unknown field types, duplicate definitions and references
+    /// to undefined fields or components.
+    pub fn parse(xml: &str) -> Result<Dictionary, DictionaryError> {
+        let root = XmlReader::new(xml).document()?;
+        Builder::default().build(&root)
+    }
+
+    /// Protocol family from the root `type` attribute (`FIX` or `FIXT`).
+    pub fn fix_type(&self) -> &str {
+        &self.fix_type
+    }
+
+    /// Major protocol version.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Minor protocol version.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Service pack number (0 if absent).
+    pub fn service_pack(&self) -> u32 {
+        self.service_pack
+    }
+
+    /// BeginString(8) value for this dictionary, e.g. `FIX.4.4`.
+    pub fn begin_string(&self) -> String {
+        format!("{}.{}.{}", self.fix_type, self.major, self.minor)
+    }
+
+    /// Standard header members.
+    pub fn header(&self) -> &[Member] {
+        &self.header
+    }
+
+    /// Standard trailer members.
+    pub fn trailer(&self) -> &[Member] {
+        &self.trailer
+    }
+
+    /// All field definitions in dictionary order.
+    pub fn fields(&self) -> &[FieldDef] {
+        &self.fields
+    }
+
+    /// All component definitions in dictionary order.
+    pub fn components(&self) -> &[ComponentDef] {
+        &self.components
+    }
+
+    /// All message definitions in dictionary order.
+    pub fn messages(&self) -> &[MessageDef] {
+        &self.messages
+    }
+
+    /// Look up a field by tag number.
+    pub fn field_by_tag(
+        &self,
+        tag: u32,
+    ) -> Option<&FieldDef> {
+        self.fields_by_tag
+            .get(&tag)
+            .map(|&i| &self.fields[i])
+    }
+
+    /// Look up a field by name.
+    pub fn field_by_name(
+        &self,
+        name: &str,
+    ) -> Option<&FieldDef> {
+        self.fields_by_name
+            .get(name)
+            .map(|&i| &self.fields[i])
+    }
+
+    /// Look up a component by name.
+    pub fn component(
+        &self,
+        name: &str,
+    ) -> Option<&ComponentDef> {
+        self.components_by_name
+            .get(name)
+            .map(|&i| &self.components[i])
+    }
+
+    /// Look up a message by MsgType(35) value.
+    pub fn message_by_type(
+        &self,
+        msg_type: &str,
+    ) -> Option<&MessageDef> {
+        self.messages_by_type
+            .get(msg_type)
+            .map(|&i| &self.messages[i])
+    }
+
+    /// Look up a message by name.
+    pub fn message_by_name(
+        &self,
+        name: &str,
+    ) -> Option<&MessageDef> {
+        self.messages
+            .iter()
+            .find(|m| m.name == name)
+    }
+}
+
+impl FromStr for Dictionary {
+    type Err = DictionaryError;
+
+    fn from_str(xml: &str) -> Result<Dictionary, DictionaryError> {
+        Dictionary::parse(xml)
+    }
+}
+
+// -----------------------------------------------------------------------------------------
+// Dictionary construction from the element tree
+// -----------------------------------------------------------------------------------------
+
+#[derive(Default)]
+struct Builder {
+    header: Vec<Member>,
+    trailer: Vec<Member>,
+    fields: Vec<FieldDef>,
+    components: Vec<ComponentDef>,
+    messages: Vec<MessageDef>,
+    fields_by_tag: BTreeMap<u32, usize>,
+    fields_by_name: BTreeMap<String, usize>,
+    components_by_name: BTreeMap<String, usize>,
+    messages_by_type: BTreeMap<String, usize>,
+}
+
+impl Builder {
+    fn build(
+        mut self,
+        root: &Element<'_>,
+    ) -> Result<Dictionary, DictionaryError> {
+        if root.name != "fix" {
+            return Err(DictionaryError::UnexpectedElement {
+                offset: root.offset,
+                element: root.name.to_owned(),
+            });
+        }
+        let fix_type = root
+            .attr("type")
+            .unwrap_or("FIX")
+            .to_owned();
+        let major = root.u32_attr("major")?;
+        let minor = root.u32_attr("minor")?;
+        let service_pack = match root.attr("servicepack") {
+            Some(_) => root.u32_attr("servicepack")?,
+            None => 0,
+        };
+
+        for section in &root.children {
+            match section.name {
+                "header" => self.header = members(section)?,
+                "trailer" => self.trailer = members(section)?,
+                "fields" => {
+                    for field in section.children_named("field")? {
+                        self.add_field(field)?;
+                    }
+                }
+                "components" => {
+                    for component in section.children_named("component")? {
+                        self.add_component(component)?;
+                    }
+                }
+                "messages" => {
+                    for message in section.children_named("message")? {
+                        self.add_message(message)?;
+                    }
+                }
+                _ => {
+                    return Err(DictionaryError::UnexpectedElement {
+                        offset: section.offset,
+                        element: section.name.to_owned(),
+                    });
+                }
+            }
+        }
+
+        self.check_references()?;
+
+        Ok(Dictionary {
+            fix_type,
+            major,
+            minor,
+            service_pack,
+            header: self.header,
+            trailer: self.trailer,
+            fields: self.fields,
+            components: self.components,
+            messages: self.messages,
+            fields_by_tag: self.fields_by_tag,
+            fields_by_name: self.fields_by_name,
+            components_by_name: self.components_by_name,
+            messages_by_type: self.messages_by_type,
+        })
+    }
+
+    fn add_field(
+        &mut self,
+        el: &Element<'_>,
+    ) -> Result<(), DictionaryError> {
+        let number = el.u32_attr("number")?;
+        let name = el.required_attr("name")?.to_owned();
+        let type_name = el.required_attr("type")?;
+        let field_type =
+            FieldType::from_name(type_name).ok_or_else(|| DictionaryError::UnknownFieldType {
+                field: name.clone(),
+                field_type: type_name.to_owned(),
+            })?;
+        let mut values = Vec::new();
+        for value in el.children_named("value")? {
+            values.push(EnumValue {
+                value: value
+                    .required_attr("enum")?
+                    .to_owned(),
+                description: value
+                    .attr("description")
+                    .unwrap_or("")
+                    .to_owned(),
+            });
+        }
+
+        if self.fields_by_tag.contains_key(&number) || self.fields_by_name.contains_key(&name) {
+            return Err(DictionaryError::DuplicateField { name, number });
+        }
+        let index = self.fields.len();
+        self.fields_by_tag.insert(number, index);
+        self.fields_by_name.insert(name.clone(), index);
+        self.fields.push(FieldDef {
+            number,
+            name,
+            field_type,
+            values,
+        });
+        Ok(())
+    }
+
+    fn add_component(
+        &mut self,
+        el: &Element<'_>,
+    ) -> Result<(), DictionaryError> {
+        let name = el.required_attr("name")?.to_owned();
+        if self.components_by_name.contains_key(&name) {
+            return Err(DictionaryError::DuplicateComponent { name });
+        }
+        let members = members(el)?;
+        self.components_by_name
+            .insert(name.clone(), self.components.len());
+        self.components
+            .push(ComponentDef { name, members });
+        Ok(())
+    }
+
+    fn add_message(
+        &mut self,
+        el: &Element<'_>,
+    ) -> Result<(), DictionaryError> {
+        let name = el.required_attr("name")?.to_owned();
+        let msg_type = el.required_attr("msgtype")?.to_owned();
+        let category = match el.attr("msgcat") {
+            Some("admin") => MessageCategory::Admin,
+            Some("app") | None => MessageCategory::App,
+            Some(other) => {
+                return Err(DictionaryError::InvalidAttribute {
+                    offset: el.offset,
+                    element: el.name.to_owned(),
+                    attribute: "msgcat",
+                    value: other.to_owned(),
+                });
+            }
+        };
+        if self.messages_by_type.contains_key(&msg_type)
+            || self
+                .messages
+                .iter()
+                .any(|m| m.name == name)
+        {
+            return Err(DictionaryError::DuplicateMessage { name, msg_type });
+        }
+        let members = members(el)?;
+        self.messages_by_type
+            .insert(msg_type.clone(), self.messages.len());
+        self.messages.push(MessageDef {
+            name,
+            msg_type,
+            category,
+            members,
+        });
+        Ok(())
+    }
+
+    /// Verify every member reference resolves and no component contains itself.
+    fn check_references(&self) -> Result<(), DictionaryError> {
+        self.check_members(&self.header, "header")?;
+        self.check_members(&self.trailer, "trailer")?;
+        for component in &self.components {
+            self.check_members(&component.members, &component.name)?;
+        }
+        for message in &self.messages {
+            self.check_members(&message.members, &message.name)?;
+        }
+
+        // Depth-first walk of component references; `path` holds the active
+        // chain and `done` the components already proven acyclic, so a
+        // component shared by many others is walked only once
+        let mut path = Vec::new();
+        let mut done = BTreeSet::new();
+        for component in &self.components {
+            self.check_recursion(&component.name, &component.members, &mut path, &mut done)?;
+        }
+        Ok(())
+    }
+
+    fn check_members(
+        &self,
+        members: &[Member],
+        parent: &str,
+    ) -> Result<(), DictionaryError> {
+        for member in members {
+            match member.kind {
+                MemberKind::Field | MemberKind::Group => {
+                    if !self
+                        .fields_by_name
+                        .contains_key(&member.name)
+                    {
+                        return Err(DictionaryError::UndefinedField {
+                            name: member.name.clone(),
+                            parent: parent.to_owned(),
+                        });
+                    }
+                }
+                MemberKind::Component => {
+                    if !self
+                        .components_by_name
+                        .contains_key(&member.name)
+                    {
+                        return Err(DictionaryError::UndefinedComponent {
+                            name: member.name.clone(),
+                            parent: parent.to_owned(),
+                        });
+                    }
+                }
+            }
+            self.check_members(&member.members, parent)?;
+        }
+        Ok(())
+    }
+
+    fn check_recursion<'a>(
+        &'a self,
+        name: &'a str,
+        members: &'a [Member],
+        path: &mut Vec<&'a str>,
+        done: &mut BTreeSet<&'a str>,
+    ) -> Result<(), DictionaryError> {
+        if done.contains(name) {
+            return Ok(());
+        }
+        if path.contains(&name) {
+            return Err(DictionaryError::RecursiveComponent {
+                name: name.to_owned(),
+            });
+        }
+        path.push(name);
+        self.visit_components(members, path, done)?;
+        path.pop();
+        done.insert(name);
+        Ok(())
+    }
+
+    /// Follow component references in `members`, descending into group entries.
+    fn visit_components<'a>(
+        &'a self,
+        members: &'a [Member],
+        path: &mut Vec<&'a str>,
+        done: &mut BTreeSet<&'a str>,
+    ) -> Result<(), DictionaryError> {
+        for member in members {
+            match member.kind {
+                MemberKind::Component => {
+                    let component = &self.components[self.components_by_name[&member.name]];
+                    self.check_recursion(&component.name, &component.members, path, done)?;
+                }
+                MemberKind::Group => self.visit_components(&member.members, path, done)?,
+                MemberKind::Field => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Convert the `<field>`, `<component>` and `<group>` children of an element.
+fn members(el: &Element<'_>) -> Result<Vec<Member>, DictionaryError> {
+    let mut out = Vec::with_capacity(el.children.len());
+    for child in &el.children {
+        let kind = match child.name {
+            "field" => MemberKind::Field,
+            "component" => MemberKind::Component,
+            "group" => MemberKind::Group,
+            _ => {
+                return Err(DictionaryError::UnexpectedElement {
+                    offset: child.offset,
+                    element: child.name.to_owned(),
+                });
+            }
+        };
+        let required = match child.attr("required") {
+            None | Some("N") | Some("n") => false,
+            Some("Y") | Some("y") => true,
+            Some(other) => {
+                return Err(DictionaryError::InvalidAttribute {
+                    offset: child.offset,
+                    element: child.name.to_owned(),
+                    attribute: "required",
+                    value: other.to_owned(),
+                });
+            }
+        };
+        let members = if kind == MemberKind::Group {
+            members(child)?
+        } else {
+            Vec::new()
+        };
+        out.push(Member {
+            name: child
+                .required_attr("name")?
+                .to_owned(),
+            kind,
+            required,
+            members,
+        });
+    }
+    Ok(out)
+}
+
+// -----------------------------------------------------------------------------------------
+// Minimal XML reader
+//
+// Builds a tree of elements with decoded attribute values. Character data is
+// ignored (dictionaries carry everything in attributes); comments, processing
+// instructions, CDATA sections and a DOCTYPE are skipped.
+// -----------------------------------------------------------------------------------------
+
+struct Element<'a> {
+    name: &'a str,
+    offset: usize,
+    attrs: Vec<(&'a str, String)>,
+    children: Vec<Element<'a>>,
+}
+
+impl<'a> Element<'a> {
+    fn attr(
+        &self,
+        name: &str,
+    ) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn required_attr(
+        &self,
+        name: &'static str,
+    ) -> Result<&str, DictionaryError> {
+        self.attr(name)
+            .ok_or_else(|| DictionaryError::MissingAttribute {
+                offset: self.offset,
+                element: self.name.to_owned(),
+                attribute: name,
+            })
+    }
+
+    fn u32_attr(
+        &self,
+        name: &'static str,
+    ) -> Result<u32, DictionaryError> {
+        let value = self.required_attr(name)?;
+        value
+            .parse()
+            .map_err(|_| DictionaryError::InvalidAttribute {
+                offset: self.offset,
+                element: self.name.to_owned(),
+                attribute: name,
+                value: value.to_owned(),
+            })
+    }
+
+    /// Children, all of which must be named `name`.
+    fn children_named(
+        &self,
+        name: &str,
+    ) -> Result<&[Element<'a>], DictionaryError> {
+        match self
+            .children
+            .iter()
+            .find(|c| c.name != name)
+        {
+            Some(other) => Err(DictionaryError::UnexpectedElement {
+                offset: other.offset,
+                element: other.name.to_owned(),
+            }),
+            None => Ok(&self.children),
+        }
+    }
+}
+
+struct XmlReader<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlReader<'a> {
+    fn new(src: &'a str) -> Self {
+        XmlReader { src, pos: 0 }
+    }
+
+    fn err<T>(
+        &self,
+        msg: &'static str,
+    ) -> Result<T, DictionaryError> {
+        Err(DictionaryError::Xml {
+            offset: self.pos,
+            msg,
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip past the next occurrence of `end`.
+    fn skip_past(
+        &mut self,
+        end: &str,
+        msg: &'static str,
+    ) -> Result<(), DictionaryError> {
+        match self.rest().find(end) {
+            Some(i) => {
+                self.pos += i + end.len();
+                Ok(())
+            }
+            None => self.err(msg),
+        }
+    }
+
+    /// Skip a comment, processing instruction, CDATA section or DOCTYPE at the cursor.
+    /// Returns false if the cursor is not at one of those.
+    fn skip_misc(&mut self) -> Result<bool, DictionaryError> {
+        let rest = self.rest();
+        if rest.starts_with("<!--") {
+            self.skip_past("-->", "unterminated comment")?;
+        } else if rest.starts_with("<?") {
+            self.skip_past("?>", "unterminated processing instruction")?;
+        } else if rest.starts_with("<![CDATA[") {
+            self.skip_past("]]>", "unterminated CDATA section")?;
+        } else if rest.starts_with("<!") {
+            self.skip_past(">", "unterminated declaration")?;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn document(mut self) -> Result<Element<'a>, DictionaryError> {
+        // Optional byte order mark
+        if self.rest().starts_with('\u{feff}') {
+            self.pos += '\u{feff}'.len_utf8();
+        }
+        loop {
+            self.skip_whitespace();
+            if !self.skip_misc()? {
+                break;
+            }
+        }
+        if !self.rest().starts_with('<') {
+            return self.err("expected root element");
+        }
+        let root = self.element(0)?;
+        loop {
+            self.skip_whitespace();
+            if !self.skip_misc()? {
+                break;
+            }
+        }
+        if !self.rest().is_empty() {
+            return self.err("content after root element");
+        }
+        Ok(root)
+    }
+
+    fn name(&mut self) -> Result<&'a str, DictionaryError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '=' | '<'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return self.err("expected name");
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// Parse an element starting at `<`.
+    fn element(
+        &mut self,
+        depth: usize,
+    ) -> Result<Element<'a>, DictionaryError> {
+        if depth > MAX_XML_DEPTH {
+            return self.err("elements nested too deeply");
+        }
+        let offset = self.pos;
+        self.pos += 1; // '<'
+        let name = self.name()?;
+        let mut el = Element {
+            name,
+            offset,
+            attrs: Vec::new(),
+            children: Vec::new(),
+        };
+
+        // Attributes up to '>' or '/>'
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(el);
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            if rest.is_empty() {
+                return self.err("unterminated start tag");
+            }
+            let attr_name = self.name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return self.err("expected '=' after attribute name");
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return self.err("expected quoted attribute value"),
+            };
+            self.pos += 1;
+            let raw = match self.rest().find(quote) {
+                Some(end) => &self.rest()[..end],
+                None => return self.err("unterminated attribute value"),
+            };
+            let value = decode_entities(raw).map_err(|i| DictionaryError::Xml {
+                offset: self.pos + i,
+                msg: "invalid entity reference",
+            })?;
+            self.pos += raw.len() + 1;
+            el.attrs.push((attr_name, value));
+        }
+
+        // Content up to the matching end tag
+        loop {
+            match self.rest().find('<') {
+                Some(i) => self.pos += i,
+                None => return self.err("missing end tag"),
+            }
+            if self.rest().starts_with("</") {
+                let end_offset = self.pos;
+                self.pos += 2;
+                let end_name = self.name()?;
+                if end_name != el.name {
+                    return Err(DictionaryError::Xml {
+                        offset: end_offset,
+                        msg: "mismatched end tag",
+                    });
+                }
+                self.skip_whitespace();
+                if !self.rest().starts_with('>') {
+                    return self.err("expected '>'");
+                }
+                self.pos += 1;
+                return Ok(el);
+            }
+            if !self.skip_misc()? {
+                let child = self.element(depth + 1)?;
+                el.children.push(child);
+            }
+        }
+    }
+}
+
+/// Decode the predefined XML entities and numeric character references.
+/// On failure returns the byte index of the offending `&`.
+fn decode_entities(raw: &str) -> Result<String, usize> {
+    if !raw.contains('&') {
+        return Ok(raw.to_owned());
+    }
+    let mut out = String::with_capacity(raw.len());
+    let mut i = 0;
+    while let Some(amp) = raw[i..].find('&') {
+        let start = i + amp;
+        out.push_str(&raw[i..start]);
+        let end = raw[start..]
+            .find(';')
+            .map(|e| start + e)
+            .ok_or(start)?;
+        let entity = &raw[start + 1..end];
+        let c = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+                    .ok_or(start)?
+            }
+        };
+        out.push(c);
+        i = end + 1;
+    }
+    out.push_str(&raw[i..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINI: &str = r#"<?xml version="1.0"?>
+<!-- test dictionary -->
+<fix type="FIX" major="4" minor="4" servicepack="0">
+  <header>
+    <field name="BeginString" required="Y"/>
+    <field name="MsgType" required="Y"/>
+  </header>
+  <trailer>
+    <field name="CheckSum" required="Y"/>
+  </trailer>
+  <messages>
+    <message name="NewOrderSingle" msgtype="D" msgcat="app">
+      <field name="ClOrdID" required="Y"/>
+      <component name="Parties" required="N"/>
+      <field name="Side" required="Y"/>
+    </message>
+  </messages>
+  <components>
+    <component name="Parties">
+      <group name="NoPartyIDs" required="N">
+        <field name="PartyID" required="N"/>
+      </group>
+    </component>
+  </components>
+  <fields>
+    <field number="8" name="BeginString" type="STRING"/>
+    <field number="10" name="CheckSum" type="STRING"/>
+    <field number="11" name="ClOrdID" type="STRING"/>
+    <field number="35" name="MsgType" type="STRING"/>
+    <field number="54" name="Side" type="CHAR">
+      <value enum="1" description="BUY"/>
+      <value enum="2" description="SELL"/>
+    </field>
+    <field number="448" name="PartyID" type="STRING"/>
+    <field number="453" name="NoPartyIDs" type="NUMINGROUP"/>
+  </fields>
+</fix>
+"#;
+
+    #[test]
+    fn test_parse_model() {
+        let dict = Dictionary::parse(MINI).unwrap();
+        assert_eq!(dict.begin_string(), "FIX.4.4");
+        assert_eq!(dict.service_pack(), 0);
+        assert_eq!(dict.header().len(), 2);
+        assert_eq!(dict.trailer()[0].name, "CheckSum");
+
+        let side = dict.field_by_tag(54).unwrap();
+        assert_eq!(side.name, "Side");
+        assert_eq!(side.field_type, FieldType::Char);
+        assert_eq!(side.value("2").unwrap().description, "SELL");
+        assert!(side.allows(b"1"));
+        assert!(!side.allows(b"3"));
+        assert!(dict.field_by_name("ClOrdID").unwrap().allows(b"anything"));
+
+        let nos = dict.message_by_type("D").unwrap();
+        assert_eq!(nos.name, "NewOrderSingle");
+        assert_eq!(nos.category, MessageCategory::App);
+        let kinds: Vec<(&str, MemberKind, bool)> = nos
+            .members
+            .iter()
+            .map(|m| (m.name.as_str(), m.kind, m.required))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("ClOrdID", MemberKind::Field, true),
+                ("Parties", MemberKind::Component, false),
+                ("Side", MemberKind::Field, true),
+            ]
+        );
+
+        let parties = dict.component("Parties").unwrap();
+        let group = &parties.members[0];
+        assert_eq!(group.kind, MemberKind::Group);
+        assert_eq!(group.name, "NoPartyIDs");
+        assert_eq!(group.members[0].name, "PartyID");
+        assert_eq!(
+            dict.field_by_name(&group.name)
+                .unwrap()
+                .field_type,
+            FieldType::NumInGroup
+        );
+        assert_eq!(dict, MINI.parse().unwrap());
+    }
+
+    #[test]
+    fn test_field_type_names() {
+        assert_eq!(FieldType::from_name("UTCTIMESTAMP"), Some(FieldType::UtcTimestamp));
+        assert_eq!(FieldType::from_name("utcdate"), Some(FieldType::UtcDateOnly));
+        assert_eq!(FieldType::from_name("NumInGroup"), Some(FieldType::NumInGroup));
+        assert_eq!(FieldType::from_name("BLOB"), None);
+        assert!(FieldType::Price.is_float());
+        assert!(FieldType::SeqNum.is_int());
+        assert!(FieldType::XmlData.is_data());
+        assert!(FieldType::MultipleCharValue.is_multi_value());
+        assert_eq!(FieldType::Length.to_string(), "LENGTH");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("plain").unwrap(), "plain");
+        assert_eq!(
+            decode_entities("A&amp;B &lt;&gt; &quot;&apos; &#65;&#x42;").unwrap(),
+            "A&B <> \"' AB"
+        );
+        assert_eq!(decode_entities("x&bogus;"), Err(1));
+        assert_eq!(decode_entities("x&amp"), Err(1));
+    }
+
+    #[test]
+    fn test_xml_errors() {
+        let cases: [(&str, DictionaryError); 4] = [
+            (
+                "<fix major=\"4\" minor=\"4\">",
+                DictionaryError::Xml {
+                    offset: 25,
+                    msg: "missing end tag",
+                },
+            ),
+            (
+                "<fix major=\"4\" minor=\"4\"></fox>",
+                DictionaryError::Xml {
+                    offset: 25,
+                    msg: "mismatched end tag",
+                },
+            ),
+            (
+                "<fix major=4 minor=\"4\"/>",
+                DictionaryError::Xml {
+                    offset: 11,
+                    msg: "expected quoted attribute value",
+                },
+            ),
+            (
+                "<fix major=\"4\" minor=\"4\"/><fix/>",
+                DictionaryError::Xml {
+                    offset: 26,
+                    msg: "content after root element",
+                },
+            ),
+        ];
+        for (xml, expected) in cases {
+            assert_eq!(Dictionary::parse(xml), Err(expected), "{xml}");
+        }
+
+        let deep = "<a>".repeat(MAX_XML_DEPTH + 2);
+        assert!(matches!(
+            Dictionary::parse(&deep),
+            Err(DictionaryError::Xml {
+                msg: "elements nested too deeply",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_dictionary_errors() {
+        let wrap = |body: &str| format!("<fix major=\"4\" minor=\"4\">{body}</fix>");
+
+        let err = Dictionary::parse(&wrap(
+            r#"<fields><field number="1" name="A" type="BLOB"/></fields>"#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            DictionaryError::UnknownFieldType {
+                field: "A".into(),
+                field_type: "BLOB".into(),
+            }
+        );
+
+        let err = Dictionary::parse(&wrap(
+            r#"<fields><field number="1" name="A" type="INT"/><field number="1" name="B" type="INT"/></fields>"#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            DictionaryError::DuplicateField {
+                name: "B".into(),
+                number: 1,
+            }
+        );
+
+        let err = Dictionary::parse(&wrap(
+            r#"<messages><message name="M" msgtype="U1"><field name="Missing" required="Y"/></message></messages>"#,
+        ))
+        .unwrap_err();
+        assert_eq!(err.to_string(), "M references undefined field Missing");
+
+        let err = Dictionary::parse(&wrap(
+            r#"<header><component name="Nope" required="N"/></header>"#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            DictionaryError::UndefinedComponent {
+                name: "Nope".into(),
+                parent: "header".into(),
+            }
+        );
+
+        let err = Dictionary::parse(&wrap(
+            r#"<header><field name="A" required="maybe"/></header>"#,
+        ))
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            DictionaryError::InvalidAttribute {
+                attribute: "required",
+                ..
+            }
+        ));
+
+        let err = Dictionary::parse("<fix minor=\"4\"/>").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Element <fix> at offset 0 is missing attribute 'major'"
+        );
+
+        let err = Dictionary::parse(&wrap(
+            r#"<components>
+                 <component name="A"><component name="B" required="N"/></component>
+                 <component name="B"><group name="G" required="N"><component name="A" required="N"/></group></component>
+               </components>
+               <fields><field number="1" name="G" type="NUMINGROUP"/></fields>"#,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            DictionaryError::RecursiveComponent { name: "A".into() }
+        );
+    }
+
+    #[test]
+    fn test_shared_components_are_checked_once() {
+        // Each level reaches the next through two paths: 2^64 paths in all
+        const DEPTH: usize = 64;
+        let mut components = String::new();
+        for i in 0..DEPTH {
+            components += &format!(
+                r#"<component name="C{i}"><component name="L{i}" required="N"/><component name="R{i}" required="N"/></component>
+                   <component name="L{i}"><component name="C{next}" required="N"/></component>
+                   <component name="R{i}"><component name="C{next}" required="N"/></component>"#,
+                next = i + 1,
+            );
+        }
+        let parse = |last: &str| {
+            Dictionary::parse(&format!(
+                r#"<fix major="4" minor="4"><components>{components}<component name="C{DEPTH}">{last}</component></components></fix>"#
+            ))
+        };
+
+        let dict = parse("").unwrap();
+        assert_eq!(dict.components().len(), 3 * DEPTH + 1);
+
+        let err = parse(r#"<component name="C0" required="N"/>"#).unwrap_err();
+        assert!(matches!(err, DictionaryError::RecursiveComponent { .. }));
+    }
+}
@@ -0,0 +1,615 @@
+//! Human-readable rendering of raw FIX messages for logs and debugging.
+//!
+//! [`FixDisplay`] wraps a byte slice and implements [`Display`](fmt::Display),
+//! rendering each field as `tag=value` followed by a printable delimiter (`|`
+//! by default). It never allocates, so it can be handed straight to `write!`,
+//! `format_args!` or a logging macro:
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let msg = b"8=FIX.4.4\x019=5\x0135=0\x0110=161\x01";
+//! assert_eq!(FixDisplay::new(msg).to_string(), "8=FIX.4.4|9=5|35=0|10=161|");
+//! ```
+//!
+//! [`DisplayOptions`] selects the delimiter (e.g. `"^A"`), tag names and enum
+//! value descriptions from the built-in FIX 4.4 tables ([`fix44_tag_name`],
+//! [`fix44_value_description`]) or from a loaded [`Dictionary`],
+//! and the tags whose values are masked (Password(554) and NewPassword(925) by
+//! default):
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let msg = b"35=A\x01553=trader\x01554=hunter2\x0154=1\x01";
+//! let options = DisplayOptions {
+//!     tag_names: true,
+//!     value_descriptions: true,
+//!     ..DisplayOptions::default()
+//! };
+//! assert_eq!(
+//!     FixDisplay::with_options(msg, options).to_string(),
+//!     "MsgType(35)=A (Logon)|Username(553)=trader|Password(554)=***|Side(54)=1 (Buy)|"
+//! );
+//! ```
+//!
+//! Partially built messages render too: pass `&buffer[..pos]` of a forge
+//! buffer mid-write. A last field without SOH is shown without a trailing
+//! delimiter, trailing NUL bytes are ignored, and bytes that do not parse as a
+//! field are shown escaped up to the next SOH, after which rendering (and
+//! masking) carries on. Non-printable bytes inside values (e.g. SOH in
+//! RawData(96)) are escaped as `\xNN`.
+
+use core::fmt;
+
+use crate::fields::LenientFieldIter;
+
+#[cfg(feature = "alloc")]
+use crate::dictionary::Dictionary;
+
+/// Tags masked by [`DisplayOptions::default`]: Password(554) and NewPassword(925).
+pub const DEFAULT_MASKED_TAGS: &[u32] = &[554, 925];
+
+/// Shown in place of a masked value.
+pub const MASKED_VALUE: &str = "***";
+
+/// How [`FixDisplay`] renders a message.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayOptions<'a> {
+    /// Written after every complete field, e.g. `"|"`, `"^A"` or `"\n"`
+    pub delimiter: &'a str,
+    /// Prefix each tag with its name: `Side(54)=1`
+    pub tag_names: bool,
+    /// Follow enumerated values with their description: `54=1 (Buy)`
+    pub value_descriptions: bool,
+    /// Tags whose values are replaced with [`MASKED_VALUE`]
+    pub masked_tags: &'a [u32],
+    /// Names and descriptions come from this dictionary instead of the built-in tables
+    #[cfg(feature = "alloc")]
+    pub dictionary: Option<&'a Dictionary>,
+}
+
+impl Default for DisplayOptions<'_> {
+    fn default() -> Self {
+        DisplayOptions {
+            delimiter: "|",
+            tag_names: false,
+            value_descriptions: false,
+            masked_tags: DEFAULT_MASKED_TAGS,
+            #[cfg(feature = "alloc")]
+            dictionary: None,
+        }
+    }
+}
+
+impl<'a> DisplayOptions<'a> {
+    fn tag_name(
+        &self,
+        tag: u32,
+    ) -> Option<&'a str> {
+        #[cfg(feature = "alloc")]
+        if let Some(dictionary) = self.dictionary {
+            return dictionary
+                .field_by_tag(tag)
+                .map(|field| field.name.as_str());
+        }
+        fix44_tag_name(tag)
+    }
+
+    fn value_description(
+        &self,
+        tag: u32,
+        value: &[u8],
+    ) -> Option<&'a str> {
+        #[cfg(feature = "alloc")]
+        if let Some(dictionary) = self.dictionary {
+            let wire = core::str::from_utf8(value).ok()?;
+            return dictionary
+                .field_by_tag(tag)?
+                .value(wire)
+                .map(|v| v.description.as_str());
+        }
+        fix44_value_description(tag, value)
+    }
+}
+
+/// [`Display`](fmt::Display) adapter for a raw FIX message or a partially built one.
+///
+/// See the [module docs](self).
+#[derive(Debug, Clone, Copy)]
+pub struct FixDisplay<'a>(&'a [u8], DisplayOptions<'a>);
+
+impl<'a> FixDisplay<'a> {
+    /// Render `msg` with the default options: `|` delimiter, no names, passwords masked.
+    pub fn new(msg: &'a [u8]) -> Self {
+        FixDisplay(msg, DisplayOptions::default())
+    }
+
+    /// Render `msg` with `options`.
+    pub fn with_options(
+        msg: &'a [u8],
+        options: DisplayOptions<'a>,
+    ) -> Self {
+        FixDisplay(msg, options)
+    }
+
+    fn write_field(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        tag: u32,
+        value: &[u8],
+    ) -> fmt::Result {
+        let options = &self.1;
+        match options
+            .tag_name(tag)
+            .filter(|_| options.tag_names)
+        {
+            Some(name) => write!(f, "{name}({tag})=")?,
+            None => write!(f, "{tag}=")?,
+        }
+        if options.masked_tags.contains(&tag) {
+            return f.write_str(MASKED_VALUE);
+        }
+        write_escaped(f, value)?;
+        if options.value_descriptions
+            && let Some(description) = options.value_description(tag, value)
+        {
+            write!(f, " ({description})")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FixDisplay<'_> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let mut msg = self.0;
+        while let [rest @ .., 0] = msg {
+            msg = rest;
+        }
+        for field in LenientFieldIter::new(msg) {
+            match field.tag {
+                Some(tag) => self.write_field(f, tag, field.value)?,
+                // Bytes that are not a field are shown escaped
+                None => write_escaped(f, field.value)?,
+            }
+            if field.terminated {
+                f.write_str(self.1.delimiter)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write `bytes` as text, escaping control characters and invalid UTF-8 as `\xNN`.
+fn write_escaped(
+    f: &mut fmt::Formatter<'_>,
+    bytes: &[u8],
+) -> fmt::Result {
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if c.is_control() {
+                write!(f, "\\x{:02x}", c as u32)?;
+            } else {
+                write!(f, "{c}")?;
+            }
+        }
+        for &b in chunk.invalid() {
+            write!(f, "\\x{b:02x}")?;
+        }
+    }
+    Ok(())
+}
+
+/// FIX 4.4 name of `tag`, for the standard header and trailer, session-level
+/// messages and common order-entry, execution and market data fields.
+///
+/// Load a [`Dictionary`] for complete coverage.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::fix44_tag_name;
+/// assert_eq!(fix44_tag_name(35), Some("MsgType"));
+/// assert_eq!(fix44_tag_name(5001), None);
+/// ```
+pub fn fix44_tag_name(tag: u32) -> Option<&'static str> {
+    FIX44_TAG_NAMES
+        .binary_search_by_key(&tag, |&(t, _)| t)
+        .ok()
+        .map(|i| FIX44_TAG_NAMES[i].1)
+}
+
+/// Tag number of the FIX 4.4 field `name`, the reverse of [`fix44_tag_name`].
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::fix44_tag_number;
+/// assert_eq!(fix44_tag_number("MsgType"), Some(35));
+/// assert_eq!(fix44_tag_number("Nonsense"), None);
+/// ```
+pub fn fix44_tag_number(name: &str) -> Option<u32> {
+    FIX44_TAG_NAMES
+        .iter()
+        .find(|&&(_, n)| n == name)
+        .map(|&(tag, _)| tag)
+}
+
+/// Description of enumerated value `value` of FIX 4.4 field `tag`, for the
+/// fields listed in the built-in table (MsgType, Side, OrdType, OrdStatus,
+/// ExecType, TimeInForce, reject reasons, ...).
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::fix44_value_description;
+/// assert_eq!(fix44_value_description(54, b"1"), Some("Buy"));
+/// assert_eq!(fix44_value_description(35, b"D"), Some("NewOrderSingle"));
+/// assert_eq!(fix44_value_description(54, b"Z"), None);
+/// ```
+pub fn fix44_value_description(
+    tag: u32,
+    value: &[u8],
+) -> Option<&'static str> {
+    let start = FIX44_VALUE_DESCRIPTIONS.partition_point(|&(t, _, _)| t < tag);
+    FIX44_VALUE_DESCRIPTIONS[start..]
+        .iter()
+        .take_while(|&&(t, _, _)| t == tag)
+        .find(|&&(_, v, _)| v.as_bytes() == value)
+        .map(|&(_, _, description)| description)
+}
+
+/// Built-in FIX 4.4 tag names, sorted by tag.
+const FIX44_TAG_NAMES: &[(u32, &str)] = &[
+    (1, "Account"),
+    (6, "AvgPx"),
+    (7, "BeginSeqNo"),
+    (8, "BeginString"),
+    (9, "BodyLength"),
+    (10, "CheckSum"),
+    (11, "ClOrdID"),
+    (12, "Commission"),
+    (13, "CommType"),
+    (14, "CumQty"),
+    (15, "Currency"),
+    (16, "EndSeqNo"),
+    (17, "ExecID"),
+    (18, "ExecInst"),
+    (19, "ExecRefID"),
+    (21, "HandlInst"),
+    (22, "SecurityIDSource"),
+    (23, "IOIID"),
+    (29, "LastCapacity"),
+    (30, "LastMkt"),
+    (31, "LastPx"),
+    (32, "LastQty"),
+    (34, "MsgSeqNum"),
+    (35, "MsgType"),
+    (36, "NewSeqNo"),
+    (37, "OrderID"),
+    (38, "OrderQty"),
+    (39, "OrdStatus"),
+    (40, "OrdType"),
+    (41, "OrigClOrdID"),
+    (42, "OrigTime"),
+    (43, "PossDupFlag"),
+    (44, "Price"),
+    (45, "RefSeqNum"),
+    (48, "SecurityID"),
+    (49, "SenderCompID"),
+    (50, "SenderSubID"),
+    (52, "SendingTime"),
+    (53, "Quantity"),
+    (54, "Side"),
+    (55, "Symbol"),
+    (56, "TargetCompID"),
+    (57, "TargetSubID"),
+    (58, "Text"),
+    (59, "TimeInForce"),
+    (60, "TransactTime"),
+    (63, "SettlType"),
+    (64, "SettlDate"),
+    (65, "SymbolSfx"),
+    (66, "ListID"),
+    (75, "TradeDate"),
+    (89, "Signature"),
+    (90, "SecureDataLen"),
+    (91, "SecureData"),
+    (93, "SignatureLength"),
+    (95, "RawDataLength"),
+    (96, "RawData"),
+    (97, "PossResend"),
+    (98, "EncryptMethod"),
+    (99, "StopPx"),
+    (100, "ExDestination"),
+    (102, "CxlRejReason"),
+    (103, "OrdRejReason"),
+    (108, "HeartBtInt"),
+    (110, "MinQty"),
+    (111, "MaxFloor"),
+    (112, "TestReqID"),
+    (115, "OnBehalfOfCompID"),
+    (116, "OnBehalfOfSubID"),
+    (122, "OrigSendingTime"),
+    (123, "GapFillFlag"),
+    (126, "ExpireTime"),
+    (128, "DeliverToCompID"),
+    (129, "DeliverToSubID"),
+    (141, "ResetSeqNumFlag"),
+    (142, "SenderLocationID"),
+    (143, "TargetLocationID"),
+    (150, "ExecType"),
+    (151, "LeavesQty"),
+    (152, "CashOrderQty"),
+    (167, "SecurityType"),
+    (198, "SecondaryOrderID"),
+    (200, "MaturityMonthYear"),
+    (207, "SecurityExchange"),
+    (262, "MDReqID"),
+    (263, "SubscriptionRequestType"),
+    (264, "MarketDepth"),
+    (265, "MDUpdateType"),
+    (267, "NoMDEntryTypes"),
+    (268, "NoMDEntries"),
+    (269, "MDEntryType"),
+    (270, "MDEntryPx"),
+    (271, "MDEntrySize"),
+    (279, "MDUpdateAction"),
+    (347, "MessageEncoding"),
+    (354, "EncodedTextLen"),
+    (355, "EncodedText"),
+    (369, "LastMsgSeqNumProcessed"),
+    (371, "RefTagID"),
+    (372, "RefMsgType"),
+    (373, "SessionRejectReason"),
+    (379, "BusinessRejectRefID"),
+    (380, "BusinessRejectReason"),
+    (383, "MaxMessageSize"),
+    (384, "NoMsgTypes"),
+    (385, "MsgDirection"),
+    (434, "CxlRejResponseTo"),
+    (447, "PartyIDSource"),
+    (448, "PartyID"),
+    (452, "PartyRole"),
+    (453, "NoPartyIDs"),
+    (526, "SecondaryClOrdID"),
+    (528, "OrderCapacity"),
+    (553, "Username"),
+    (554, "Password"),
+    (627, "NoHops"),
+    (628, "HopCompID"),
+    (629, "HopSendingTime"),
+    (630, "HopRefID"),
+    (789, "NextExpectedMsgSeqNum"),
+    (925, "NewPassword"),
+];
+
+/// Built-in FIX 4.4 enumerated value descriptions, sorted by tag.
+const FIX44_VALUE_DESCRIPTIONS: &[(u32, &str, &str)] = &[
+    (21, "1", "AutomatedExecutionNoIntervention"),
+    (21, "2", "AutomatedExecutionInterventionOK"),
+    (21, "3", "ManualOrder"),
+    (35, "0", "Heartbeat"),
+    (35, "1", "TestRequest"),
+    (35, "2", "ResendRequest"),
+    (35, "3", "Reject"),
+    (35, "4", "SequenceReset"),
+    (35, "5", "Logout"),
+    (35, "6", "IndicationOfInterest"),
+    (35, "8", "ExecutionReport"),
+    (35, "9", "OrderCancelReject"),
+    (35, "A", "Logon"),
+    (35, "B", "News"),
+    (35, "D", "NewOrderSingle"),
+    (35, "E", "NewOrderList"),
+    (35, "F", "OrderCancelRequest"),
+    (35, "G", "OrderCancelReplaceRequest"),
+    (35, "H", "OrderStatusRequest"),
+    (35, "R", "QuoteRequest"),
+    (35, "S", "Quote"),
+    (35, "V", "MarketDataRequest"),
+    (35, "W", "MarketDataSnapshotFullRefresh"),
+    (35, "X", "MarketDataIncrementalRefresh"),
+    (35, "Y", "MarketDataRequestReject"),
+    (35, "c", "SecurityDefinitionRequest"),
+    (35, "d", "SecurityDefinition"),
+    (35, "j", "BusinessMessageReject"),
+    (35, "q", "OrderMassCancelRequest"),
+    (35, "r", "OrderMassCancelReport"),
+    (35, "AE", "TradeCaptureReport"),
+    (35, "AF", "OrderMassStatusRequest"),
+    (39, "0", "New"),
+    (39, "1", "PartiallyFilled"),
+    (39, "2", "Filled"),
+    (39, "3", "DoneForDay"),
+    (39, "4", "Canceled"),
+    (39, "5", "Replaced"),
+    (39, "6", "PendingCancel"),
+    (39, "7", "Stopped"),
+    (39, "8", "Rejected"),
+    (39, "9", "Suspended"),
+    (39, "A", "PendingNew"),
+    (39, "B", "Calculated"),
+    (39, "C", "Expired"),
+    (39, "D", "AcceptedForBidding"),
+    (39, "E", "PendingReplace"),
+    (40, "1", "Market"),
+    (40, "2", "Limit"),
+    (40, "3", "Stop"),
+    (40, "4", "StopLimit"),
+    (40, "K", "MarketWithLeftOverAsLimit"),
+    (40, "P", "Pegged"),
+    (54, "1", "Buy"),
+    (54, "2", "Sell"),
+    (54, "3", "BuyMinus"),
+    (54, "4", "SellPlus"),
+    (54, "5", "SellShort"),
+    (54, "6", "SellShortExempt"),
+    (54, "7", "Undisclosed"),
+    (54, "8", "Cross"),
+    (54, "9", "CrossShort"),
+    (59, "0", "Day"),
+    (59, "1", "GoodTillCancel"),
+    (59, "2", "AtTheOpening"),
+    (59, "3", "ImmediateOrCancel"),
+    (59, "4", "FillOrKill"),
+    (59, "5", "GoodTillCrossing"),
+    (59, "6", "GoodTillDate"),
+    (59, "7", "AtTheClose"),
+    (98, "0", "None"),
+    (102, "0", "TooLateToCancel"),
+    (102, "1", "UnknownOrder"),
+    (102, "2", "BrokerOption"),
+    (102, "3", "OrderAlreadyInPendingStatus"),
+    (102, "99", "Other"),
+    (103, "0", "BrokerOption"),
+    (103, "1", "UnknownSymbol"),
+    (103, "2", "ExchangeClosed"),
+    (103, "3", "OrderExceedsLimit"),
+    (103, "4", "TooLateToEnter"),
+    (103, "5", "UnknownOrder"),
+    (103, "6", "DuplicateOrder"),
+    (103, "99", "Other"),
+    (150, "0", "New"),
+    (150, "3", "DoneForDay"),
+    (150, "4", "Canceled"),
+    (150, "5", "Replaced"),
+    (150, "6", "PendingCancel"),
+    (150, "7", "Stopped"),
+    (150, "8", "Rejected"),
+    (150, "9", "Suspended"),
+    (150, "A", "PendingNew"),
+    (150, "B", "Calculated"),
+    (150, "C", "Expired"),
+    (150, "D", "Restated"),
+    (150, "E", "PendingReplace"),
+    (150, "F", "Trade"),
+    (150, "G", "TradeCorrect"),
+    (150, "H", "TradeCancel"),
+    (150, "I", "OrderStatus"),
+    (263, "0", "Snapshot"),
+    (263, "1", "SnapshotPlusUpdates"),
+    (263, "2", "DisablePreviousSnapshot"),
+    (269, "0", "Bid"),
+    (269, "1", "Offer"),
+    (269, "2", "Trade"),
+    (279, "0", "New"),
+    (279, "1", "Change"),
+    (279, "2", "Delete"),
+    (373, "0", "InvalidTagNumber"),
+    (373, "1", "RequiredTagMissing"),
+    (373, "2", "TagNotDefinedForThisMessageType"),
+    (373, "3", "UndefinedTag"),
+    (373, "4", "TagSpecifiedWithoutAValue"),
+    (373, "5", "ValueIsIncorrect"),
+    (373, "6", "IncorrectDataFormatForValue"),
+    (373, "7", "DecryptionProblem"),
+    (373, "8", "SignatureProblem"),
+    (373, "9", "CompIDProblem"),
+    (373, "10", "SendingTimeAccuracyProblem"),
+    (373, "11", "InvalidMsgType"),
+    (373, "12", "XMLValidationError"),
+    (373, "13", "TagAppearsMoreThanOnce"),
+    (373, "14", "TagSpecifiedOutOfRequiredOrder"),
+    (373, "15", "RepeatingGroupFieldsOutOfOrder"),
+    (373, "16", "IncorrectNumInGroupCountForRepeatingGroup"),
+    (373, "17", "NonDataValueIncludesFieldDelimiter"),
+    (373, "99", "Other"),
+    (380, "0", "Other"),
+    (380, "1", "UnknownID"),
+    (380, "2", "UnknownSecurity"),
+    (380, "3", "UnsupportedMessageType"),
+    (380, "4", "ApplicationNotAvailable"),
+    (380, "5", "ConditionallyRequiredFieldMissing"),
+    (380, "6", "NotAuthorized"),
+    (380, "7", "DeliverToFirmNotAvailableAtThisTime"),
+    (434, "1", "OrderCancelRequest"),
+    (434, "2", "OrderCancelReplaceRequest"),
+];
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::buffer::FORGE_WRITE_START;
+    use crate::test_util::{forge, forge_unfinished};
+    use crate::writing::{write_tag_and_data, write_tag_and_str};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_tables_sorted() {
+        assert!(
+            FIX44_TAG_NAMES
+                .windows(2)
+                .all(|w| w[0].0 < w[1].0)
+        );
+        assert!(
+            FIX44_VALUE_DESCRIPTIONS
+                .windows(2)
+                .all(|w| w[0].0 <= w[1].0)
+        );
+    }
+
+    #[test]
+    fn test_display_finished_message() {
+        let (buffer, len) = forge(|buffer, pos| {
+            let n = write_tag_and_str(buffer, pos, b"54=", "2");
+            n + write_tag_and_data(buffer, pos + n, b"95=", b"96=", b"a\x01b")
+        });
+
+        let text = FixDisplay::new(&buffer[..len]).to_string();
+        assert_eq!(
+            &text[..text.len() - 7],
+            "8=FIX.4.4|9=0048|35=D|49=CLIENT|56=BROKER|34=12|54=2|95=3|96=a\\x01b|"
+        );
+        assert!(text.ends_with('|'));
+
+        let options = DisplayOptions {
+            delimiter: "^A",
+            value_descriptions: true,
+            ..DisplayOptions::default()
+        };
+        let text = FixDisplay::with_options(&buffer[..len], options).to_string();
+        assert!(text.starts_with("8=FIX.4.4^A9=0048^A35=D (NewOrderSingle)^A49=CLIENT^A"));
+        assert!(text.contains("^A54=2 (Sell)^A"));
+    }
+
+    #[test]
+    fn test_display_partial_buffer() {
+        // Mid-write: the whole forge buffer, placeholder BodyLength, last field unterminated
+        let (buffer, _) = forge_unfinished(|buffer, pos| {
+            let n = write_tag_and_str(buffer, pos, b"11=", "ORD-1");
+            buffer[pos + n..pos + n + 5].copy_from_slice(b"58=ha");
+            n
+        });
+        assert_eq!(
+            FixDisplay::new(&buffer).to_string(),
+            "8=FIX.4.4|9=0000|35=D|49=CLIENT|56=BROKER|34=12|11=ORD-1|58=ha"
+        );
+        assert_eq!(FixDisplay::new(&buffer[..FORGE_WRITE_START]).to_string(), "8=FIX.4.4|9=0000|35=");
+        assert_eq!(FixDisplay::new(b"35=0\x01garbage\x01").to_string(), "35=0|garbage|");
+        assert_eq!(FixDisplay::new(b"").to_string(), "");
+    }
+
+    #[test]
+    fn test_display_masking() {
+        let msg = b"553=me\x01554=secret\x01925=new\x0158=\xff\x01";
+        assert_eq!(FixDisplay::new(msg).to_string(), "553=me|554=***|925=***|58=\\xff|");
+        let options = DisplayOptions {
+            masked_tags: &[553],
+            ..DisplayOptions::default()
+        };
+        assert_eq!(
+            FixDisplay::with_options(msg, options).to_string(),
+            "553=***|554=secret|925=new|58=\\xff|"
+        );
+    }
+
+    #[test]
+    fn test_display_masks_after_malformed_field() {
+        let msg = b"35=A\x01garbage\x01553=me\x01554=hunter2\x01x=1\x01925=new\x01";
+        assert_eq!(
+            FixDisplay::new(msg).to_string(),
+            "35=A|garbage|553=me|554=***|x=1|925=***|"
+        );
+        // Malformed field right before an unterminated password
+        assert_eq!(FixDisplay::new(b"=\x01554=hun").to_string(), "=|554=***");
+    }
+}
@@ -1,32 +1,388 @@
 //! Error types for FIX protocol parsing and validation.
 //!
 //! This module provides comprehensive error handling for FIX protocol data parsing,
-//! including missing required fields and invalid value errors.
+//! including missing required fields, invalid value errors and position-aware
+//! errors for malformed wire data.
+
+/// Standard FIX 4.4 SessionRejectReason(373) codes.
+pub mod session_reject_reason {
+    /// 0 = Invalid tag number
+    pub const INVALID_TAG_NUMBER: u16 = 0;
+    /// 1 = Required tag missing
+    pub const REQUIRED_TAG_MISSING: u16 = 1;
+    /// 2 = Tag not defined for this message type
+    pub const TAG_NOT_DEFINED_FOR_MESSAGE_TYPE: u16 = 2;
+    /// 3 = Undefined tag
+    pub const UNDEFINED_TAG: u16 = 3;
+    /// 4 = Tag specified without a value
+    pub const TAG_SPECIFIED_WITHOUT_A_VALUE: u16 = 4;
+    /// 5 = Value is incorrect (out of range) for this tag
+    pub const VALUE_IS_INCORRECT: u16 = 5;
+    /// 6 = Incorrect data format for value
+    pub const INCORRECT_DATA_FORMAT: u16 = 6;
+    /// 7 = Decryption problem
+    pub const DECRYPTION_PROBLEM: u16 = 7;
+    /// 8 = Signature problem
+    pub const SIGNATURE_PROBLEM: u16 = 8;
+    /// 9 = CompID problem
+    pub const COMPID_PROBLEM: u16 = 9;
+    /// 10 = SendingTime accuracy problem
+    pub const SENDING_TIME_ACCURACY_PROBLEM: u16 = 10;
+    /// 11 = Invalid MsgType
+    pub const INVALID_MSGTYPE: u16 = 11;
+    /// 12 = XML Validation error
+    pub const XML_VALIDATION_ERROR: u16 = 12;
+    /// 13 = Tag appears more than once
+    pub const TAG_APPEARS_MORE_THAN_ONCE: u16 = 13;
+    /// 14 = Tag specified out of required order
+    pub const TAG_SPECIFIED_OUT_OF_REQUIRED_ORDER: u16 = 14;
+    /// 15 = Repeating group fields out of order
+    pub const REPEATING_GROUP_FIELDS_OUT_OF_ORDER: u16 = 15;
+    /// 16 = Incorrect NumInGroup count for repeating group
+    pub const INCORRECT_NUMINGROUP_COUNT: u16 = 16;
+    /// 17 = Non "data" value includes field delimiter (SOH character)
+    pub const NON_DATA_VALUE_INCLUDES_FIELD_DELIMITER: u16 = 17;
+    /// 99 = Other
+    pub const OTHER: u16 = 99;
+}
+
+/// Number of `u64` words in a [`MissingMask`].
+pub const MISSING_MASK_WORDS: usize = 4;
+
+/// Fixed-width bitset of missing required members, indexed like the `meta` slice
+/// of [`ReadError::MissingRequiredFields`].
+///
+/// Holds up to `64 * MISSING_MASK_WORDS` (256) members inline so the error stays
+/// `Copy`-cheap to build on the hot path. Bits beyond that are ignored.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let mut mask = MissingMask::new();
+/// mask.set(3);
+/// mask.set(70);
+/// assert!(mask.is_set(3) && mask.is_set(70));
+/// assert!(!mask.is_set(4));
+/// assert_eq!(MissingMask::from_bits(0b101).count(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MissingMask {
+    words: [u64; MISSING_MASK_WORDS],
+}
+
+impl MissingMask {
+    /// Maximum number of members a mask can track.
+    pub const CAPACITY: usize = 64 * MISSING_MASK_WORDS;
+
+    /// Empty mask (nothing missing).
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            words: [0; MISSING_MASK_WORDS],
+        }
+    }
+
+    /// Mask covering members 0..64 from a single `u64`.
+    #[inline]
+    pub const fn from_bits(bits: u64) -> Self {
+        let mut words = [0; MISSING_MASK_WORDS];
+        words[0] = bits;
+        Self { words }
+    }
+
+    /// Mask from its raw words (word 0 holds members 0..64).
+    #[inline]
+    pub const fn from_words(words: [u64; MISSING_MASK_WORDS]) -> Self {
+        Self { words }
+    }
+
+    /// Raw words (word 0 holds members 0..64).
+    #[inline]
+    pub const fn words(&self) -> &[u64; MISSING_MASK_WORDS] {
+        &self.words
+    }
+
+    /// Mark member `i` as missing. Indices `>= CAPACITY` are ignored.
+    #[inline]
+    pub const fn set(&mut self, i: usize) {
+        if i < Self::CAPACITY {
+            self.words[i / 64] |= 1 << (i % 64);
+        }
+    }
+
+    /// Whether member `i` is marked missing.
+    #[inline]
+    pub const fn is_set(&self, i: usize) -> bool {
+        i < Self::CAPACITY && (self.words[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    /// Whether no member is marked missing.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        let mut i = 0;
+        while i < MISSING_MASK_WORDS {
+            if self.words[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Number of members marked missing.
+    #[inline]
+    pub const fn count(&self) -> u32 {
+        let mut n = 0;
+        let mut i = 0;
+        while i < MISSING_MASK_WORDS {
+            n += self.words[i].count_ones();
+            i += 1;
+        }
+        n
+    }
+}
+
+impl From<u64> for MissingMask {
+    #[inline]
+    fn from(bits: u64) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+/// Formats as one hexadecimal number, most significant word first (e.g. `0x5`).
+impl core::fmt::UpperHex for MissingMask {
+    fn fmt(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        let top = self
+            .words
+            .iter()
+            .rposition(|w| *w != 0)
+            .unwrap_or(0);
+        write!(f, "{:X}", self.words[top])?;
+        for w in self.words[..top].iter().rev() {
+            write!(f, "{w:016X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Kind of a required message member listed in the `meta` slice of
+/// [`ReadError::MissingRequiredFields`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum MemberKind {
+    /// Plain field; the tag is the field's own tag
+    Field = 0,
+    /// Component block; the tag is its first required field
+    Component = 1,
+    /// Repeating group; the tag is its NumInGroup (count) tag
+    Group = 2,
+}
+
+impl MemberKind {
+    /// Lower-case name used in error messages (`field`, `component`, `group`).
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            MemberKind::Field => "field",
+            MemberKind::Component => "component",
+            MemberKind::Group => "group",
+        }
+    }
+}
+
+impl core::fmt::Display for MemberKind {
+    fn fmt(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A required member reported missing by [`ReadError::missing_members`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingMember {
+    /// Member name from the generated metadata
+    pub name: &'static str,
+    /// Field tag, or the count tag for groups
+    pub tag: u16,
+    /// Field, component or group
+    pub kind: MemberKind,
+}
+
+impl core::fmt::Display for MissingMember {
+    fn fmt(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        let MissingMember { name, tag, kind } = self;
+        match kind {
+            // group: tag is the count tag
+            MemberKind::Group => write!(
+                f,
+                "{kind} {name}(countTag={tag})"
+            ),
+            _ => write!(
+                f,
+                "{kind} {name}(tag={tag})"
+            ),
+        }
+    }
+}
+
+/// Allocation-free iterator over the members marked in a [`MissingMask`].
+///
+/// Returned by [`ReadError::missing_members`]; empty for other error variants.
+#[derive(Debug, Clone)]
+pub struct MissingMembers<'a> {
+    mask: Option<&'a MissingMask>,
+    meta: &'static [(&'static str, u16, MemberKind)],
+    index: usize,
+}
+
+impl Iterator for MissingMembers<'_> {
+    type Item = MissingMember;
+
+    #[inline]
+    fn next(&mut self) -> Option<MissingMember> {
+        let mask = self.mask?;
+        while self.index < self.meta.len() {
+            let i = self.index;
+            self.index += 1;
+            if mask.is_set(i) {
+                let (name, tag, kind) = self.meta[i];
+                return Some(MissingMember { name, tag, kind });
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.meta.len() - self.index))
+    }
+}
 
 /// Strict parse error type for generated read() APIs.
 ///
 /// This error type is designed for high-performance parsing scenarios where
 /// detailed error information is needed for debugging and validation.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum ReadError {
-    /// Aggregated (bitmask) missing required members: fields (kind=0), components (kind=1), groups (kind=2).
+    /// Aggregated (bitmask) missing required members: fields, components and groups.
     ///
     /// The meta slice layout is: (name, tag_or_count_tag, kind)
     MissingRequiredFields {
-        /// Bitmask indicating which required fields are missing
-        missing_mask: u64,
+        /// Bitset indicating which required members are missing (bit `i` = `meta[i]`)
+        missing_mask: MissingMask,
         /// Metadata about the fields, components, and groups
-        meta: &'static [(&'static str, u16, u8)],
+        meta: &'static [(&'static str, u16, MemberKind)],
+        /// Raw tag of a missing field found by runtime (dictionary-driven)
+        /// validation, which has no static `meta`; `None` for generated readers
+        tag: Option<u32>,
     },
-    /// Invalid value encountered during parsing
+    /// Invalid value encountered during parsing (e.g. not one of the field's enumerated values)
     InvalidValue {
-        /// Name of the field that had an invalid value
+        /// Name of the field that had an invalid value (empty if not known statically)
+        name: &'static str,
+        /// FIX tag number
+        tag: u32,
+        /// Description of what went wrong
+        msg: &'static str,
+        /// Byte offset of the field, if the value was read from a message buffer
+        offset: Option<usize>,
+    },
+    /// Value bytes do not match the field's data format (e.g. invalid UTF-8 or non-ASCII)
+    InvalidFormat {
+        /// Name of the field that had a badly formatted value (empty if not known statically)
         name: &'static str,
         /// FIX tag number
-        tag: u16,
+        tag: u32,
         /// Description of what went wrong
         msg: &'static str,
+        /// Byte offset of the field, if the value was read from a message buffer
+        offset: Option<usize>,
+    },
+    /// Tag contains a non-digit byte (or is empty)
+    MalformedTag {
+        /// Byte offset of the offending byte
+        offset: usize,
+        /// Tag digits parsed before the offending byte (0 if none)
+        tag: u32,
+    },
+    /// Tag is not followed by `=`
+    MissingEquals {
+        /// Byte offset where `=` was expected
+        offset: usize,
+        /// Raw tag number
+        tag: u32,
+    },
+    /// Buffer ended before the field was complete
+    UnexpectedEnd {
+        /// Byte offset of the end of the buffer
+        offset: usize,
+        /// Raw tag number of the incomplete field (0 if the tag itself was cut off)
+        tag: u32,
+    },
+    /// Length-prefixed data value is not followed by SOH at the declared length
+    MissingDelimiter {
+        /// Byte offset where SOH was expected
+        offset: usize,
+        /// Raw tag number of the data field
+        tag: u32,
+    },
+    /// Tag appears more than once where only one occurrence is allowed
+    DuplicateTag {
+        /// Byte offset of the repeated field
+        offset: usize,
+        /// Raw tag number
+        tag: u32,
+    },
+    /// Tag appears outside its header/body/trailer section
+    TagOutOfOrder {
+        /// Byte offset of the misplaced field
+        offset: usize,
+        /// Raw tag number
+        tag: u32,
+    },
+    /// Tag is not defined (unknown to the parser or dictionary)
+    UnknownTag {
+        /// Byte offset of the field
+        offset: usize,
+        /// Raw tag number
+        tag: u32,
+    },
+    /// Repeating group entry count does not match its NumInGroup field
+    GroupCountMismatch {
+        /// Byte offset of the NumInGroup field
+        offset: usize,
+        /// Raw NumInGroup (count) tag number
+        tag: u32,
+        /// Count declared by the NumInGroup field
+        expected: u32,
+        /// Number of entries actually found
+        actual: u32,
+    },
+    /// Tag is defined but not allowed in this message type
+    TagNotDefinedForMessage {
+        /// Byte offset of the field
+        offset: usize,
+        /// Raw tag number
+        tag: u32,
+    },
+    /// Field is present with an empty value
+    EmptyValue {
+        /// Byte offset of the field
+        offset: usize,
+        /// Raw tag number
+        tag: u32,
+    },
+    /// MsgType(35) is not defined in the dictionary
+    InvalidMsgType {
+        /// Byte offset of the MsgType field
+        offset: usize,
     },
 }
 
@@ -36,8 +392,16 @@ impl core::fmt::Display for ReadError {
         f: &mut core::fmt::Formatter<'_>,
     ) -> core::fmt::Result {
         match self {
-            ReadError::MissingRequiredFields { missing_mask, meta } => {
-                if *missing_mask == 0 {
+            ReadError::MissingRequiredFields {
+                tag: Some(tag), ..
+            } => {
+                write!(
+                    f,
+                    "Required tag {tag} missing"
+                )
+            }
+            ReadError::MissingRequiredFields { missing_mask, .. } => {
+                if missing_mask.is_empty() {
                     return write!(
                         f,
                         "No required members are missing"
@@ -47,66 +411,279 @@ impl core::fmt::Display for ReadError {
                     f,
                     "Missing required members (mask=0x{missing_mask:0X}): "
                 )?;
-                let mut first = true;
-                for (i, (name, tag, kind)) in meta.iter().enumerate() {
-                    if (missing_mask >> i) & 1 == 1 {
-                        if !first {
-                            write!(f, ", ")?;
-                        }
-                        first = false;
-                        let kind_str = match kind {
-                            0 => "field",
-                            1 => "component",
-                            _ => "group",
-                        };
-                        if *kind == 2 {
-                            // group: tag is the count tag
-                            write!(
-                                f,
-                                "{kind_str} {name}(countTag={tag})"
-                            )?;
-                        } else {
-                            write!(
-                                f,
-                                "{kind_str} {name}(tag={tag})"
-                            )?;
-                        }
+                for (i, member) in self.missing_members().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
                     }
+                    write!(f, "{member}")?;
                 }
                 Ok(())
             }
-            ReadError::InvalidValue { name, tag, msg } => {
+            ReadError::InvalidValue {
+                name,
+                tag,
+                msg,
+                offset,
+            } => {
+                write!(f, "Invalid value for ")?;
+                write_field_position(f, name, *tag, *offset)?;
+                write!(f, ": {msg}")
+            }
+            ReadError::InvalidFormat {
+                name,
+                tag,
+                msg,
+                offset,
+            } => {
+                write!(f, "Invalid format for ")?;
+                write_field_position(f, name, *tag, *offset)?;
+                write!(f, ": {msg}")
+            }
+            ReadError::MalformedTag { offset, tag } => {
+                write!(
+                    f,
+                    "Malformed tag at offset {offset} (tag={tag})"
+                )
+            }
+            ReadError::MissingEquals { offset, tag } => {
+                write!(
+                    f,
+                    "Missing '=' after tag {tag} at offset {offset}"
+                )
+            }
+            ReadError::UnexpectedEnd { offset, tag } => {
+                write!(
+                    f,
+                    "Unexpected end of buffer at offset {offset} (tag={tag})"
+                )
+            }
+            ReadError::MissingDelimiter { offset, tag } => {
                 write!(
                     f,
-                    "Invalid value for {name} (tag={tag}): {msg}"
+                    "Missing SOH after data field {tag} at offset {offset}"
+                )
+            }
+            ReadError::DuplicateTag { offset, tag } => {
+                write!(
+                    f,
+                    "Duplicate tag {tag} at offset {offset}"
+                )
+            }
+            ReadError::TagOutOfOrder { offset, tag } => {
+                write!(
+                    f,
+                    "Tag {tag} out of order at offset {offset}"
+                )
+            }
+            ReadError::UnknownTag { offset, tag } => {
+                write!(
+                    f,
+                    "Unknown tag {tag} at offset {offset}"
+                )
+            }
+            ReadError::GroupCountMismatch {
+                offset,
+                tag,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Repeating group {tag} at offset {offset} declares {expected} entries, found {actual}"
+                )
+            }
+            ReadError::TagNotDefinedForMessage { offset, tag } => {
+                write!(
+                    f,
+                    "Tag {tag} not defined for this message type at offset {offset}"
+                )
+            }
+            ReadError::EmptyValue { offset, tag } => {
+                write!(
+                    f,
+                    "Tag {tag} specified without a value at offset {offset}"
+                )
+            }
+            ReadError::InvalidMsgType { offset } => {
+                write!(
+                    f,
+                    "Invalid MsgType at offset {offset}"
                 )
             }
         }
     }
 }
 
-impl std::error::Error for ReadError {}
+impl core::error::Error for ReadError {}
+
+/// Write `Name (tag=N)` or `tag N`, followed by ` at offset O` when known.
+fn write_field_position(
+    f: &mut core::fmt::Formatter<'_>,
+    name: &str,
+    tag: u32,
+    offset: Option<usize>,
+) -> core::fmt::Result {
+    if name.is_empty() {
+        write!(f, "tag {tag}")?;
+    } else {
+        write!(f, "{name} (tag={tag})")?;
+    }
+    match offset {
+        Some(offset) => write!(f, " at offset {offset}"),
+        None => Ok(()),
+    }
+}
 
 impl ReadError {
+    /// Byte offset in the message buffer where the problem was detected.
+    ///
+    /// Returns `None` for `MissingRequiredFields`, and for `InvalidValue` and
+    /// `InvalidFormat` when the value was not read from a message buffer.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ReadError::MalformedTag { offset, .. }
+            | ReadError::MissingEquals { offset, .. }
+            | ReadError::UnexpectedEnd { offset, .. }
+            | ReadError::MissingDelimiter { offset, .. }
+            | ReadError::DuplicateTag { offset, .. }
+            | ReadError::TagOutOfOrder { offset, .. }
+            | ReadError::UnknownTag { offset, .. }
+            | ReadError::GroupCountMismatch { offset, .. }
+            | ReadError::TagNotDefinedForMessage { offset, .. }
+            | ReadError::EmptyValue { offset, .. }
+            | ReadError::InvalidMsgType { offset } => Some(*offset),
+            ReadError::InvalidValue { offset, .. } | ReadError::InvalidFormat { offset, .. } => {
+                *offset
+            }
+            ReadError::MissingRequiredFields { .. } => None,
+        }
+    }
+
+    /// Raw tag number the error refers to.
+    ///
+    /// Returns `None` for `MissingRequiredFields` from generated readers, which
+    /// may cover several members.
+    pub fn tag(&self) -> Option<u32> {
+        match self {
+            ReadError::InvalidValue { tag, .. }
+            | ReadError::InvalidFormat { tag, .. }
+            | ReadError::MalformedTag { tag, .. }
+            | ReadError::MissingEquals { tag, .. }
+            | ReadError::UnexpectedEnd { tag, .. }
+            | ReadError::MissingDelimiter { tag, .. }
+            | ReadError::DuplicateTag { tag, .. }
+            | ReadError::TagOutOfOrder { tag, .. }
+            | ReadError::UnknownTag { tag, .. }
+            | ReadError::GroupCountMismatch { tag, .. }
+            | ReadError::TagNotDefinedForMessage { tag, .. }
+            | ReadError::EmptyValue { tag, .. } => Some(*tag),
+            ReadError::InvalidMsgType { .. } => Some(35),
+            ReadError::MissingRequiredFields { tag, .. } => *tag,
+        }
+    }
+
+    /// Map this error to the FIX SessionRejectReason(373) code for a session-level Reject.
+    ///
+    /// See [`session_reject_reason`] for the code constants. Garbled framing
+    /// (missing `=`, truncated buffer) maps to `OTHER` (99).
+    ///
+    /// # Example
+    /// ```
+    /// # use fix44_forge_helpers::*;
+    /// let error = ReadError::DuplicateTag { offset: 30, tag: 55 };
+    /// assert_eq!(
+    ///     error.session_reject_reason(),
+    ///     session_reject_reason::TAG_APPEARS_MORE_THAN_ONCE
+    /// );
+    /// ```
+    pub fn session_reject_reason(&self) -> u16 {
+        use session_reject_reason::*;
+        match self {
+            ReadError::MissingRequiredFields { .. } => REQUIRED_TAG_MISSING,
+            ReadError::InvalidValue { .. } => VALUE_IS_INCORRECT,
+            ReadError::InvalidFormat { .. } => INCORRECT_DATA_FORMAT,
+            ReadError::MalformedTag { .. } => INVALID_TAG_NUMBER,
+            ReadError::MissingEquals { .. } | ReadError::UnexpectedEnd { .. } => OTHER,
+            ReadError::MissingDelimiter { .. } => INCORRECT_DATA_FORMAT,
+            ReadError::DuplicateTag { .. } => TAG_APPEARS_MORE_THAN_ONCE,
+            ReadError::TagOutOfOrder { .. } => TAG_SPECIFIED_OUT_OF_REQUIRED_ORDER,
+            ReadError::UnknownTag { .. } => UNDEFINED_TAG,
+            ReadError::GroupCountMismatch { .. } => INCORRECT_NUMINGROUP_COUNT,
+            ReadError::TagNotDefinedForMessage { .. } => TAG_NOT_DEFINED_FOR_MESSAGE_TYPE,
+            ReadError::EmptyValue { .. } => TAG_SPECIFIED_WITHOUT_A_VALUE,
+            ReadError::InvalidMsgType { .. } => INVALID_MSGTYPE,
+        }
+    }
+
+    /// Tag number to report in RefTagID(371) of a session-level Reject.
+    ///
+    /// For `MissingRequiredFields` from generated readers this is the tag (or
+    /// count tag) of the first missing member; otherwise it is [`tag()`](ReadError::tag).
+    pub fn ref_tag_id(&self) -> Option<u32> {
+        match self {
+            ReadError::MissingRequiredFields { tag: Some(tag), .. } => Some(*tag),
+            ReadError::MissingRequiredFields { .. } => self
+                .missing_members()
+                .next()
+                .map(|member| member.tag as u32),
+            _ => self.tag(),
+        }
+    }
+
+    /// Iterate the missing required members of `ReadError::MissingRequiredFields`
+    /// without allocating.
+    ///
+    /// The iterator is empty for other variants.
+    ///
+    /// # Example
+    /// ```
+    /// # use fix44_forge_helpers::*;
+    /// static META: [(&str, u16, MemberKind); 2] =
+    ///     [("Symbol", 55, MemberKind::Field), ("Parties", 453, MemberKind::Group)];
+    /// let error = ReadError::MissingRequiredFields {
+    ///     missing_mask: MissingMask::from_bits(0b10),
+    ///     meta: &META,
+    ///     tag: None,
+    /// };
+    /// let first = error.missing_members().next().unwrap();
+    /// assert_eq!(first.name, "Parties");
+    /// assert_eq!(first.tag, 453);
+    /// assert_eq!(first.kind, MemberKind::Group);
+    /// ```
+    pub fn missing_members(&self) -> MissingMembers<'_> {
+        match self {
+            ReadError::MissingRequiredFields {
+                missing_mask, meta, ..
+            } => MissingMembers {
+                mask: Some(missing_mask),
+                meta,
+                index: 0,
+            },
+            _ => MissingMembers {
+                mask: None,
+                meta: &[],
+                index: 0,
+            },
+        }
+    }
+
     /// Returns the list of names of missing required members when this is
     /// `ReadError::MissingRequiredFields`.
     ///
     /// Returns `Some(Vec::new())` if the variant is present but no bits are
-    /// actually missing (mask == 0), and `None` for other variants.
+    /// actually missing (empty mask), and `None` for other variants. Prefer
+    /// [`missing_members`](ReadError::missing_members) on hot paths; this allocates.
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
     #[allow(dead_code)]
-    pub fn missing_member_names(&self) -> Option<Vec<&'static str>> {
+    pub fn missing_member_names(&self) -> Option<alloc::vec::Vec<&'static str>> {
         match self {
-            ReadError::MissingRequiredFields { missing_mask, meta } => {
-                let mut v = Vec::new();
-                if *missing_mask != 0 {
-                    for (i, (name, _tag, _kind)) in meta.iter().enumerate() {
-                        if (missing_mask >> i) & 1 == 1 {
-                            v.push(*name);
-                        }
-                    }
-                }
-                Some(v)
-            }
+            ReadError::MissingRequiredFields { .. } => Some(
+                self.missing_members()
+                    .map(|member| member.name)
+                    .collect(),
+            ),
             _ => None,
         }
     }
@@ -119,8 +696,9 @@ mod tests {
     #[test]
     fn test_read_error_display_no_missing_fields() {
         let error = ReadError::MissingRequiredFields {
-            missing_mask: 0,
-            meta: &[("TestField", 1, 0)],
+            missing_mask: MissingMask::from_bits(0),
+            meta: &[("TestField", 1, MemberKind::Field)],
+            tag: None,
         };
         assert_eq!(
             error.to_string(),
@@ -130,10 +708,11 @@ mod tests {
 
     #[test]
     fn test_read_error_display_missing_fields() {
-        let meta = &[("Field1", 1, 0), ("Component1", 2, 1), ("Group1", 3, 2)];
+        let meta = &[("Field1", 1, MemberKind::Field), ("Component1", 2, MemberKind::Component), ("Group1", 3, MemberKind::Group)];
         let error = ReadError::MissingRequiredFields {
-            missing_mask: 0b101, // First and third items missing
+            missing_mask: MissingMask::from_bits(0b101), // First and third items missing
             meta,
+            tag: None,
         };
         let error_str = error.to_string();
         assert!(error_str.contains("Missing required members"));
@@ -147,6 +726,7 @@ mod tests {
             name: "TestField",
             tag: 42,
             msg: "Expected numeric value",
+            offset: None,
         };
         assert_eq!(
             error.to_string(),
@@ -154,14 +734,287 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_read_error_display_invalid_format() {
+        let error = ReadError::InvalidFormat {
+            name: "Text",
+            tag: 58,
+            msg: "invalid UTF-8",
+            offset: None,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid format for Text (tag=58): invalid UTF-8"
+        );
+    }
+
+    #[test]
+    fn test_read_error_display_runtime_validation() {
+        let error = ReadError::InvalidValue {
+            name: "",
+            tag: 54,
+            msg: "unknown side",
+            offset: Some(20),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid value for tag 54 at offset 20: unknown side"
+        );
+        assert_eq!(error.offset(), Some(20));
+
+        let error = ReadError::MissingRequiredFields {
+            missing_mask: MissingMask::new(),
+            meta: &[],
+            tag: Some(112),
+        };
+        assert_eq!(error.to_string(), "Required tag 112 missing");
+        assert_eq!(error.tag(), Some(112));
+        assert_eq!(error.missing_members().count(), 0);
+    }
+
+    #[test]
+    fn test_read_error_display_positional() {
+        let error = ReadError::MissingEquals {
+            offset: 17,
+            tag: 34,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Missing '=' after tag 34 at offset 17"
+        );
+
+        let error = ReadError::GroupCountMismatch {
+            offset: 40,
+            tag: 453,
+            expected: 3,
+            actual: 2,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Repeating group 453 at offset 40 declares 3 entries, found 2"
+        );
+    }
+
+    #[test]
+    fn test_read_error_offset_and_tag() {
+        let error = ReadError::DuplicateTag {
+            offset: 25,
+            tag: 49,
+        };
+        assert_eq!(error.offset(), Some(25));
+        assert_eq!(error.tag(), Some(49));
+
+        let error = ReadError::InvalidValue {
+            name: "Side",
+            tag: 54,
+            msg: "unknown side",
+            offset: None,
+        };
+        assert_eq!(error.offset(), None);
+        assert_eq!(error.tag(), Some(54));
+
+        let error = ReadError::MissingRequiredFields {
+            missing_mask: MissingMask::from_bits(1),
+            meta: &[("Symbol", 55, MemberKind::Field)],
+            tag: None,
+        };
+        assert_eq!(error.offset(), None);
+        assert_eq!(error.tag(), None);
+    }
+
+    #[test]
+    fn test_session_reject_reason() {
+        use session_reject_reason::*;
+        let cases = [
+            (
+                ReadError::MissingRequiredFields {
+                    missing_mask: MissingMask::from_bits(0b10),
+                    meta: &[("Symbol", 55, MemberKind::Field), ("Side", 54, MemberKind::Field)],
+                    tag: None,
+                },
+                REQUIRED_TAG_MISSING,
+                Some(54),
+            ),
+            (
+                ReadError::InvalidValue {
+                    name: "Side",
+                    tag: 54,
+                    msg: "unknown side",
+                    offset: None,
+                },
+                VALUE_IS_INCORRECT,
+                Some(54),
+            ),
+            (
+                ReadError::InvalidFormat {
+                    name: "Text",
+                    tag: 58,
+                    msg: "invalid UTF-8",
+                    offset: None,
+                },
+                INCORRECT_DATA_FORMAT,
+                Some(58),
+            ),
+            (
+                ReadError::MalformedTag { offset: 3, tag: 0 },
+                INVALID_TAG_NUMBER,
+                Some(0),
+            ),
+            (
+                ReadError::MissingEquals { offset: 3, tag: 34 },
+                OTHER,
+                Some(34),
+            ),
+            (
+                ReadError::UnexpectedEnd { offset: 9, tag: 10 },
+                OTHER,
+                Some(10),
+            ),
+            (
+                ReadError::MissingDelimiter { offset: 9, tag: 96 },
+                INCORRECT_DATA_FORMAT,
+                Some(96),
+            ),
+            (
+                ReadError::DuplicateTag { offset: 9, tag: 49 },
+                TAG_APPEARS_MORE_THAN_ONCE,
+                Some(49),
+            ),
+            (
+                ReadError::TagOutOfOrder { offset: 9, tag: 52 },
+                TAG_SPECIFIED_OUT_OF_REQUIRED_ORDER,
+                Some(52),
+            ),
+            (
+                ReadError::UnknownTag { offset: 9, tag: 9999 },
+                UNDEFINED_TAG,
+                Some(9999),
+            ),
+            (
+                ReadError::GroupCountMismatch {
+                    offset: 9,
+                    tag: 453,
+                    expected: 2,
+                    actual: 1,
+                },
+                INCORRECT_NUMINGROUP_COUNT,
+                Some(453),
+            ),
+            (
+                ReadError::MissingRequiredFields {
+                    missing_mask: MissingMask::new(),
+                    meta: &[],
+                    tag: Some(49),
+                },
+                REQUIRED_TAG_MISSING,
+                Some(49),
+            ),
+            (
+                ReadError::TagNotDefinedForMessage { offset: 9, tag: 44 },
+                TAG_NOT_DEFINED_FOR_MESSAGE_TYPE,
+                Some(44),
+            ),
+            (
+                ReadError::EmptyValue { offset: 9, tag: 58 },
+                TAG_SPECIFIED_WITHOUT_A_VALUE,
+                Some(58),
+            ),
+            (
+                ReadError::InvalidMsgType { offset: 12 },
+                INVALID_MSGTYPE,
+                Some(35),
+            ),
+        ];
+        for (error, reason, ref_tag) in cases {
+            assert_eq!(error.session_reject_reason(), reason, "{error}");
+            assert_eq!(error.ref_tag_id(), ref_tag, "{error}");
+        }
+    }
+
+    #[test]
+    fn test_missing_mask_beyond_64_members() {
+        static META: [(&str, u16, MemberKind); 130] = {
+            let mut meta = [("Filler", 0, MemberKind::Field); 130];
+            meta[0] = ("First", 1, MemberKind::Field);
+            meta[64] = ("Middle", 65, MemberKind::Component);
+            meta[129] = ("Last", 130, MemberKind::Group);
+            meta
+        };
+        let mut mask = MissingMask::new();
+        assert!(mask.is_empty());
+        mask.set(64);
+        mask.set(129);
+        mask.set(MissingMask::CAPACITY); // ignored
+        assert_eq!(mask.count(), 2);
+        assert!(!mask.is_set(0));
+        assert!(!mask.is_set(MissingMask::CAPACITY));
+
+        let error = ReadError::MissingRequiredFields {
+            missing_mask: mask,
+            meta: &META,
+            tag: None,
+        };
+        let names: Vec<&str> = error.missing_members().map(|m| m.name).collect();
+        assert_eq!(names, ["Middle", "Last"]);
+        assert_eq!(error.ref_tag_id(), Some(65));
+        assert_eq!(
+            error.to_string(),
+            "Missing required members (mask=0x200000000000000010000000000000000): component Middle(tag=65), group Last(countTag=130)"
+        );
+
+        // Single-word masks keep the compact hex rendering
+        assert_eq!(format!("{:X}", MissingMask::from_bits(0x5)), "5");
+        assert_eq!(format!("{:X}", MissingMask::new()), "0");
+    }
+
+    #[test]
+    fn test_missing_members_iter() {
+        let meta = &[
+            ("Symbol", 55, MemberKind::Field),
+            ("Instrument", 55, MemberKind::Component),
+            ("Parties", 453, MemberKind::Group),
+        ];
+        let error = ReadError::MissingRequiredFields {
+            missing_mask: MissingMask::from_bits(0b110),
+            meta,
+            tag: None,
+        };
+        let members: Vec<MissingMember> = error.missing_members().collect();
+        assert_eq!(
+            members,
+            [
+                MissingMember {
+                    name: "Instrument",
+                    tag: 55,
+                    kind: MemberKind::Component,
+                },
+                MissingMember {
+                    name: "Parties",
+                    tag: 453,
+                    kind: MemberKind::Group,
+                },
+            ]
+        );
+        assert_eq!(members[0].to_string(), "component Instrument(tag=55)");
+        assert_eq!(members[1].to_string(), "group Parties(countTag=453)");
+
+        let error = ReadError::DuplicateTag {
+            offset: 9,
+            tag: 49,
+        };
+        assert_eq!(error.missing_members().count(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
     #[test]
     fn test_missing_member_names() {
-        let meta = &[("Field1", 1, 0), ("Field2", 2, 0), ("Field3", 3, 0)];
+        let meta = &[("Field1", 1, MemberKind::Field), ("Field2", 2, MemberKind::Field), ("Field3", 3, MemberKind::Field)];
 
         // Test with missing fields
         let error = ReadError::MissingRequiredFields {
-            missing_mask: 0b101, // First and third fields missing
+            missing_mask: MissingMask::from_bits(0b101), // First and third fields missing
             meta,
+            tag: None,
         };
         let names = error
             .missing_member_names()
@@ -173,8 +1026,9 @@ mod tests {
 
         // Test with no missing fields
         let error = ReadError::MissingRequiredFields {
-            missing_mask: 0,
+            missing_mask: MissingMask::from_bits(0),
             meta,
+            tag: None,
         };
         let names = error
             .missing_member_names()
@@ -186,6 +1040,7 @@ mod tests {
             name: "TestField",
             tag: 42,
             msg: "Test message",
+            offset: None,
         };
         assert!(error
             .missing_member_names()
@@ -0,0 +1,525 @@
+//! Field-level iteration over raw FIX messages.
+//!
+//! This module provides a zero-allocation iterator that walks a FIX byte stream
+//! field by field, yielding the numeric tag and the raw value slice. Values are
+//! left untouched so they can be handed straight to the `read_*` functions.
+//!
+//! # Length-prefixed data fields
+//!
+//! FIX `data` fields (RawData(96), XmlData(213), EncodedText(355), ...) may contain
+//! SOH and are always preceded by their length field. When the iterator sees a
+//! known length tag, it remembers the declared length and reads exactly that many
+//! bytes for the matching data tag instead of scanning for SOH.
+
+use crate::errors::ReadError;
+use crate::reading::read_u32;
+
+/// FIX 4.4 length/data tag pairs: (length tag, data tag).
+pub const DATA_FIELD_PAIRS: &[(u32, u32)] = &[
+    (90, 91),   // SecureDataLen -> SecureData
+    (93, 89),   // SignatureLength -> Signature
+    (95, 96),   // RawDataLength -> RawData
+    (212, 213), // XmlDataLen -> XmlData
+    (348, 349), // EncodedIssuerLen -> EncodedIssuer
+    (350, 351), // EncodedSecurityDescLen -> EncodedSecurityDesc
+    (352, 353), // EncodedListExecInstLen -> EncodedListExecInst
+    (354, 355), // EncodedTextLen -> EncodedText
+    (356, 357), // EncodedSubjectLen -> EncodedSubject
+    (358, 359), // EncodedHeadlineLen -> EncodedHeadline
+    (360, 361), // EncodedAllocTextLen -> EncodedAllocText
+    (362, 363), // EncodedUnderlyingIssuerLen -> EncodedUnderlyingIssuer
+    (364, 365), // EncodedUnderlyingSecurityDescLen -> EncodedUnderlyingSecurityDesc
+    (445, 446), // EncodedListStatusTextLen -> EncodedListStatusText
+    (618, 619), // EncodedLegIssuerLen -> EncodedLegIssuer
+    (621, 622), // EncodedLegSecurityDescLen -> EncodedLegSecurityDesc
+];
+
+/// Return the data tag announced by a FIX 4.4 length tag, if `tag` is one.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::data_tag_for_length_tag;
+/// assert_eq!(data_tag_for_length_tag(95), Some(96));
+/// assert_eq!(data_tag_for_length_tag(34), None);
+/// ```
+#[inline(always)]
+pub const fn data_tag_for_length_tag(tag: u32) -> Option<u32> {
+    let mut i = 0;
+    while i < DATA_FIELD_PAIRS.len() {
+        if DATA_FIELD_PAIRS[i].0 == tag {
+            return Some(DATA_FIELD_PAIRS[i].1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Zero-allocation iterator over the `(tag, value)` pairs of a FIX byte stream.
+///
+/// Iteration stops at the end of the buffer or at the first malformed field
+/// (non-numeric tag, missing `=`, or a value not terminated by SOH).
+/// Use [`try_next`](TagValueIter::try_next) to learn why and where iteration stopped.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let msg = b"35=D\x0195=3\x0196=a\x01b\x0134=7\x01";
+/// let mut it = TagValueIter::new(msg);
+/// assert_eq!(it.next(), Some((35, &b"D"[..])));
+/// assert_eq!(it.next(), Some((95, &b"3"[..])));
+/// assert_eq!(it.next(), Some((96, &b"a\x01b"[..])));
+/// assert_eq!(it.next(), Some((34, &b"7"[..])));
+/// assert_eq!(it.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagValueIter<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    /// Pending (data tag, declared length) announced by the previous length field.
+    pending_data: Option<(u32, usize)>,
+}
+
+impl<'a> TagValueIter<'a> {
+    /// Create an iterator over the fields in `buf`.
+    #[inline(always)]
+    pub fn new(buf: &'a [u8]) -> Self {
+        TagValueIter {
+            buf,
+            pos: 0,
+            pending_data: None,
+        }
+    }
+
+    /// Byte offset of the next field to be read.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The bytes not yet consumed by the iterator.
+    #[inline(always)]
+    pub fn remainder(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Read the next field, reporting malformed input as a position-aware [`ReadError`].
+    ///
+    /// Returns `Ok(None)` at the end of the buffer. On error the iterator does not
+    /// advance, so [`position`](TagValueIter::position) still points at the start
+    /// of the offending field.
+    ///
+    /// # Example
+    /// ```
+    /// # use fix44_forge_helpers::*;
+    /// let mut it = TagValueIter::new(b"35=D\x0134\x01");
+    /// assert_eq!(it.try_next(), Ok(Some((35, &b"D"[..]))));
+    /// assert_eq!(it.try_next(), Err(ReadError::MissingEquals { offset: 7, tag: 34 }));
+    /// ```
+    #[inline(always)]
+    pub fn try_next(&mut self) -> Result<Option<(u32, &'a [u8])>, ReadError> {
+        let buf = self.buf;
+        let len = buf.len();
+        let mut i = self.pos;
+        if i >= len {
+            return Ok(None);
+        }
+
+        // Tag digits up to '='
+        let tag_start = i;
+        let mut tag: u32 = 0;
+        while i < len {
+            let b = unsafe { *buf.get_unchecked(i) };
+            if !b.is_ascii_digit() {
+                break;
+            }
+            tag = tag.wrapping_mul(10).wrapping_add((b - b'0') as u32);
+            i += 1;
+        }
+        if i == len {
+            return Err(ReadError::UnexpectedEnd { offset: i, tag });
+        }
+        match unsafe { *buf.get_unchecked(i) } {
+            b'=' if i != tag_start => {}
+            0x01 if i != tag_start => return Err(ReadError::MissingEquals { offset: i, tag }),
+            _ => return Err(ReadError::MalformedTag { offset: i, tag }),
+        }
+        i += 1;
+
+        // Value: exact length for announced data fields, SOH-terminated otherwise
+        let value_start = i;
+        let value_end = match self.pending_data {
+            Some((data_tag, data_len)) if data_tag == tag => {
+                // The length comes off the wire and may overflow a 32-bit usize
+                let end = match value_start.checked_add(data_len) {
+                    Some(end) if end < len => end,
+                    _ => return Err(ReadError::UnexpectedEnd { offset: len, tag }),
+                };
+                if buf[end] != 0x01 {
+                    return Err(ReadError::MissingDelimiter { offset: end, tag });
+                }
+                end
+            }
+            _ => {
+                while i < len && unsafe { *buf.get_unchecked(i) } != 0x01 {
+                    i += 1;
+                }
+                if i == len {
+                    return Err(ReadError::UnexpectedEnd { offset: len, tag });
+                }
+                i
+            }
+        };
+        let value = &buf[value_start..value_end];
+
+        self.pending_data =
+            data_tag_for_length_tag(tag).map(|data_tag| (data_tag, read_u32(value) as usize));
+        self.pos = value_end + 1;
+        Ok(Some((tag, value)))
+    }
+}
+
+impl<'a> Iterator for TagValueIter<'a> {
+    type Item = (u32, &'a [u8]);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(u32, &'a [u8])> {
+        self.try_next().ok().flatten()
+    }
+}
+
+/// A field yielded by [`LenientFieldIter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LenientField<'a> {
+    /// The field's tag, or `None` for bytes that do not form a field
+    pub tag: Option<u32>,
+    /// The field's value, or the bytes that do not form a field, without SOH
+    pub value: &'a [u8],
+    /// Byte offset of `value` in the buffer
+    pub offset: usize,
+    /// Whether `value` is followed by SOH; only the last item can be unterminated
+    pub terminated: bool,
+}
+
+/// Iterator over the fields of a possibly malformed or partially written FIX
+/// byte stream.
+///
+/// Well-formed fields are read as by [`TagValueIter`]. Bytes that do not form
+/// a field are yielded with no tag up to the next SOH, and iteration carries
+/// on after it, so the fields that follow are still seen. A last field with no
+/// SOH (a message still being written) is yielded unterminated, with its tag
+/// if it starts with one. This is the walk [`FixDisplay`](crate::FixDisplay)
+/// renders and masks, so tools that mask values themselves should use it too.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let fields: Vec<_> = LenientFieldIter::new(b"35=D\x01oops\x0154=1\x0158=ha")
+///     .map(|f| (f.tag, f.value, f.terminated))
+///     .collect();
+/// assert_eq!(
+///     fields,
+///     [
+///         (Some(35), &b"D"[..], true),
+///         (None, b"oops", true),
+///         (Some(54), b"1", true),
+///         (Some(58), b"ha", false),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct LenientFieldIter<'a> {
+    inner: TagValueIter<'a>,
+}
+
+impl<'a> LenientFieldIter<'a> {
+    /// Create an iterator over the fields in `buf`.
+    #[inline(always)]
+    pub fn new(buf: &'a [u8]) -> Self {
+        LenientFieldIter {
+            inner: TagValueIter::new(buf),
+        }
+    }
+}
+
+impl<'a> Iterator for LenientFieldIter<'a> {
+    type Item = LenientField<'a>;
+
+    fn next(&mut self) -> Option<LenientField<'a>> {
+        let it = &mut self.inner;
+        let start = it.pos;
+        match it.try_next() {
+            Ok(Some((tag, value))) => {
+                return Some(LenientField {
+                    tag: Some(tag),
+                    value,
+                    offset: it.pos - 1 - value.len(),
+                    terminated: true,
+                });
+            }
+            Ok(None) => return None,
+            Err(_) => {}
+        }
+
+        // Not a field: skip to the next SOH and start afresh after it
+        let rest = &it.buf[start..];
+        it.pending_data = None;
+        if let Some(soh) = rest.iter().position(|&b| b == 0x01) {
+            it.pos = start + soh + 1;
+            return Some(LenientField {
+                tag: None,
+                value: &rest[..soh],
+                offset: start,
+                terminated: true,
+            });
+        }
+        it.pos = it.buf.len();
+        let tag_len = rest
+            .iter()
+            .position(|&b| b == b'=')
+            .filter(|&eq| (1..=10).contains(&eq) && rest[..eq].iter().all(u8::is_ascii_digit));
+        Some(match tag_len {
+            Some(eq) => LenientField {
+                tag: Some(
+                    rest[..eq]
+                        .iter()
+                        .fold(0u32, |tag, &d| tag.wrapping_mul(10).wrapping_add((d - b'0') as u32)),
+                ),
+                value: &rest[eq + 1..],
+                offset: start + eq + 1,
+                terminated: false,
+            },
+            None => LenientField {
+                tag: None,
+                value: rest,
+                offset: start,
+                terminated: false,
+            },
+        })
+    }
+}
+
+/// Standard header fields of a raw FIX message, borrowed from the message bytes.
+///
+/// Fields absent from the message are left empty (or 0 for MsgSeqNum).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MessageHeader<'a> {
+    /// BeginString(8)
+    pub begin_string: &'a [u8],
+    /// MsgType(35)
+    pub msg_type: &'a [u8],
+    /// MsgSeqNum(34)
+    pub msg_seq_num: u32,
+    /// SenderCompID(49)
+    pub sender_comp_id: &'a [u8],
+    /// TargetCompID(56)
+    pub target_comp_id: &'a [u8],
+}
+
+impl<'a> MessageHeader<'a> {
+    /// Extract the standard header fields from a raw message.
+    ///
+    /// Best effort: scanning stops at the first malformed field or once all
+    /// header fields have been seen, so a message rejected for a body problem
+    /// still yields a usable header.
+    ///
+    /// # Example
+    /// ```
+    /// # use fix44_forge_helpers::MessageHeader;
+    /// let msg = b"8=FIX.4.4\x019=25\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=7\x01";
+    /// let header = MessageHeader::parse(msg);
+    /// assert_eq!(header.msg_type, b"D");
+    /// assert_eq!(header.msg_seq_num, 7);
+    /// assert_eq!(header.sender_comp_id, b"CLIENT");
+    /// ```
+    pub fn parse(msg: &'a [u8]) -> Self {
+        let mut header = MessageHeader::default();
+        let mut seen: u8 = 0;
+        for (tag, value) in TagValueIter::new(msg) {
+            let bit = match tag {
+                8 => {
+                    header.begin_string = value;
+                    1
+                }
+                35 => {
+                    header.msg_type = value;
+                    2
+                }
+                34 => {
+                    header.msg_seq_num = read_u32(value);
+                    4
+                }
+                49 => {
+                    header.sender_comp_id = value;
+                    8
+                }
+                56 => {
+                    header.target_comp_id = value;
+                    16
+                }
+                _ => 0,
+            };
+            seen |= bit;
+            if seen == 0b11111 {
+                break;
+            }
+        }
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writing::{write_tag_and_data, write_tag_and_str, write_tag_and_u32};
+
+    #[test]
+    fn test_tag_value_iter_basic() {
+        let msg = b"8=FIX.4.4\x019=12\x0135=D\x01";
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(msg).collect();
+        assert_eq!(
+            fields,
+            [(8, &b"FIX.4.4"[..]), (9, &b"12"[..]), (35, &b"D"[..])]
+        );
+    }
+
+    #[test]
+    fn test_tag_value_iter_data_field_with_soh() {
+        let mut buf = [0u8; 64];
+        let mut pos = 0;
+        pos += write_tag_and_str(&mut buf, pos, b"35=", "B");
+        pos += write_tag_and_data(&mut buf, pos, b"95=", b"96=", b"x\x0110=000\x01y");
+        pos += write_tag_and_u32(&mut buf, pos, b"34=", 2);
+
+        let mut it = TagValueIter::new(&buf[..pos]);
+        assert_eq!(it.next(), Some((35, &b"B"[..])));
+        assert_eq!(it.next(), Some((95, &b"10"[..])));
+        assert_eq!(it.next(), Some((96, &b"x\x0110=000\x01y"[..])));
+        assert_eq!(it.next(), Some((34, &b"2"[..])));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.position(), pos);
+    }
+
+    #[test]
+    fn test_tag_value_iter_length_only_applies_to_next_field() {
+        // Declared length is ignored when the data tag does not follow immediately
+        let msg = b"354=3\x0158=a\x01b\x01";
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(msg).collect();
+        assert_eq!(
+            fields,
+            [(354, &b"3"[..]), (58, &b"a"[..])]
+        );
+    }
+
+    #[test]
+    fn test_tag_value_iter_malformed() {
+        // Missing '='
+        let mut it = TagValueIter::new(b"35=D\x0134\x01");
+        assert_eq!(it.next(), Some((35, &b"D"[..])));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.position(), 5);
+        assert_eq!(it.remainder(), b"34\x01");
+
+        // Non-numeric tag
+        assert_eq!(TagValueIter::new(b"A=1\x01").next(), None);
+
+        // Unterminated value
+        assert_eq!(TagValueIter::new(b"35=D").next(), None);
+
+        // Data length runs past the buffer
+        let mut it = TagValueIter::new(b"95=10\x0196=abc\x01");
+        assert_eq!(it.next(), Some((95, &b"10"[..])));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_try_next_errors() {
+        let mut it = TagValueIter::new(b"35=D\x013a=1\x01");
+        assert_eq!(it.try_next(), Ok(Some((35, &b"D"[..]))));
+        assert_eq!(it.try_next(), Err(ReadError::MalformedTag { offset: 6, tag: 3 }));
+        // Iterator does not advance past the error
+        assert_eq!(it.position(), 5);
+        assert_eq!(it.try_next(), Err(ReadError::MalformedTag { offset: 6, tag: 3 }));
+
+        assert_eq!(
+            TagValueIter::new(b"=1\x01").try_next(),
+            Err(ReadError::MalformedTag { offset: 0, tag: 0 })
+        );
+        assert_eq!(
+            TagValueIter::new(b"\x01").try_next(),
+            Err(ReadError::MalformedTag { offset: 0, tag: 0 })
+        );
+        let mut it = TagValueIter::new(b"8=FIX.4.4\x019");
+        it.try_next().unwrap();
+        assert_eq!(it.try_next(), Err(ReadError::UnexpectedEnd { offset: 11, tag: 9 }));
+
+        assert_eq!(
+            TagValueIter::new(b"58=abc").try_next(),
+            Err(ReadError::UnexpectedEnd { offset: 6, tag: 58 })
+        );
+
+        let mut it = TagValueIter::new(b"95=2\x0196=abc\x01");
+        it.try_next().unwrap();
+        assert_eq!(it.try_next(), Err(ReadError::MissingDelimiter { offset: 10, tag: 96 }));
+
+        let mut it = TagValueIter::new(b"95=4294967295\x0196=abc\x01");
+        it.try_next().unwrap();
+        assert_eq!(it.try_next(), Err(ReadError::UnexpectedEnd { offset: 21, tag: 96 }));
+
+        let mut it = TagValueIter::new(b"");
+        assert_eq!(it.try_next(), Ok(None));
+    }
+
+    #[test]
+    fn test_lenient_field_iter() {
+        let fields: Vec<_> = LenientFieldIter::new(b"35=D\x01\x0195=9\x0196=ab\x0134=7\x01x=1")
+            .map(|f| (f.tag, f.value, f.offset, f.terminated))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                (Some(35), &b"D"[..], 3, true),
+                (None, b"", 5, true),
+                (Some(95), b"9", 9, true),
+                // RawData shorter than announced: skipped up to its SOH
+                (None, b"96=ab", 11, true),
+                (Some(34), b"7", 20, true),
+                (None, b"x=1", 22, false),
+            ]
+        );
+
+        let mut it = LenientFieldIter::new(b"58=");
+        assert_eq!(
+            it.next(),
+            Some(LenientField {
+                tag: Some(58),
+                value: b"",
+                offset: 3,
+                terminated: false,
+            })
+        );
+        assert_eq!(it.next(), None);
+        assert_eq!(LenientFieldIter::new(b"").next(), None);
+    }
+
+    #[test]
+    fn test_message_header_parse() {
+        let msg = b"8=FIX.4.4\x019=40\x0135=8\x0134=12\x0149=BRKR\x0152=20240101-00:00:00.000\x0156=CLNT\x0155=X\x01";
+        let header = MessageHeader::parse(msg);
+        assert_eq!(
+            header,
+            MessageHeader {
+                begin_string: b"FIX.4.4",
+                msg_type: b"8",
+                msg_seq_num: 12,
+                sender_comp_id: b"BRKR",
+                target_comp_id: b"CLNT",
+            }
+        );
+
+        // Malformed body: header still extracted up to the bad field
+        let header = MessageHeader::parse(b"8=FIX.4.4\x0135=D\x0134=3\x01x=1\x0149=A\x01");
+        assert_eq!(header.msg_type, b"D");
+        assert_eq!(header.msg_seq_num, 3);
+        assert_eq!(header.sender_comp_id, b"");
+    }
+}
@@ -0,0 +1,616 @@
+//! Append-only, memory-mapped [`MessageStore`] that survives restarts.
+//!
+//! A store directory holds three files:
+//!
+//! - `messages.log` - stored messages back to back, byte for byte as sent
+//! - `messages.idx` - one 20-byte little-endian record per message:
+//!   MsgSeqNum (`u32`), length (`u32`), offset into the log (`u64`) and a
+//!   CRC-32 over those 16 bytes and the message
+//! - `seqnums` - two 16-byte slots, each holding a generation (`u32`), the next
+//!   sender and next target MsgSeqNum (`u32` each) and a CRC-32 of the three
+//!
+//! Each message is written to the log before its index record, and the log is
+//! read back through a shared read-only memory map, so [`get`](MessageStore::get)
+//! borrows straight from the page cache. When the store is reopened after a
+//! crash, the index is cut at the first partial record, record pointing past
+//! the end of the log or record whose CRC does not match its message, and the
+//! log is cut after the last message still indexed; every message that
+//! survives has been checked against its CRC.
+//!
+//! Sequence number updates alternate between the two `seqnums` slots, so a
+//! write torn by a crash leaves the previous numbers readable in the other
+//! slot; the slot with the highest generation and a valid CRC wins.
+//!
+//! Durability is controlled by [`FsyncPolicy`]; whatever the policy,
+//! [`sync`](MessageStore::sync) forces everything to disk.
+//!
+//! ```no_run
+//! # use fix44_forge_helpers::*;
+//! let mut store = FileStore::open("/var/lib/fix/CLIENT-BROKER", FsyncPolicy::Always)?;
+//! let config = SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Initiator);
+//! let session = Session::with_sequences(config, store.sequences(), 1_700_000_000, 0);
+//! # Ok::<(), StoreError>(())
+//! ```
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read};
+use std::os::unix::fs::FileExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::ptr::NonNull;
+
+use crate::store::{MessageStore, StoreError};
+
+/// Size of one `messages.idx` record.
+const INDEX_RECORD_LEN: usize = 20;
+
+/// Size of one of the two `seqnums` slots.
+const SEQNUMS_SLOT_LEN: usize = 16;
+
+/// Smallest log mapping; mappings grow by powers of two.
+const MIN_MAP_LEN: usize = 64 * 1024;
+
+/// When [`FileStore`] calls `fsync` (`fdatasync` where available).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Leave flushing to the operating system; a power loss may lose recent messages
+    Never,
+    /// Sync after every stored message and sequence number update
+    Always,
+    /// Sync after every `n` writes
+    Every(u32),
+}
+
+/// Read-only shared mapping of the log.
+#[derive(Debug)]
+struct Mapping {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// The mapping is read-only and owned by one store
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    fn new(
+        file: &File,
+        len: usize,
+    ) -> io::Result<Self> {
+        // Mapping past the end of the file is fine as long as only bytes below
+        // the file length are read
+        let ptr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mapping {
+            ptr: NonNull::new(ptr.cast()).expect("mmap returned null"),
+            len,
+        })
+    }
+
+    /// # Safety
+    /// `end` must not exceed the mapping or the current file length.
+    unsafe fn slice(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> &[u8] {
+        debug_assert!(start <= end && end <= self.len);
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr().add(start), end - start) }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.len) };
+    }
+}
+
+/// One stored message.
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    seq: u32,
+    len: u32,
+    offset: u64,
+}
+
+impl IndexEntry {
+    /// The record for this entry, checksummed together with `msg`.
+    fn to_bytes(
+        self,
+        msg: &[u8],
+    ) -> [u8; INDEX_RECORD_LEN] {
+        let mut record = [0u8; INDEX_RECORD_LEN];
+        record[0..4].copy_from_slice(&self.seq.to_le_bytes());
+        record[4..8].copy_from_slice(&self.len.to_le_bytes());
+        record[8..16].copy_from_slice(&self.offset.to_le_bytes());
+        let crc = crc32_update(crc32_update(!0, &record[0..16]), msg);
+        record[16..20].copy_from_slice(&(!crc).to_le_bytes());
+        record
+    }
+
+    fn from_bytes(record: &[u8]) -> Self {
+        IndexEntry {
+            seq: u32::from_le_bytes(record[0..4].try_into().unwrap()),
+            len: u32::from_le_bytes(record[4..8].try_into().unwrap()),
+            offset: u64::from_le_bytes(record[8..16].try_into().unwrap()),
+        }
+    }
+
+    fn end(&self) -> u64 {
+        self.offset + self.len as u64
+    }
+}
+
+/// File-backed [`MessageStore`]. See the [module docs](self).
+#[derive(Debug)]
+pub struct FileStore {
+    log: File,
+    index_file: File,
+    seqnums: File,
+    map: Option<Mapping>,
+    log_len: u64,
+    index: Vec<IndexEntry>,
+    next_sender_seq: u32,
+    next_target_seq: u32,
+    /// Generation of the newest `seqnums` slot
+    seqnums_generation: u32,
+    fsync: FsyncPolicy,
+    /// Writes since the last sync
+    unsynced: u32,
+}
+
+impl FileStore {
+    /// Open (or create) the store in directory `dir`, recovering from any
+    /// partially written messages.
+    pub fn open(
+        dir: impl AsRef<Path>,
+        fsync: FsyncPolicy,
+    ) -> Result<Self, StoreError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let open = |name: &str| {
+            OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(dir.join(name))
+        };
+        let log = open("messages.log")?;
+        let mut index_file = open("messages.idx")?;
+        let mut seqnums = open("seqnums")?;
+
+        // Keep the longest prefix of records that are complete, contiguous,
+        // increasing, backed by the log and matching their CRC
+        let mut records = Vec::new();
+        index_file.read_to_end(&mut records)?;
+        let log_on_disk = log.metadata()?.len();
+        let mut index: Vec<IndexEntry> = Vec::with_capacity(records.len() / INDEX_RECORD_LEN);
+        let mut log_len = 0;
+        let mut msg = Vec::new();
+        for record in records.chunks_exact(INDEX_RECORD_LEN) {
+            let entry = IndexEntry::from_bytes(record);
+            let increasing = index.last().is_none_or(|last| entry.seq > last.seq);
+            if entry.offset != log_len || entry.end() > log_on_disk || !increasing {
+                break;
+            }
+            msg.resize(entry.len as usize, 0);
+            log.read_exact_at(&mut msg, entry.offset)?;
+            if entry.to_bytes(&msg) != record {
+                break;
+            }
+            log_len = entry.end();
+            index.push(entry);
+        }
+        index_file.set_len((index.len() * INDEX_RECORD_LEN) as u64)?;
+        log.set_len(log_len)?;
+
+        let mut slots = Vec::new();
+        seqnums.read_to_end(&mut slots)?;
+        let newest = slots
+            .chunks_exact(SEQNUMS_SLOT_LEN)
+            .filter_map(read_seqnums_slot)
+            .max_by_key(|&(generation, _, _)| generation);
+        let (generation, sender, target) = newest.unwrap_or((0, 1, 1));
+        let after_stored = index.last().map_or(1, |last| last.seq + 1);
+
+        let mut store = FileStore {
+            log,
+            index_file,
+            seqnums,
+            map: None,
+            log_len,
+            index,
+            next_sender_seq: sender.max(after_stored),
+            next_target_seq: target.max(1),
+            seqnums_generation: generation,
+            fsync,
+            unsynced: 0,
+        };
+        store.ensure_mapped()?;
+        store.write_seqnums()?;
+        store.sync()?;
+        Ok(store)
+    }
+
+    /// Number of stored messages.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// No messages stored.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// The fsync policy in effect.
+    pub fn fsync_policy(&self) -> FsyncPolicy {
+        self.fsync
+    }
+
+    fn ensure_mapped(&mut self) -> io::Result<()> {
+        let needed = self.log_len as usize;
+        if needed == 0 || self.map.as_ref().is_some_and(|m| m.len >= needed) {
+            return Ok(());
+        }
+        let len = needed.next_power_of_two().max(MIN_MAP_LEN);
+        // Drop the old mapping first so both are never held at once
+        self.map = None;
+        self.map = Some(Mapping::new(&self.log, len)?);
+        Ok(())
+    }
+
+    /// Write the sequence numbers into the older `seqnums` slot.
+    fn write_seqnums(&mut self) -> io::Result<()> {
+        self.seqnums_generation = self.seqnums_generation.wrapping_add(1);
+        let generation = self.seqnums_generation;
+        let mut slot = [0u8; SEQNUMS_SLOT_LEN];
+        slot[0..4].copy_from_slice(&generation.to_le_bytes());
+        slot[4..8].copy_from_slice(&self.next_sender_seq.to_le_bytes());
+        slot[8..12].copy_from_slice(&self.next_target_seq.to_le_bytes());
+        let crc = crc32(&slot[0..12]);
+        slot[12..16].copy_from_slice(&crc.to_le_bytes());
+        let offset = (generation % 2) as u64 * SEQNUMS_SLOT_LEN as u64;
+        self.seqnums.write_all_at(&slot, offset)
+    }
+
+    fn written(&mut self) -> Result<(), StoreError> {
+        self.unsynced += 1;
+        match self.fsync {
+            FsyncPolicy::Never => Ok(()),
+            FsyncPolicy::Always => self.sync(),
+            FsyncPolicy::Every(n) if self.unsynced >= n => self.sync(),
+            FsyncPolicy::Every(_) => Ok(()),
+        }
+    }
+}
+
+impl MessageStore for FileStore {
+    fn store(
+        &mut self,
+        seq: u32,
+        msg: &[u8],
+    ) -> Result<(), StoreError> {
+        if let Some(last) = self.index.last()
+            && seq <= last.seq
+        {
+            return Err(StoreError::SequenceNotIncreasing {
+                seq,
+                last: last.seq,
+            });
+        }
+        let entry = IndexEntry {
+            seq,
+            len: msg.len() as u32,
+            offset: self.log_len,
+        };
+        self.log.write_all_at(msg, entry.offset)?;
+        self.index_file
+            .write_all_at(&entry.to_bytes(msg), (self.index.len() * INDEX_RECORD_LEN) as u64)?;
+        self.log_len = entry.end();
+        self.index.push(entry);
+        self.next_sender_seq = self.next_sender_seq.max(seq + 1);
+        self.write_seqnums()?;
+        self.ensure_mapped()?;
+        self.written()
+    }
+
+    fn get(
+        &self,
+        seq: u32,
+    ) -> Option<&[u8]> {
+        let i = self
+            .index
+            .binary_search_by_key(&seq, |entry| entry.seq)
+            .ok()?;
+        let entry = self.index[i];
+        let map = self.map.as_ref()?;
+        // Every indexed byte lies below log_len, which the mapping covers
+        Some(unsafe { map.slice(entry.offset as usize, entry.end() as usize) })
+    }
+
+    fn next_sender_seq(&self) -> u32 {
+        self.next_sender_seq
+    }
+
+    fn next_target_seq(&self) -> u32 {
+        self.next_target_seq
+    }
+
+    fn set_next_sender_seq(
+        &mut self,
+        seq: u32,
+    ) -> Result<(), StoreError> {
+        self.next_sender_seq = seq;
+        self.write_seqnums()?;
+        self.written()
+    }
+
+    fn set_next_target_seq(
+        &mut self,
+        seq: u32,
+    ) -> Result<(), StoreError> {
+        self.next_target_seq = seq;
+        self.write_seqnums()?;
+        self.written()
+    }
+
+    fn reset(&mut self) -> Result<(), StoreError> {
+        self.map = None;
+        self.index.clear();
+        self.log_len = 0;
+        self.index_file.set_len(0)?;
+        self.log.set_len(0)?;
+        self.next_sender_seq = 1;
+        self.next_target_seq = 1;
+        self.write_seqnums()?;
+        self.sync()
+    }
+
+    fn sync(&mut self) -> Result<(), StoreError> {
+        self.log.sync_data()?;
+        self.index_file.sync_data()?;
+        self.seqnums.sync_data()?;
+        self.unsynced = 0;
+        Ok(())
+    }
+}
+
+/// `(generation, next sender, next target)` of a `seqnums` slot with a valid CRC.
+fn read_seqnums_slot(slot: &[u8]) -> Option<(u32, u32, u32)> {
+    let word = |i: usize| u32::from_le_bytes(slot[i..i + 4].try_into().unwrap());
+    (crc32(&slot[0..12]) == word(12) && word(0) != 0).then(|| (word(0), word(4), word(8)))
+}
+
+/// CRC-32 (IEEE 802.3, reflected) lookup table.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Feed `bytes` into a running (pre-inverted) CRC-32.
+fn crc32_update(
+    crc: u32,
+    bytes: &[u8],
+) -> u32 {
+    bytes.iter().fold(crc, |crc, &b| {
+        CRC32_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !crc32_update(!0, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequence::SequenceManager;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fix44-store-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn message(seq: u32) -> Vec<u8> {
+        format!("8=FIX.4.4\x019=0000\x0135=D\x0134={seq}\x0111=ORDER-{seq}\x0110=000\x01").into_bytes()
+    }
+
+    /// Store messages 1..=3, skip 4 (an unstored heartbeat) and expect 7 next.
+    fn populate(dir: &Path) {
+        let mut store = FileStore::open(dir, FsyncPolicy::Never).unwrap();
+        for seq in 1..=3 {
+            store.store(seq, &message(seq)).unwrap();
+        }
+        store.set_next_sender_seq(5).unwrap();
+        store.set_next_target_seq(7).unwrap();
+    }
+
+    fn truncate(
+        path: PathBuf,
+        by: u64,
+    ) {
+        let file = OpenOptions::new().write(true).open(path).unwrap();
+        let len = file.metadata().unwrap().len();
+        file.set_len(len - by).unwrap();
+    }
+
+    #[test]
+    fn test_survives_restart() {
+        let dir = temp_dir("restart");
+        populate(&dir);
+
+        let mut store = FileStore::open(&dir, FsyncPolicy::Always).unwrap();
+        assert_eq!(store.len(), 3);
+        for seq in 1..=3 {
+            assert_eq!(store.get(seq), Some(&message(seq)[..]));
+        }
+        assert_eq!(store.get(4), None);
+        assert_eq!(store.sequences(), SequenceManager::with_numbers(7, 5));
+
+        // A message larger than the initial mapping forces a remap
+        let big = vec![b'x'; MIN_MAP_LEN * 2];
+        store.store(5, &big).unwrap();
+        assert_eq!(store.get(5), Some(&big[..]));
+        assert_eq!(store.get(1), Some(&message(1)[..]));
+        assert!(matches!(
+            store.store(5, b"again"),
+            Err(StoreError::SequenceNotIncreasing { seq: 5, last: 5 })
+        ));
+        drop(store);
+
+        let store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.get(5), Some(&big[..]));
+        assert_eq!(store.next_sender_seq(), 6);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_crash_with_truncated_log() {
+        let dir = temp_dir("truncated-log");
+        populate(&dir);
+        truncate(dir.join("messages.log"), 3);
+
+        let mut store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(2), Some(&message(2)[..]));
+        assert_eq!(store.get(3), None);
+        // Message 3 may have gone out: the sequence numbers are kept
+        assert_eq!(store.sequences(), SequenceManager::with_numbers(7, 5));
+        assert_eq!(
+            fs::metadata(dir.join("messages.idx")).unwrap().len(),
+            2 * INDEX_RECORD_LEN as u64
+        );
+
+        store.store(5, &message(5)).unwrap();
+        drop(store);
+        let store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.get(5), Some(&message(5)[..]));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_crash_with_truncated_index() {
+        let dir = temp_dir("truncated-index");
+        populate(&dir);
+        // Partial third record: the third message is in the log but unindexed
+        truncate(dir.join("messages.idx"), 5);
+
+        let store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(3), None);
+        let log_len = fs::metadata(dir.join("messages.log")).unwrap().len();
+        assert_eq!(log_len, (message(1).len() + message(2).len()) as u64);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Overwrite `len` bytes at `offset` with garbage, as a torn write would.
+    fn corrupt(
+        path: PathBuf,
+        offset: u64,
+        len: usize,
+    ) {
+        let file = OpenOptions::new().write(true).open(path).unwrap();
+        file.write_all_at(&vec![0xa5; len], offset).unwrap();
+    }
+
+    /// Offset of the `seqnums` slot written last.
+    fn newest_slot(dir: &Path) -> u64 {
+        let slots = fs::read(dir.join("seqnums")).unwrap();
+        let generation = |slot: &[u8]| read_seqnums_slot(slot).unwrap().0;
+        let (first, second) = slots.split_at(SEQNUMS_SLOT_LEN);
+        if generation(second) > generation(first) { SEQNUMS_SLOT_LEN as u64 } else { 0 }
+    }
+
+    #[test]
+    fn test_crash_with_torn_seqnums() {
+        let dir = temp_dir("torn-seqnums");
+        populate(&dir);
+        // Damage to the older slot is harmless
+        let newest = newest_slot(&dir);
+        corrupt(dir.join("seqnums"), SEQNUMS_SLOT_LEN as u64 - newest, 6);
+        let store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.sequences(), SequenceManager::with_numbers(7, 5));
+        drop(store);
+
+        // Tearing the newest write falls back to the update before it
+        let dir2 = temp_dir("torn-seqnums-newest");
+        populate(&dir2);
+        let newest = newest_slot(&dir2);
+        corrupt(dir2.join("seqnums"), newest + 4, 3);
+        let mut store = FileStore::open(&dir2, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.sequences(), SequenceManager::with_numbers(1, 5));
+        // The recovered numbers are written to a fresh slot and survive another restart
+        store.set_next_target_seq(7).unwrap();
+        drop(store);
+        let store = FileStore::open(&dir2, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.sequences(), SequenceManager::with_numbers(7, 5));
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&dir2).unwrap();
+    }
+
+    #[test]
+    fn test_crash_with_corrupt_message() {
+        let dir = temp_dir("corrupt-message");
+        populate(&dir);
+        // A bit flip inside message 2: it and everything after it are dropped
+        corrupt(dir.join("messages.log"), message(1).len() as u64 + 20, 1);
+
+        let store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get(1), Some(&message(1)[..]));
+        assert_eq!(store.get(2), None);
+        assert_eq!(store.sequences(), SequenceManager::with_numbers(7, 5));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_fsync_policy_and_reset() {
+        let dir = temp_dir("fsync");
+        let mut store = FileStore::open(&dir, FsyncPolicy::Every(2)).unwrap();
+        assert_eq!(store.fsync_policy(), FsyncPolicy::Every(2));
+        store.store(1, &message(1)).unwrap();
+        assert_eq!(store.unsynced, 1);
+        store.set_next_target_seq(2).unwrap();
+        assert_eq!(store.unsynced, 0);
+
+        store.reset().unwrap();
+        assert!(store.is_empty());
+        assert_eq!(store.get(1), None);
+        drop(store);
+        let store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        assert!(store.is_empty());
+        assert_eq!(store.sequences(), SequenceManager::new());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
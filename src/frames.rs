@@ -0,0 +1,376 @@
+//! Splitting a byte stream into FIX messages.
+//!
+//! [`FrameSplitter`] walks a buffer holding any number of messages - a socket
+//! read, a chunk of a log file - and yields each one as a [`Frame`]. Messages
+//! may be delimited by SOH or by `|` (as in most logs), and anything between
+//! them (timestamps, log levels, newlines) is skipped.
+//!
+//! A frame normally ends where BodyLength(9) says it does. When the declared
+//! length does not land on a `10=XXX` trailer, the splitter falls back to the
+//! first trailer before the next `8=FIX`, so one bad message does not swallow
+//! the rest of the log; [`Frame::validate`] then reports the mismatch.
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let log = b"12:00:01 IN 8=FIX.4.4|9=5|35=0|10=163|\n\
+//!             12:00:02 IN 8=FIX.4.4|9=5|35=0|10=000|\n";
+//! let mut frames = FrameSplitter::new(log);
+//! let first = frames.next().unwrap();
+//! assert_eq!(first.bytes, b"8=FIX.4.4|9=5|35=0|10=163|");
+//! assert_eq!(first.validate(), Ok(()));
+//! let second = frames.next().unwrap();
+//! assert_eq!(
+//!     second.validate(),
+//!     Err(FrameError::CheckSum { declared: 0, computed: 163 })
+//! );
+//! assert!(frames.next().is_none());
+//! ```
+
+use core::fmt;
+
+use crate::buffer::{CHECKSUM_FIELD_LEN, checksum};
+
+const SOH: u8 = 0x01;
+const BEGIN_STRING: &[u8] = b"8=FIX";
+
+/// A BodyLength(9) or CheckSum(10) that does not match the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// BodyLength(9) differs from the bytes between it and CheckSum(10)
+    BodyLength {
+        /// Value of BodyLength(9)
+        declared: usize,
+        /// Bytes actually counted
+        actual: usize,
+    },
+    /// CheckSum(10) differs from the sum of the preceding bytes
+    CheckSum {
+        /// Value of CheckSum(10)
+        declared: u8,
+        /// Sum computed over the message
+        computed: u8,
+    },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            FrameError::BodyLength { declared, actual } => {
+                write!(f, "BodyLength(9) is {declared} but body has {actual} bytes")
+            }
+            FrameError::CheckSum { declared, computed } => write!(
+                f,
+                "CheckSum(10) is {declared:03} but message sums to {computed:03}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for FrameError {}
+
+/// One message located by [`FrameSplitter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame<'a> {
+    /// Offset of `8=FIX` in the splitter's buffer
+    pub offset: usize,
+    /// The message, from `8=` through the delimiter after CheckSum(10)
+    pub bytes: &'a [u8],
+    /// Field delimiter: SOH or `|`
+    pub delimiter: u8,
+}
+
+impl Frame<'_> {
+    /// Check BodyLength(9) and CheckSum(10).
+    ///
+    /// For `|`-delimited frames the checksum is computed as if every `|` were
+    /// SOH, i.e. against the message as it went over the wire.
+    pub fn validate(&self) -> Result<(), FrameError> {
+        let bytes = self.bytes;
+        let trailer = bytes.len().saturating_sub(CHECKSUM_FIELD_LEN);
+        let (declared, body_start) = body_length(bytes, self.delimiter).unwrap_or((0, 0));
+        let actual = trailer.saturating_sub(body_start);
+        if declared != actual || !is_trailer(&bytes[trailer..], self.delimiter) {
+            return Err(FrameError::BodyLength { declared, actual });
+        }
+        let mut computed = checksum(&bytes[..trailer]);
+        if self.delimiter != SOH {
+            let count = bytes[..trailer]
+                .iter()
+                .filter(|&&b| b == self.delimiter)
+                .count();
+            computed = computed.wrapping_sub(((self.delimiter - SOH) as usize * count) as u8);
+        }
+        let digits = &bytes[trailer + 3..trailer + 6];
+        let declared = (digits[0] - b'0') as u32 * 100
+            + (digits[1] - b'0') as u32 * 10
+            + (digits[2] - b'0') as u32;
+        if declared != computed as u32 {
+            return Err(FrameError::CheckSum {
+                declared: declared as u8,
+                computed,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the messages in a buffer.
+///
+/// See the [module docs](self). Iteration stops at the first message that is
+/// not complete yet; [`position`](FrameSplitter::position) is then where a
+/// streaming caller should keep its bytes from before reading more.
+#[derive(Debug, Clone)]
+pub struct FrameSplitter<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    complete: bool,
+}
+
+impl<'a> FrameSplitter<'a> {
+    /// Split `buf`, starting at its first byte.
+    pub fn new(buf: &'a [u8]) -> Self {
+        FrameSplitter {
+            buf,
+            pos: 0,
+            complete: false,
+        }
+    }
+
+    /// Split `buf` as the whole of the input, e.g. the tail left at end of file.
+    ///
+    /// A message whose BodyLength(9) runs past the end of `buf` is then cut at
+    /// its `10=XXX` trailer instead of being waited on.
+    pub fn complete(buf: &'a [u8]) -> Self {
+        FrameSplitter {
+            buf,
+            pos: 0,
+            complete: true,
+        }
+    }
+
+    /// Offset of the first byte not consumed by a yielded frame or skipped as
+    /// noise.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The unconsumed tail of the buffer.
+    pub fn remainder(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+}
+
+impl<'a> Iterator for FrameSplitter<'a> {
+    type Item = Frame<'a>;
+
+    fn next(&mut self) -> Option<Frame<'a>> {
+        let buf = self.buf;
+        loop {
+            let Some(start) = find_begin(buf, self.pos) else {
+                // Keep a possibly split "8=FIX" for the next read
+                self.pos = self
+                    .pos
+                    .max(buf.len().saturating_sub(BEGIN_STRING.len() - 1));
+                return None;
+            };
+            self.pos = start;
+            let rest = &buf[start..];
+            let delimiter = rest
+                .iter()
+                .copied()
+                .find(|&b| b == SOH || b == b'|')?;
+            let Some((declared, body_start)) = body_length(rest, delimiter) else {
+                if rest.iter().filter(|&&b| b == delimiter).count() < 2 {
+                    // BodyLength may still be on its way
+                    return None;
+                }
+                self.pos = start + 1;
+                continue;
+            };
+            let next = find_begin(buf, start + 1);
+            let declared_end = body_start.saturating_add(declared);
+            let fits = declared_end.saturating_add(CHECKSUM_FIELD_LEN) <= rest.len();
+            let end = if fits && is_trailer(&rest[declared_end..], delimiter) {
+                Some(declared_end + CHECKSUM_FIELD_LEN)
+            } else if fits || next.is_some() || self.complete {
+                let limit = next.map_or(rest.len(), |n| n - start);
+                find_trailer(&rest[..limit], body_start, delimiter)
+            } else {
+                return None;
+            };
+            match (end, next) {
+                (Some(end), _) => {
+                    self.pos = start + end;
+                    return Some(Frame {
+                        offset: start,
+                        bytes: &rest[..end],
+                        delimiter,
+                    });
+                }
+                (None, Some(next)) => self.pos = next,
+                (None, None) => return None,
+            }
+        }
+    }
+}
+
+/// Offset of the next `8=FIX` at or after `from` that does not continue a
+/// longer tag number (e.g. `58=FIX...` in Text).
+fn find_begin(
+    buf: &[u8],
+    from: usize,
+) -> Option<usize> {
+    let mut i = from;
+    while i + BEGIN_STRING.len() <= buf.len() {
+        let at = i + buf[i..]
+            .windows(BEGIN_STRING.len())
+            .position(|w| w == BEGIN_STRING)?;
+        if at == 0 || !buf[at - 1].is_ascii_digit() {
+            return Some(at);
+        }
+        i = at + 1;
+    }
+    None
+}
+
+/// BodyLength(9) value and the offset just past its delimiter.
+///
+/// `msg` starts at `8=`; BodyLength must be the second field.
+fn body_length(
+    msg: &[u8],
+    delimiter: u8,
+) -> Option<(usize, usize)> {
+    let after_begin = msg.iter().position(|&b| b == delimiter)? + 1;
+    let rest = msg.get(after_begin..)?;
+    let digits = rest.strip_prefix(b"9=")?;
+    let len = digits.iter().position(|&b| b == delimiter)?;
+    if len == 0 || len > 9 || !digits[..len].iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let value = digits[..len]
+        .iter()
+        .fold(0usize, |acc, &b| acc * 10 + (b - b'0') as usize);
+    Some((value, after_begin + 2 + len + 1))
+}
+
+/// Whether `bytes` starts with `10=NNN` and `delimiter`.
+fn is_trailer(
+    bytes: &[u8],
+    delimiter: u8,
+) -> bool {
+    bytes.len() >= CHECKSUM_FIELD_LEN
+        && bytes.starts_with(b"10=")
+        && bytes[3..6].iter().all(u8::is_ascii_digit)
+        && bytes[6] == delimiter
+}
+
+/// End of the first `10=NNN` field that starts a field at or after `from`.
+fn find_trailer(
+    msg: &[u8],
+    from: usize,
+    delimiter: u8,
+) -> Option<usize> {
+    (from..msg.len())
+        .find(|&i| msg[i - 1] == delimiter && is_trailer(&msg[i..], delimiter))
+        .map(|i| i + CHECKSUM_FIELD_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{FORGE_WRITE_START, finalize_message, forge_out_buffer};
+    use crate::writing::write_tag_and_u32;
+
+    fn heartbeat(seq: u32) -> ([u8; crate::buffer::FORGE_BUFFER_SIZE], usize) {
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'0';
+        buffer[pos + 1] = SOH;
+        pos += 2;
+        pos += write_tag_and_u32(&mut buffer, pos, b"34=", seq);
+        let len = finalize_message(&mut buffer, pos);
+        (buffer, len)
+    }
+
+    #[test]
+    fn test_splits_back_to_back_messages() {
+        let mut stream = Vec::new();
+        for seq in 1..=3 {
+            let (buffer, len) = heartbeat(seq);
+            stream.extend_from_slice(&buffer[..len]);
+        }
+        let frames: Vec<Frame> = FrameSplitter::new(&stream).collect();
+        assert_eq!(frames.len(), 3);
+        for frame in &frames {
+            assert_eq!(frame.delimiter, SOH);
+            assert_eq!(frame.validate(), Ok(()));
+        }
+        assert_eq!(frames[1].offset, frames[0].bytes.len());
+    }
+
+    #[test]
+    fn test_pipe_delimited_log_with_prefixes() {
+        let (buffer, len) = heartbeat(9);
+        let piped: Vec<u8> = buffer[..len]
+            .iter()
+            .map(|&b| if b == SOH { b'|' } else { b })
+            .collect();
+        let mut log = b"2024-01-02 03:04:05.678 58=FIX noise ".to_vec();
+        log.extend_from_slice(&piped);
+        log.extend_from_slice(b"\n");
+
+        let mut frames = FrameSplitter::new(&log);
+        let frame = frames.next().unwrap();
+        assert_eq!(frame.bytes, &piped[..]);
+        assert_eq!(frame.delimiter, b'|');
+        assert_eq!(frame.validate(), Ok(()));
+        assert!(frames.next().is_none());
+    }
+
+    #[test]
+    fn test_bad_body_length_does_not_swallow_next_message() {
+        let log = b"8=FIX.4.4|9=50|35=0|10=163|\n8=FIX.4.4|9=5|35=0|10=163|\n";
+        let frames: Vec<Frame> = FrameSplitter::new(log).collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(
+            frames[0].validate(),
+            Err(FrameError::BodyLength {
+                declared: 50,
+                actual: 5
+            })
+        );
+        assert_eq!(frames[1].validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_incomplete_tail_is_left_for_next_read() {
+        let (buffer, len) = heartbeat(1);
+        let mut stream = buffer[..len].to_vec();
+        stream.extend_from_slice(&buffer[..len - 4]);
+
+        let mut frames = FrameSplitter::new(&stream);
+        assert!(frames.next().is_some());
+        assert!(frames.next().is_none());
+        assert_eq!(frames.position(), len);
+        assert_eq!(frames.remainder(), &buffer[..len - 4]);
+    }
+
+    #[test]
+    fn test_complete_input_falls_back_to_trailer() {
+        let log = b"8=FIX.4.4|9=50|35=0|10=163|\n";
+        assert!(FrameSplitter::new(log).next().is_none());
+        let frame = FrameSplitter::complete(log).next().unwrap();
+        assert_eq!(frame.bytes, b"8=FIX.4.4|9=50|35=0|10=163|");
+        assert!(matches!(frame.validate(), Err(FrameError::BodyLength { .. })));
+    }
+
+    #[test]
+    fn test_noise_only_keeps_possible_partial_begin_string() {
+        let mut frames = FrameSplitter::new(b"garbage 8=FI");
+        assert!(frames.next().is_none());
+        assert_eq!(frames.remainder(), b"8=FI");
+    }
+}
@@ -0,0 +1,441 @@
+//! Repeating group decoding on top of [`TagValueIter`].
+//!
+//! A repeating group is a NumInGroup field (e.g. NoPartyIDs(453)) followed by
+//! that many entries. Every entry starts with the delimiter tag (the first
+//! member, e.g. PartyID(448)) and runs until the next delimiter or the first
+//! tag that is not a member of the group. [`GroupIter`] splits the entries into
+//! sub-slices without allocating and checks the declared count; [`GroupWriter`]
+//! writes a group whose entry count is only known at the end.
+
+use crate::errors::ReadError;
+use crate::fields::TagValueIter;
+use core::iter::FusedIterator;
+use core::ptr;
+
+/// Digits reserved for the NumInGroup value by [`GroupWriter`] (counts 0-9999).
+pub const GROUP_COUNT_WIDTH: usize = 4;
+
+/// Shape of a repeating group.
+///
+/// `members` lists every tag that may appear in an entry, including the
+/// delimiter and the count and member tags of nested groups, so nested groups
+/// stay inside their parent entry.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::GroupSpec;
+/// /// NoPartyIDs(453) with the nested NoPartySubIDs(802) group
+/// const NO_PARTY_IDS: GroupSpec<'static> = GroupSpec {
+///     count_tag: 453,
+///     delimiter: 448,
+///     members: &[448, 447, 452, 802, 523, 803],
+/// };
+/// assert!(NO_PARTY_IDS.contains(523));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupSpec<'m> {
+    /// NumInGroup tag
+    pub count_tag: u32,
+    /// First tag of every entry
+    pub delimiter: u32,
+    /// All tags that belong to an entry
+    pub members: &'m [u32],
+}
+
+impl GroupSpec<'_> {
+    /// Whether `tag` belongs to an entry of this group.
+    #[inline(always)]
+    pub fn contains(
+        &self,
+        tag: u32,
+    ) -> bool {
+        tag == self.delimiter || self.members.contains(&tag)
+    }
+}
+
+/// Iterator over the entries of one repeating group, yielding each entry as a
+/// sub-slice (including its trailing SOH).
+///
+/// Create it right after reading the NumInGroup field from a [`TagValueIter`];
+/// the parent iterator advances past each entry as it is yielded, so once the
+/// group is exhausted the parent resumes at the field following the group.
+/// If the number of entries differs from the declared count, the last item is
+/// [`ReadError::GroupCountMismatch`]; framing errors are passed through. No
+/// items follow an error.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// const NO_PARTY_IDS: GroupSpec<'static> =
+///     GroupSpec { count_tag: 453, delimiter: 448, members: &[448, 447, 452] };
+///
+/// let msg = b"453=2\x01448=FIRM\x01452=1\x01448=TRADER\x01452=11\x0155=EUR/USD\x01";
+/// let mut it = TagValueIter::new(msg);
+/// let offset = it.position();
+/// let (_, count) = it.try_next()?.unwrap();
+///
+/// let entries: Vec<&[u8]> = GroupIter::new(&mut it, NO_PARTY_IDS, offset, read_u32(count))
+///     .collect::<Result<_, _>>()?;
+/// assert_eq!(entries, [&b"448=FIRM\x01452=1\x01"[..], &b"448=TRADER\x01452=11\x01"[..]]);
+///
+/// // The parent iterator continues after the group
+/// assert_eq!(it.try_next()?, Some((55, &b"EUR/USD"[..])));
+/// # Ok::<(), ReadError>(())
+/// ```
+#[derive(Debug)]
+pub struct GroupIter<'a, 'i, 'm> {
+    it: &'i mut TagValueIter<'a>,
+    spec: GroupSpec<'m>,
+    count_offset: usize,
+    expected: u32,
+    found: u32,
+    done: bool,
+}
+
+impl<'a, 'i, 'm> GroupIter<'a, 'i, 'm> {
+    /// Iterate the entries following a NumInGroup field.
+    ///
+    /// `it` must be positioned just after the NumInGroup field, which started at
+    /// byte `count_offset` and declared `count` entries.
+    #[inline]
+    pub fn new(
+        it: &'i mut TagValueIter<'a>,
+        spec: GroupSpec<'m>,
+        count_offset: usize,
+        count: u32,
+    ) -> Self {
+        GroupIter {
+            it,
+            spec,
+            count_offset,
+            expected: count,
+            found: 0,
+            done: false,
+        }
+    }
+
+    /// The group's shape.
+    #[inline(always)]
+    pub fn spec(&self) -> &GroupSpec<'m> {
+        &self.spec
+    }
+
+    /// Entry count declared by the NumInGroup field.
+    #[inline(always)]
+    pub fn expected(&self) -> u32 {
+        self.expected
+    }
+
+    /// Entries found so far.
+    #[inline(always)]
+    pub fn found(&self) -> u32 {
+        self.found
+    }
+
+    /// Consume the entry starting at the parent's position, if one starts there.
+    fn next_entry(&mut self) -> Result<Option<&'a [u8]>, ReadError> {
+        // `&mut TagValueIter` is itself an `Iterator`, so name the inherent method
+        let rest = self.it.remainder();
+        let start = TagValueIter::position(self.it);
+        let mut peek = self.it.clone();
+        match peek.try_next()? {
+            Some((tag, _)) if tag == self.spec.delimiter => *self.it = peek,
+            _ => return Ok(None),
+        }
+        loop {
+            let mut peek = self.it.clone();
+            match peek.try_next()? {
+                Some((tag, _)) if tag != self.spec.delimiter && self.spec.contains(tag) => {
+                    *self.it = peek
+                }
+                _ => break,
+            }
+        }
+        Ok(Some(&rest[..TagValueIter::position(self.it) - start]))
+    }
+
+    fn mismatch(&self) -> ReadError {
+        ReadError::GroupCountMismatch {
+            offset: self.count_offset,
+            tag: self.spec.count_tag,
+            expected: self.expected,
+            actual: self.found,
+        }
+    }
+}
+
+impl<'a> Iterator for GroupIter<'a, '_, '_> {
+    type Item = Result<&'a [u8], ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_entry() {
+            Ok(Some(entry)) if self.found < self.expected => {
+                self.found += 1;
+                Some(Ok(entry))
+            }
+            Ok(Some(_)) => {
+                // More entries than declared: count the rest for the error
+                self.done = true;
+                self.found += 1;
+                loop {
+                    match self.next_entry() {
+                        Ok(Some(_)) => self.found += 1,
+                        Ok(None) => return Some(Err(self.mismatch())),
+                        Err(error) => return Some(Err(error)),
+                    }
+                }
+            }
+            Ok(None) => {
+                self.done = true;
+                (self.found != self.expected).then(|| Err(self.mismatch()))
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl FusedIterator for GroupIter<'_, '_, '_> {}
+
+/// Writer for a repeating group whose entry count is not known up front.
+///
+/// [`begin`](GroupWriter::begin) writes the NumInGroup field with a zero-padded
+/// `0000` placeholder; call [`entry`](GroupWriter::entry) before writing each
+/// entry with any `write_tag_and_*` function, then
+/// [`finish`](GroupWriter::finish) patches the count in place, the same way
+/// [`update_body_length`](crate::update_body_length) patches BodyLength.
+/// The writer only records positions, so nested groups simply use a second
+/// writer inside an entry.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let mut buf = [0u8; 128];
+/// let (mut levels, mut pos) = GroupWriter::begin(&mut buf, 0, b"268=");
+/// for (side, px) in [(b'0', 1.25), (b'1', 1.5)] {
+///     levels.entry();
+///     pos += write_tag_and_char(&mut buf, pos, b"269=", side);
+///     pos += write_tag_and_f64(&mut buf, pos, b"270=", px);
+/// }
+/// levels.finish(&mut buf);
+/// assert_eq!(&buf[..pos], b"268=0002\x01269=0\x01270=1.25\x01269=1\x01270=1.5\x01");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupWriter {
+    /// Offset of the first placeholder digit
+    value_pos: usize,
+    count: u32,
+}
+
+impl GroupWriter {
+    /// Write `tag_and_eq`, the `0000` placeholder and SOH at `offset`.
+    ///
+    /// Returns the writer and the number of bytes written.
+    #[inline(always)]
+    pub fn begin(
+        bytes: &mut [u8],
+        offset: usize,
+        tag_and_eq: &[u8],
+    ) -> (Self, usize) {
+        let value_pos = offset + tag_and_eq.len();
+        unsafe {
+            let dst = bytes.as_mut_ptr();
+            ptr::copy_nonoverlapping(tag_and_eq.as_ptr(), dst.add(offset), tag_and_eq.len());
+            ptr::copy_nonoverlapping(b"0000\x01".as_ptr(), dst.add(value_pos), GROUP_COUNT_WIDTH + 1);
+        }
+        let writer = GroupWriter {
+            value_pos,
+            count: 0,
+        };
+        (writer, tag_and_eq.len() + GROUP_COUNT_WIDTH + 1)
+    }
+
+    /// Count one more entry; call it once per entry.
+    #[inline(always)]
+    pub fn entry(&mut self) {
+        self.count += 1;
+    }
+
+    /// Entries counted so far.
+    #[inline(always)]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Patch the placeholder with the entry count.
+    ///
+    /// # Panics
+    /// If the count does not fit in [`GROUP_COUNT_WIDTH`] digits (10,000 entries
+    /// or more); a wrapped count would silently misframe the group.
+    #[inline(always)]
+    pub fn finish(
+        self,
+        bytes: &mut [u8],
+    ) {
+        assert!(self.count < 10_000, "NumInGroup {} exceeds 4 digits", self.count);
+        let count = self.count;
+        unsafe {
+            let ptr = bytes.as_mut_ptr().add(self.value_pos);
+            *ptr = b'0' + (count / 1000) as u8;
+            *ptr.add(1) = b'0' + (count / 100 % 10) as u8;
+            *ptr.add(2) = b'0' + (count / 10 % 10) as u8;
+            *ptr.add(3) = b'0' + (count % 10) as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reading::read_u32;
+
+    const PARTIES: GroupSpec<'static> = GroupSpec {
+        count_tag: 453,
+        delimiter: 448,
+        members: &[447, 452, 802, 523, 803],
+    };
+    const SUB_IDS: GroupSpec<'static> = GroupSpec {
+        count_tag: 802,
+        delimiter: 523,
+        members: &[803],
+    };
+
+    /// Collect the group that starts at the beginning of `msg`, plus the tag after it.
+    fn entries(msg: &[u8]) -> (Vec<Result<&[u8], ReadError>>, Option<u32>) {
+        let mut it = TagValueIter::new(msg);
+        let offset = it.position();
+        let (_, count) = it.try_next().unwrap().unwrap();
+        let entries = GroupIter::new(&mut it, PARTIES, offset, read_u32(count)).collect();
+        let next = it.try_next().ok().flatten().map(|(tag, _)| tag);
+        (entries, next)
+    }
+
+    #[test]
+    fn test_entries_and_resume() {
+        let (items, next) = entries(b"453=2\x01448=A\x01452=1\x01448=B\x0155=X\x01");
+        assert_eq!(items, [Ok(&b"448=A\x01452=1\x01"[..]), Ok(&b"448=B\x01"[..])]);
+        assert_eq!(next, Some(55));
+
+        let (items, next) = entries(b"453=0\x0155=X\x01");
+        assert_eq!(items, []);
+        assert_eq!(next, Some(55));
+    }
+
+    #[test]
+    fn test_nested_group() {
+        let msg = b"453=1\x01448=A\x01802=2\x01523=S1\x01803=4\x01523=S2\x01452=3\x0110=000\x01";
+        let (items, next) = entries(msg);
+        assert_eq!(next, Some(10));
+        let entry = items[0].clone().unwrap();
+        assert_eq!(entry, &msg[6..msg.len() - 7]);
+
+        let mut it = TagValueIter::new(entry);
+        let mut sub_ids = Vec::new();
+        while let Some((tag, value)) = it.try_next().unwrap() {
+            if tag == 802 {
+                let offset = it.position() - 6;
+                for sub in GroupIter::new(&mut it, SUB_IDS, offset, read_u32(value)) {
+                    sub_ids.push(sub.unwrap());
+                }
+            }
+        }
+        assert_eq!(sub_ids, [&b"523=S1\x01803=4\x01"[..], &b"523=S2\x01"[..]]);
+    }
+
+    #[test]
+    fn test_count_mismatch() {
+        let (items, _) = entries(b"453=2\x01448=A\x0155=X\x01");
+        assert_eq!(
+            items,
+            [
+                Ok(&b"448=A\x01"[..]),
+                Err(ReadError::GroupCountMismatch {
+                    offset: 0,
+                    tag: 453,
+                    expected: 2,
+                    actual: 1,
+                }),
+            ]
+        );
+
+        let (items, next) = entries(b"453=1\x01448=A\x01448=B\x01448=C\x0155=X\x01");
+        assert_eq!(
+            items,
+            [
+                Ok(&b"448=A\x01"[..]),
+                Err(ReadError::GroupCountMismatch {
+                    offset: 0,
+                    tag: 453,
+                    expected: 1,
+                    actual: 3,
+                }),
+            ]
+        );
+        assert_eq!(next, Some(55));
+    }
+
+    #[test]
+    fn test_group_writer_nested() {
+        use crate::writing::{write_tag_and_str, write_tag_and_u32};
+
+        let mut buf = [0u8; 256];
+        let (mut parties, mut pos) = GroupWriter::begin(&mut buf, 0, b"453=");
+        for (id, subs) in [("A", &["S1", "S2"][..]), ("B", &[][..])] {
+            parties.entry();
+            pos += write_tag_and_str(&mut buf, pos, b"448=", id);
+            let (mut sub_ids, written) = GroupWriter::begin(&mut buf, pos, b"802=");
+            pos += written;
+            for sub in subs {
+                sub_ids.entry();
+                pos += write_tag_and_str(&mut buf, pos, b"523=", sub);
+            }
+            sub_ids.finish(&mut buf);
+        }
+        parties.finish(&mut buf);
+        pos += write_tag_and_u32(&mut buf, pos, b"55=", 1);
+        assert_eq!(
+            &buf[..pos],
+            b"453=0002\x01448=A\x01802=0002\x01523=S1\x01523=S2\x01448=B\x01802=0000\x0155=1\x01"
+        );
+
+        // Zero-padded counts read back through GroupIter
+        let (items, next) = entries(&buf[..pos]);
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(Result::is_ok));
+        assert_eq!(next, Some(55));
+    }
+
+    #[test]
+    fn test_group_writer_count_digits() {
+        let mut buf = [0u8; 16];
+        let (mut group, len) = GroupWriter::begin(&mut buf, 2, b"268=");
+        for _ in 0..1234 {
+            group.entry();
+        }
+        assert_eq!(group.count(), 1234);
+        group.finish(&mut buf);
+        assert_eq!(&buf[2..2 + len], b"268=1234\x01");
+    }
+
+    #[test]
+    #[should_panic(expected = "NumInGroup 10000 exceeds 4 digits")]
+    fn test_group_writer_count_overflow() {
+        let mut buf = [0u8; 16];
+        let (mut group, _) = GroupWriter::begin(&mut buf, 0, b"268=");
+        for _ in 0..10_000 {
+            group.entry();
+        }
+        group.finish(&mut buf);
+    }
+
+    #[test]
+    fn test_framing_error() {
+        let (items, _) = entries(b"453=1\x01448=A\x01452");
+        assert_eq!(items, [Err(ReadError::UnexpectedEnd { offset: 15, tag: 452 })]);
+    }
+}
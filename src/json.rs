@@ -0,0 +1,720 @@
+//! FIX ⇄ JSON conversion for integrations and test fixtures (`json` feature).
+//!
+//! [`fix_to_json`] turns a raw message into a JSON object whose keys follow
+//! the wire order; [`json_to_fix`] writes such an object back into a forge
+//! buffer and finalizes it. Values stay JSON strings, so nothing is lost to
+//! number formatting (`"44": "1.0825"`, `"38": "0100"`); when converting back,
+//! numbers and booleans are accepted as well (`true` becomes `Y`).
+//!
+//! Keys are tag numbers (`"35"`) or, with [`JsonOptions::tag_names`], field
+//! names from a loaded [`Dictionary`] or the built-in FIX 4.4 table
+//! ([`fix44_tag_name`]). Both forms are accepted when converting back.
+//!
+//! Repeating groups become arrays of entry objects under the NumInGroup key;
+//! the count is implied by the array length. A count written differently from
+//! that length (`453=0002`) is kept as `{"count": "0002", "entries": [...]}`,
+//! and a value that is not valid UTF-8 (binary RawData(96), SecureData(91))
+//! as `{"base64": "..."}`, so the original bytes come back. Group shapes come from
+//! [`JsonOptions::groups`] and, for the message's MsgType, from
+//! [`JsonOptions::dictionary`]. A tag that repeats outside a known group cannot
+//! be represented as an object key and is reported as
+//! [`JsonError::DuplicateTag`].
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! const NO_PARTY_IDS: GroupSpec<'static> =
+//!     GroupSpec { count_tag: 453, delimiter: 448, members: &[448, 452] };
+//! let options = JsonOptions { groups: &[NO_PARTY_IDS], ..JsonOptions::default() };
+//!
+//! let msg = b"8=FIX.4.4\x019=0049\x0135=D\x0111=ORD-1\x01453=2\x01448=FIRM\x01452=1\x01448=ME\x01452=11\x0110=255\x01";
+//! let json = fix_to_json(msg, &options).unwrap();
+//! assert_eq!(
+//!     json.to_string(),
+//!     r#"{"8":"FIX.4.4","9":"0049","35":"D","11":"ORD-1","453":[{"448":"FIRM","452":"1"},{"448":"ME","452":"11"}],"10":"255"}"#
+//! );
+//!
+//! let mut buffer = [0u8; FORGE_BUFFER_SIZE];
+//! let len = json_to_fix(&json, &mut buffer, &options).unwrap();
+//! assert_eq!(&buffer[..len], msg);
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+pub use serde_json::Value as JsonValue;
+use serde_json::Map;
+
+use crate::buffer::{CHECKSUM_FIELD_LEN, FORGE_WRITE_START, finalize_message, forge_out_buffer};
+use crate::dictionary::{Dictionary, Member};
+use crate::display::{fix44_tag_name, fix44_tag_number};
+use crate::errors::{MemberKind, ReadError};
+use crate::fields::TagValueIter;
+use crate::groups::GroupSpec;
+use crate::reading::read_u32;
+use crate::validator::first_tag;
+use crate::writing::write_tag_num_and_bytes;
+
+/// How [`fix_to_json`] and [`json_to_fix`] name fields and find repeating groups.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonOptions<'a> {
+    /// Use field names instead of tag numbers as keys where a name is known
+    pub tag_names: bool,
+    /// Repeating group shapes, including nested groups; checked before the dictionary
+    pub groups: &'a [GroupSpec<'a>],
+    /// Source of field names and of the group shapes of each message type
+    pub dictionary: Option<&'a Dictionary>,
+}
+
+/// Errors from [`fix_to_json`] and [`json_to_fix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// The FIX input is malformed, or a group's entries do not match its count
+    Fix(ReadError),
+    /// A tag repeats outside a known repeating group
+    DuplicateTag {
+        /// Repeated tag
+        tag: u32,
+    },
+    /// The JSON input is not an object
+    NotAnObject,
+    /// A JSON key is neither a tag number nor a known field name
+    UnknownKey {
+        /// Offending key
+        key: String,
+    },
+    /// A JSON value is null, an array under a non-group key, an object other
+    /// than a `count`/`entries` group or a `base64` value, or invalid base64
+    InvalidValue {
+        /// Key of the value
+        key: String,
+    },
+    /// BeginString(8) or MsgType(35) is missing from the JSON input
+    MissingField {
+        /// Missing tag
+        tag: u32,
+    },
+    /// BeginString(8) is not a `FIX.4.x` version the forge buffer supports
+    UnsupportedBeginString,
+    /// The message does not fit the output buffer
+    BufferTooSmall,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            JsonError::Fix(e) => write!(f, "malformed FIX message: {e}"),
+            JsonError::DuplicateTag { tag } => write!(f, "tag {tag} repeats outside a known repeating group"),
+            JsonError::NotAnObject => f.write_str("JSON message must be an object"),
+            JsonError::UnknownKey { key } => write!(f, "JSON key {key:?} is not a tag number or known field name"),
+            JsonError::InvalidValue { key } => write!(f, "JSON value for {key:?} is not a string, number, boolean or group array"),
+            JsonError::MissingField { tag } => write!(f, "required tag {tag} missing from JSON message"),
+            JsonError::UnsupportedBeginString => f.write_str("BeginString must be FIX.4.x"),
+            JsonError::BufferTooSmall => f.write_str("message does not fit the output buffer"),
+        }
+    }
+}
+
+impl core::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            JsonError::Fix(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ReadError> for JsonError {
+    fn from(e: ReadError) -> Self {
+        JsonError::Fix(e)
+    }
+}
+
+/// Convert a complete raw message into a JSON object, preserving field order.
+///
+/// # Errors
+/// [`JsonError::Fix`] for malformed input or a group count mismatch and
+/// [`JsonError::DuplicateTag`] for a tag repeated outside a known group.
+pub fn fix_to_json(
+    msg: &[u8],
+    options: &JsonOptions<'_>,
+) -> Result<JsonValue, JsonError> {
+    let msg_type = TagValueIter::new(msg)
+        .find(|&(tag, _)| tag == 35)
+        .and_then(|(_, value)| core::str::from_utf8(value).ok());
+    let converter = Converter::new(options, msg_type);
+    let mut it = TagValueIter::new(msg);
+    let object = converter.object(&mut it, None)?;
+    // Stopped early only on a framing error; surface it
+    it.try_next()?;
+    Ok(JsonValue::Object(object))
+}
+
+/// Write a JSON object produced by [`fix_to_json`] (or written by hand) into
+/// `buffer` as a finalized message; returns its length.
+///
+/// BeginString(8) and MsgType(35) must be present and are written first;
+/// BodyLength(9) (four digits) and CheckSum(10) are recomputed, and every
+/// other field is written in object order.
+///
+/// # Errors
+/// [`JsonError::NotAnObject`], [`JsonError::UnknownKey`],
+/// [`JsonError::InvalidValue`], [`JsonError::MissingField`],
+/// [`JsonError::UnsupportedBeginString`] or [`JsonError::BufferTooSmall`].
+pub fn json_to_fix(
+    json: &JsonValue,
+    buffer: &mut [u8],
+    options: &JsonOptions<'_>,
+) -> Result<usize, JsonError> {
+    let JsonValue::Object(object) = json else {
+        return Err(JsonError::NotAnObject);
+    };
+    let converter = Converter::new(options, None);
+    let mut begin_string = None;
+    let mut msg_type = None;
+    for (key, value) in object {
+        match converter.tag(key)? {
+            8 => begin_string = Some(scalar(key, value)?),
+            35 => msg_type = Some(scalar(key, value)?),
+            _ => {}
+        }
+    }
+    let begin_string = begin_string.ok_or(JsonError::MissingField { tag: 8 })?;
+    let msg_type = msg_type.ok_or(JsonError::MissingField { tag: 35 })?;
+    // update_body_length assumes "8=FIX.4.x"
+    if begin_string.len() != 7 || !begin_string.starts_with("FIX.4.") {
+        return Err(JsonError::UnsupportedBeginString);
+    }
+    // BodyLength is four digits
+    let limit = buffer.len().min(17 + 9999 + CHECKSUM_FIELD_LEN);
+    if limit < FORGE_WRITE_START + msg_type.len() + 1 + CHECKSUM_FIELD_LEN {
+        return Err(JsonError::BufferTooSmall);
+    }
+    buffer[..FORGE_WRITE_START].copy_from_slice(&forge_out_buffer(&begin_string)[..FORGE_WRITE_START]);
+    let mut pos = FORGE_WRITE_START;
+    buffer[pos..pos + msg_type.len()].copy_from_slice(msg_type.as_bytes());
+    pos += msg_type.len();
+    buffer[pos] = 0x01;
+    pos += 1;
+
+    let converter = Converter::new(options, Some(&msg_type));
+    let mut writer = Writer {
+        buffer: &mut buffer[..limit],
+        pos,
+    };
+    for (key, value) in object {
+        match converter.tag(key)? {
+            8 | 9 | 10 | 35 => {}
+            tag => converter.write_value(&mut writer, key, tag, value)?,
+        }
+    }
+    let pos = writer.pos;
+    Ok(finalize_message(buffer, pos))
+}
+
+/// Owned shape of one repeating group.
+#[derive(Debug, Clone)]
+struct Shape {
+    count_tag: u32,
+    delimiter: u32,
+    members: Vec<u32>,
+}
+
+impl Shape {
+    fn contains(
+        &self,
+        tag: u32,
+    ) -> bool {
+        tag == self.delimiter || self.members.contains(&tag)
+    }
+}
+
+struct Converter<'a> {
+    options: &'a JsonOptions<'a>,
+    shapes: Vec<Shape>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(
+        options: &'a JsonOptions<'a>,
+        msg_type: Option<&str>,
+    ) -> Self {
+        let mut shapes: Vec<Shape> = options
+            .groups
+            .iter()
+            .map(|spec| Shape {
+                count_tag: spec.count_tag,
+                delimiter: spec.delimiter,
+                members: spec.members.to_vec(),
+            })
+            .collect();
+        if let Some(dict) = options.dictionary {
+            add_shapes(dict, dict.header(), &mut shapes);
+            if let Some(message) = msg_type.and_then(|msg_type| dict.message_by_type(msg_type)) {
+                add_shapes(dict, &message.members, &mut shapes);
+            }
+            add_shapes(dict, dict.trailer(), &mut shapes);
+        }
+        Converter { options, shapes }
+    }
+
+    fn shape(
+        &self,
+        count_tag: u32,
+    ) -> Option<&Shape> {
+        self.shapes
+            .iter()
+            .find(|shape| shape.count_tag == count_tag)
+    }
+
+    fn key(
+        &self,
+        tag: u32,
+    ) -> String {
+        let name = match self.options.dictionary {
+            Some(dict) => dict
+                .field_by_tag(tag)
+                .map(|field| field.name.as_str()),
+            None => fix44_tag_name(tag),
+        };
+        match name.filter(|_| self.options.tag_names) {
+            Some(name) => name.to_owned(),
+            None => tag.to_string(),
+        }
+    }
+
+    fn tag(
+        &self,
+        key: &str,
+    ) -> Result<u32, JsonError> {
+        if !key.is_empty() && key.bytes().all(|b| b.is_ascii_digit()) {
+            return key
+                .parse()
+                .map_err(|_| JsonError::UnknownKey { key: key.to_owned() });
+        }
+        let tag = match self.options.dictionary {
+            Some(dict) => dict
+                .field_by_name(key)
+                .map(|field| field.number),
+            None => fix44_tag_number(key),
+        };
+        tag.ok_or_else(|| JsonError::UnknownKey { key: key.to_owned() })
+    }
+
+    /// Read fields into an object until the input ends or, inside a group
+    /// entry, a tag outside `entry` or the next entry's delimiter.
+    fn object(
+        &self,
+        it: &mut TagValueIter<'_>,
+        entry: Option<&Shape>,
+    ) -> Result<Map<String, JsonValue>, JsonError> {
+        let mut object = Map::new();
+        loop {
+            let mut peek = it.clone();
+            let offset = peek.position();
+            let Ok(Some((tag, value))) = peek.try_next() else {
+                break;
+            };
+            if let Some(entry) = entry
+                && (!entry.contains(tag) || (tag == entry.delimiter && !object.is_empty()))
+            {
+                break;
+            }
+            *it = peek;
+            let key = self.key(tag);
+            if object.contains_key(&key) {
+                return Err(JsonError::DuplicateTag { tag });
+            }
+            let value = match self.shape(tag) {
+                Some(shape) => {
+                    let expected = read_u32(value);
+                    let mut entries = Vec::new();
+                    while let Ok(Some((next, _))) = it.clone().try_next()
+                        && next == shape.delimiter
+                    {
+                        entries.push(JsonValue::Object(self.object(it, Some(shape))?));
+                    }
+                    if entries.len() != expected as usize {
+                        return Err(JsonError::Fix(ReadError::GroupCountMismatch {
+                            offset,
+                            tag,
+                            expected,
+                            actual: entries.len() as u32,
+                        }));
+                    }
+                    if value == entries.len().to_string().as_bytes() {
+                        JsonValue::Array(entries)
+                    } else {
+                        let mut group = Map::new();
+                        group.insert("count".to_owned(), bytes_value(value));
+                        group.insert("entries".to_owned(), JsonValue::Array(entries));
+                        JsonValue::Object(group)
+                    }
+                }
+                None => bytes_value(value),
+            };
+            object.insert(key, value);
+        }
+        Ok(object)
+    }
+
+    fn write_value(
+        &self,
+        writer: &mut Writer<'_>,
+        key: &str,
+        tag: u32,
+        value: &JsonValue,
+    ) -> Result<(), JsonError> {
+        let invalid = || JsonError::InvalidValue { key: key.to_owned() };
+        let (count, entries) = match value {
+            // The array is the group: its length is the count, its objects the entries
+            JsonValue::Array(entries) => (entries.len().to_string(), entries),
+            JsonValue::Object(object) => match (
+                object.len(),
+                object.get("base64"),
+                object.get("count"),
+                object.get("entries"),
+            ) {
+                (1, Some(JsonValue::String(text)), _, _) => {
+                    return writer.field(tag, &base64_decode(text).ok_or_else(invalid)?);
+                }
+                // A count that is not the plain entry count, e.g. "0002"
+                (2, _, Some(count), Some(JsonValue::Array(entries))) => {
+                    let count = scalar(key, count)?;
+                    if !count.bytes().all(|b| b.is_ascii_digit())
+                        || count.parse::<usize>().ok() != Some(entries.len())
+                    {
+                        return Err(invalid());
+                    }
+                    (count, entries)
+                }
+                _ => return Err(invalid()),
+            },
+            _ => return writer.field(tag, scalar(key, value)?.as_bytes()),
+        };
+        writer.field(tag, count.as_bytes())?;
+        for entry in entries {
+            let JsonValue::Object(entry) = entry else {
+                return Err(JsonError::InvalidValue { key: key.to_owned() });
+            };
+            for (key, value) in entry {
+                let tag = self.tag(key)?;
+                self.write_value(writer, key, tag, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Bounds-checked field writer over the output buffer.
+struct Writer<'b> {
+    buffer: &'b mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn field(
+        &mut self,
+        tag: u32,
+        value: &[u8],
+    ) -> Result<(), JsonError> {
+        // Up to 10 tag digits, '=', value, SOH, then room for CheckSum(10)
+        if self.pos + 12 + value.len() + CHECKSUM_FIELD_LEN > self.buffer.len() {
+            return Err(JsonError::BufferTooSmall);
+        }
+        self.pos += write_tag_num_and_bytes(self.buffer, self.pos, tag, value);
+        Ok(())
+    }
+}
+
+/// FIX wire text of a scalar JSON value.
+fn scalar(
+    key: &str,
+    value: &JsonValue,
+) -> Result<String, JsonError> {
+    match value {
+        JsonValue::String(s) => Ok(s.clone()),
+        JsonValue::Number(n) => Ok(n.to_string()),
+        JsonValue::Bool(b) => Ok(if *b { "Y" } else { "N" }.to_owned()),
+        _ => Err(JsonError::InvalidValue { key: key.to_owned() }),
+    }
+}
+
+/// JSON form of a FIX value: a string, or `{"base64": ...}` when not UTF-8.
+fn bytes_value(value: &[u8]) -> JsonValue {
+    match core::str::from_utf8(value) {
+        Ok(text) => JsonValue::String(text.to_owned()),
+        Err(_) => {
+            let mut object = Map::new();
+            object.insert("base64".to_owned(), JsonValue::String(base64_encode(value)));
+            JsonValue::Object(object)
+        }
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard, padded base64.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Inverse of [`base64_encode`]; `None` unless `text` is padded standard base64.
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    if !text.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    for (index, chunk) in text.chunks(4).enumerate() {
+        let last = index == text.len() / 4 - 1;
+        let padding = chunk
+            .iter()
+            .rev()
+            .take_while(|&&b| b == b'=')
+            .count();
+        if padding > 2 || (padding > 0 && !last) {
+            return None;
+        }
+        let mut n = 0u32;
+        for &b in &chunk[..4 - padding] {
+            let digit = BASE64.iter().position(|&c| c == b)? as u32;
+            n = n << 6 | digit;
+        }
+        n <<= 6 * padding as u32;
+        out.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Some(out)
+}
+
+/// Collect the group shapes declared in `members`, nested groups included.
+fn add_shapes(
+    dict: &Dictionary,
+    members: &[Member],
+    shapes: &mut Vec<Shape>,
+) {
+    for member in members {
+        match member.kind {
+            MemberKind::Field => {}
+            MemberKind::Component => {
+                if let Some(component) = dict.component(&member.name) {
+                    add_shapes(dict, &component.members, shapes);
+                }
+            }
+            MemberKind::Group => {
+                let count_tag = dict
+                    .field_by_name(&member.name)
+                    .map(|field| field.number);
+                let delimiter = first_tag(dict, &member.members);
+                if let (Some(count_tag), Some(delimiter)) = (count_tag, delimiter)
+                    && !shapes
+                        .iter()
+                        .any(|shape| shape.count_tag == count_tag)
+                {
+                    let mut tags = Vec::new();
+                    member_tags(dict, &member.members, &mut tags);
+                    shapes.push(Shape {
+                        count_tag,
+                        delimiter,
+                        members: tags,
+                    });
+                }
+                add_shapes(dict, &member.members, shapes);
+            }
+        }
+    }
+}
+
+/// Every tag that may appear in an entry made of `members`.
+fn member_tags(
+    dict: &Dictionary,
+    members: &[Member],
+    tags: &mut Vec<u32>,
+) {
+    for member in members {
+        if member.kind == MemberKind::Component {
+            if let Some(component) = dict.component(&member.name) {
+                member_tags(dict, &component.members, tags);
+            }
+            continue;
+        }
+        if let Some(field) = dict.field_by_name(&member.name) {
+            tags.push(field.number);
+        }
+        member_tags(dict, &member.members, tags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::FORGE_BUFFER_SIZE;
+    use crate::test_util::forge;
+    use crate::writing::{write_tag_and_data, write_tag_and_str};
+
+    #[test]
+    fn test_round_trip_with_names_and_data() {
+        let (buffer, len) = forge(|buf, offset| {
+            let mut pos = offset;
+            pos += write_tag_and_str(buf, pos, b"11=", "ORD-1");
+            pos += write_tag_and_data(buf, pos, b"95=", b"96=", b"a\x01b");
+            pos += write_tag_and_str(buf, pos, b"5001=", "custom");
+            pos - offset
+        });
+        let options = JsonOptions {
+            tag_names: true,
+            ..JsonOptions::default()
+        };
+        let json = fix_to_json(&buffer[..len], &options).unwrap();
+        let keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "BeginString",
+                "BodyLength",
+                "MsgType",
+                "SenderCompID",
+                "TargetCompID",
+                "MsgSeqNum",
+                "ClOrdID",
+                "RawDataLength",
+                "RawData",
+                "5001",
+                "CheckSum",
+            ]
+        );
+        assert_eq!(json["RawData"], "a\u{1}b");
+
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = json_to_fix(&json, &mut out, &options).unwrap();
+        assert_eq!(out[..n], buffer[..len]);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_count_text_and_binary_values() {
+        let spec = [GroupSpec {
+            count_tag: 453,
+            delimiter: 448,
+            members: &[448],
+        }];
+        let options = JsonOptions {
+            groups: &spec,
+            ..JsonOptions::default()
+        };
+        let (buffer, len) = forge(|buf, offset| {
+            let mut pos = offset;
+            pos += write_tag_and_str(buf, pos, b"453=", "0002");
+            pos += write_tag_and_str(buf, pos, b"448=", "A");
+            pos += write_tag_and_str(buf, pos, b"448=", "B");
+            pos += write_tag_and_data(buf, pos, b"95=", b"96=", b"\xff\x00\x01ab");
+            pos += write_tag_and_str(buf, pos, b"58=", "\u{e9}");
+            pos - offset
+        });
+        let json = fix_to_json(&buffer[..len], &options).unwrap();
+        assert_eq!(
+            json["453"].to_string(),
+            r#"{"count":"0002","entries":[{"448":"A"},{"448":"B"}]}"#
+        );
+        assert_eq!(json["96"].to_string(), r#"{"base64":"/wABYWI="}"#);
+        assert_eq!(json["58"], "\u{e9}");
+
+        let text = json.to_string();
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = json_to_fix(&text.parse().unwrap(), &mut out, &options).unwrap();
+        assert_eq!(out[..n], buffer[..len]);
+    }
+
+    #[test]
+    fn test_base64() {
+        for bytes in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar", b"\xff\xfe\x00"] {
+            let text = base64_encode(bytes);
+            assert_eq!(base64_decode(&text).as_deref(), Some(bytes), "{text}");
+        }
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        for bad in ["Zm8", "Zm==Zm8=", "Z===", "Zm8*"] {
+            assert_eq!(base64_decode(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_hand_written_json() {
+        let json: JsonValue = r#"{"MsgType": "D", "BeginString": "FIX.4.4", "38": 100, "43": true, "44": 1.5}"#
+            .parse()
+            .unwrap();
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = json_to_fix(&json, &mut out, &JsonOptions::default()).unwrap();
+        assert_eq!(&out[..n - CHECKSUM_FIELD_LEN], b"8=FIX.4.4\x019=0024\x0135=D\x0138=100\x0143=Y\x0144=1.5\x01");
+    }
+
+    #[test]
+    fn test_errors() {
+        let options = JsonOptions::default();
+        let (buffer, len) = forge(|buf, offset| {
+            let mut pos = offset;
+            pos += write_tag_and_str(buf, pos, b"448=", "A");
+            pos += write_tag_and_str(buf, pos, b"448=", "B");
+            pos - offset
+        });
+        assert_eq!(fix_to_json(&buffer[..len], &options), Err(JsonError::DuplicateTag { tag: 448 }));
+
+        let spec = [GroupSpec {
+            count_tag: 453,
+            delimiter: 448,
+            members: &[448],
+        }];
+        let grouped = JsonOptions {
+            groups: &spec,
+            ..JsonOptions::default()
+        };
+        assert!(matches!(
+            fix_to_json(b"35=D\x01453=3\x01448=A\x01448=B\x01", &grouped),
+            Err(JsonError::Fix(ReadError::GroupCountMismatch { tag: 453, expected: 3, actual: 2, .. }))
+        ));
+        assert!(matches!(fix_to_json(b"35=D\x0158", &options), Err(JsonError::Fix(_))));
+
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let cases = [
+            (r#"[]"#, JsonError::NotAnObject),
+            (r#"{"8": "FIX.4.4"}"#, JsonError::MissingField { tag: 35 }),
+            (r#"{"8": "FIXT.1.1", "35": "D"}"#, JsonError::UnsupportedBeginString),
+            (r#"{"8": "FIX.4.4", "35": "D", "Bogus": "1"}"#, JsonError::UnknownKey { key: "Bogus".into() }),
+            (r#"{"8": "FIX.4.4", "35": "D", "58": null}"#, JsonError::InvalidValue { key: "58".into() }),
+            (r#"{"8": "FIX.4.4", "35": "D", "96": {"base64": "a*=="}}"#, JsonError::InvalidValue { key: "96".into() }),
+            (r#"{"8": "FIX.4.4", "35": "D", "453": {"count": "02", "entries": [{}]}}"#, JsonError::InvalidValue { key: "453".into() }),
+            (r#"{"8": "FIX.4.4", "35": "D", "453": {"count": "0x1", "entries": [{}]}}"#, JsonError::InvalidValue { key: "453".into() }),
+        ];
+        for (text, error) in cases {
+            let json: JsonValue = text.parse().unwrap();
+            assert_eq!(json_to_fix(&json, &mut out, &options), Err(error), "{text}");
+        }
+        let json: JsonValue = r#"{"8": "FIX.4.4", "35": "D", "58": "0123456789"}"#
+            .parse()
+            .unwrap();
+        assert_eq!(json_to_fix(&json, &mut out[..40], &options), Err(JsonError::BufferTooSmall));
+        assert!(json_to_fix(&json, &mut out[..60], &options).is_ok());
+    }
+}
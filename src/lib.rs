@@ -8,8 +8,20 @@
 //!
 //! ## Platform Support
 //!
-//! **Unix/Linux Only** - This crate requires Unix-like systems and will NOT compile on Windows.
-//! Uses `libc::clock_gettime` and other Unix-specific system calls for maximum performance.
+//! The crate is `#![no_std]` at its core. Cargo features opt into the parts that
+//! need an operating system:
+//!
+//! - `alloc` - allocating conveniences such as `ReadError::missing_member_names`,
+//!   the QuickFIX data dictionary loader, the message code generator and `MemoryStore`
+//! - `std` (implies `alloc`) - ClOrdID generation (`write_tag_and_ClOrdID`)
+//! - `clock` - system-clock timestamp writers and the `libc::timespec` variants,
+//!   via `libc::clock_gettime`; **Unix only**
+//! - `file-store` (implies `std`) - the memory-mapped, restart-safe `FileStore`;
+//!   **Unix only**
+//!
+//! `std`, `clock` and `file-store` are enabled by default. With `default-features = false` the
+//! parsing and formatting helpers build for bare-metal targets; use
+//! `format_timestamp` / `format_logging_timestamp` with your own time source.
 //!
 //! ## Performance Philosophy
 //!
@@ -44,23 +56,75 @@
 //! // Result: "8=FIX.4.4\x019=0012\x0135=D\x0134=123\x01"
 //! ```
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 // Compile-time platform check
-#[cfg(not(unix))]
+#[cfg(all(feature = "clock", not(unix)))]
+compile_error!(
+    "the `clock` feature of fix44-forge-helpers requires a Unix-like operating system (Linux, macOS, BSD). Windows is not supported due to the use of Unix-specific system calls like libc::clock_gettime. Disable default features (keeping `std` if needed) or use WSL2 / a containerized Linux environment for Windows development."
+);
+
+#[cfg(all(feature = "file-store", not(unix)))]
 compile_error!(
-    "fix44-forge-helpers requires a Unix-like operating system (Linux, macOS, BSD). Windows is not supported due to the use of Unix-specific system calls like libc::clock_gettime. Consider using WSL2 or a containerized Linux environment for Windows development."
+    "the `file-store` feature of fix44-forge-helpers requires a Unix-like operating system (it memory-maps its log with libc::mmap). Disable default features (keeping `std` if needed) to build on other platforms."
 );
 
 pub mod buffer;
+#[cfg(feature = "alloc")]
+pub mod codegen;
+#[cfg(feature = "alloc")]
+pub mod dictionary;
+pub mod display;
 pub mod errors;
+pub mod fields;
+#[cfg(feature = "file-store")]
+pub mod file_store;
+pub mod frames;
+pub mod groups;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod reading;
+pub mod reject;
+pub mod resend;
+pub mod sequence;
+pub mod session;
 pub mod special;
+pub mod store;
+pub mod template;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "alloc")]
+pub mod validator;
 pub mod writing;
 
 // Re-export all public items for convenience
 pub use buffer::*;
+#[cfg(feature = "alloc")]
+pub use codegen::*;
+#[cfg(feature = "alloc")]
+pub use dictionary::*;
+pub use display::*;
 pub use errors::*;
+pub use fields::*;
+#[cfg(feature = "file-store")]
+pub use file_store::*;
+pub use frames::*;
+pub use groups::*;
+#[cfg(feature = "json")]
+pub use json::*;
 pub use reading::*;
+pub use reject::*;
+pub use resend::*;
+pub use sequence::*;
+pub use session::*;
 pub use special::*;
+pub use store::*;
+pub use template::*;
+#[cfg(feature = "alloc")]
+pub use validator::*;
 pub use writing::*;
 
 // Common constants used across modules
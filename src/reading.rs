@@ -19,6 +19,7 @@
 //! - Limited fractional precision (6 digits for f32, 15 for f64)
 //! - Extra fractional digits are ignored
 
+use crate::errors::ReadError;
 use core::str;
 
 /// Maximum fractional digits to parse for f32 (writers emit <= 6)
@@ -66,6 +67,7 @@ const POW10_F64: [f64; F64_FRAC_MAX + 1] = [
 
 /// Fast check if a byte is an ASCII digit
 #[inline(always)]
+#[allow(clippy::manual_range_contains)]
 fn is_digit(b: u8) -> bool {
     b'0' <= b && b <= b'9'
 }
@@ -73,6 +75,7 @@ fn is_digit(b: u8) -> bool {
 /// Parse a boolean value from bytes.
 ///
 /// Returns `true` for "Y", `false` for anything else.
+/// Use [`try_read_bool`] when invalid values must be detected.
 ///
 /// # Example
 /// ```
@@ -86,11 +89,71 @@ pub fn read_bool(buf: &[u8]) -> bool {
     matches!(buf, [b'Y'])
 }
 
+/// Strictly parse a FIX boolean value.
+///
+/// Accepts exactly `Y` or `N`. Anything else (including lowercase, `1`/`0` and
+/// empty input) returns [`ReadError::InvalidValue`] for the given field, so a
+/// corrupted PossDupFlag(43) or GapFillFlag(123) is never silently read as "No".
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::try_read_bool;
+/// assert_eq!(try_read_bool(b"Y", "PossDupFlag", 43).unwrap(), true);
+/// assert_eq!(try_read_bool(b"N", "PossDupFlag", 43).unwrap(), false);
+/// assert!(try_read_bool(b"y", "PossDupFlag", 43).is_err());
+/// ```
+#[inline(always)]
+pub fn try_read_bool(buf: &[u8], name: &'static str, tag: u16) -> Result<bool, ReadError> {
+    match buf {
+        [b'Y'] => Ok(true),
+        [b'N'] => Ok(false),
+        _ => Err(ReadError::InvalidValue {
+            name,
+            tag: tag.into(),
+            msg: "expected Y or N",
+            offset: None,
+        }),
+    }
+}
+
+/// Parse a boolean value, additionally accepting `1`/`0` and `true`/`false`.
+///
+/// Opt-in for counterparties known to send non-standard flags. The accepted
+/// spellings are listed explicitly; any other input returns
+/// [`ReadError::InvalidValue`].
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::try_read_bool_lenient;
+/// assert_eq!(try_read_bool_lenient(b"1", "GapFillFlag", 123).unwrap(), true);
+/// assert_eq!(try_read_bool_lenient(b"false", "GapFillFlag", 123).unwrap(), false);
+/// assert!(try_read_bool_lenient(b"yes", "GapFillFlag", 123).is_err());
+/// ```
+#[inline(always)]
+pub fn try_read_bool_lenient(
+    buf: &[u8],
+    name: &'static str,
+    tag: u16,
+) -> Result<bool, ReadError> {
+    match buf {
+        b"Y" | b"1" | b"true" => Ok(true),
+        b"N" | b"0" | b"false" => Ok(false),
+        _ => Err(ReadError::InvalidValue {
+            name,
+            tag: tag.into(),
+            msg: "expected Y, N, 1, 0, true or false",
+            offset: None,
+        }),
+    }
+}
+
 /// Convert bytes to a string slice without UTF-8 validation.
 ///
 /// # Safety
 /// This function assumes the input bytes are valid UTF-8.
-/// In FIX protocol context, this is typically safe.
+/// Bytes received from a counterparty are untrusted: prefer [`read_ascii_str`]
+/// for FIX `String` fields or [`try_read_str`] for UTF-8 text. This function is
+/// expected to become `unsafe` in the next major version.
 ///
 /// # Example
 /// ```
@@ -102,6 +165,197 @@ pub fn read_str(buf: &[u8]) -> &str {
     unsafe { str::from_utf8_unchecked(buf) }
 }
 
+/// Read a FIX `char` value (first byte of the field).
+///
+/// Returns 0 for empty input.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::read_char;
+/// assert_eq!(read_char(b"1"), b'1');
+/// assert_eq!(read_char(b""), 0);
+/// ```
+#[inline(always)]
+pub fn read_char(buf: &[u8]) -> u8 {
+    match buf.first() {
+        Some(&c) => c,
+        None => 0,
+    }
+}
+
+/// Iterator over the space-separated values of a `MultipleStringValue` field.
+///
+/// Created by [`read_multi_str`]. Repeated spaces are skipped, so no empty
+/// values are ever yielded. Each value is validated like [`try_read_str`];
+/// iteration stops at the first value that is not valid UTF-8, leaving it at
+/// the start of [`remainder`](Self::remainder).
+#[derive(Debug, Clone)]
+pub struct MultiStrIter<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> MultiStrIter<'a> {
+    /// The bytes not yet consumed by the iterator.
+    ///
+    /// Empty once every value has been yielded; non-empty after iteration
+    /// stopped on an invalid value.
+    #[inline(always)]
+    pub fn remainder(&self) -> &'a [u8] {
+        self.rest
+    }
+}
+
+impl<'a> Iterator for MultiStrIter<'a> {
+    type Item = &'a str;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<&'a str> {
+        let mut start = 0;
+        while start < self.rest.len() && self.rest[start] == b' ' {
+            start += 1;
+        }
+        if start == self.rest.len() {
+            self.rest = &[];
+            return None;
+        }
+        let mut end = start;
+        while end < self.rest.len() && self.rest[end] != b' ' {
+            end += 1;
+        }
+        let value = try_read_str(&self.rest[start..end], "", 0).ok()?;
+        self.rest = &self.rest[end..];
+        Some(value)
+    }
+}
+
+/// Iterator over the space-separated values of a `MultipleCharValue` field.
+///
+/// Created by [`read_multi_char`]. Yields each single-byte value; iteration
+/// stops at the first value longer than one byte (or not valid UTF-8), leaving
+/// it at the start of [`remainder`](Self::remainder).
+#[derive(Debug, Clone)]
+pub struct MultiCharIter<'a> {
+    inner: MultiStrIter<'a>,
+}
+
+impl<'a> MultiCharIter<'a> {
+    /// The bytes not yet consumed by the iterator.
+    ///
+    /// Empty once every value has been yielded; non-empty after iteration
+    /// stopped on an invalid value.
+    #[inline(always)]
+    pub fn remainder(&self) -> &'a [u8] {
+        self.inner.remainder()
+    }
+}
+
+impl Iterator for MultiCharIter<'_> {
+    type Item = u8;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<u8> {
+        let mut inner = self.inner.clone();
+        let c = match inner.next().map(str::as_bytes) {
+            Some(&[c]) => Some(c),
+            None => None,
+            // Leave a multi-byte value unconsumed so it shows in the remainder
+            Some(_) => return None,
+        };
+        self.inner = inner;
+        c
+    }
+}
+
+/// Iterate the values of a `MultipleStringValue` field such as QuoteCondition(276).
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::read_multi_str;
+/// let mut it = read_multi_str(b"A AB");
+/// assert_eq!(it.next(), Some("A"));
+/// assert_eq!(it.next(), Some("AB"));
+/// assert_eq!(it.next(), None);
+/// ```
+#[inline(always)]
+pub fn read_multi_str(buf: &[u8]) -> MultiStrIter<'_> {
+    MultiStrIter { rest: buf }
+}
+
+/// Iterate the values of a `MultipleCharValue` field such as ExecInst(18).
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::read_multi_char;
+/// let values: Vec<u8> = read_multi_char(b"1 2 G").collect();
+/// assert_eq!(values, b"12G");
+/// ```
+#[inline(always)]
+pub fn read_multi_char(buf: &[u8]) -> MultiCharIter<'_> {
+    MultiCharIter {
+        inner: read_multi_str(buf),
+    }
+}
+
+/// Convert bytes to a string slice, validating that they are ASCII.
+///
+/// FIX `String` fields are ASCII by spec, so this is the fast path for reading
+/// untrusted text: a high-bit check ([`<[u8]>::is_ascii`]) with no UTF-8 decoding.
+/// Returns [`ReadError::InvalidFormat`] if any byte is outside 0x00..=0x7F.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::read_ascii_str;
+/// assert_eq!(read_ascii_str(b"MSFT", "Symbol", 55).unwrap(), "MSFT");
+/// assert!(read_ascii_str("café".as_bytes(), "Symbol", 55).is_err());
+/// ```
+#[inline(always)]
+pub fn read_ascii_str<'a>(
+    buf: &'a [u8],
+    name: &'static str,
+    tag: u16,
+) -> Result<&'a str, ReadError> {
+    if buf.is_ascii() {
+        // ASCII is always valid UTF-8
+        Ok(unsafe { str::from_utf8_unchecked(buf) })
+    } else {
+        Err(ReadError::InvalidFormat {
+            name,
+            tag: tag.into(),
+            msg: "non-ASCII byte in String field",
+            offset: None,
+        })
+    }
+}
+
+/// Convert bytes to a string slice, validating UTF-8.
+///
+/// Pure-ASCII input (the common case in FIX) is accepted by the same ASCII
+/// check as [`read_ascii_str`]; only values containing non-ASCII bytes pay for
+/// full UTF-8 validation. Returns [`ReadError::InvalidFormat`] on invalid UTF-8.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::try_read_str;
+/// assert_eq!(try_read_str("café".as_bytes(), "Text", 58).unwrap(), "café");
+/// assert!(try_read_str(b"\xff\xfe", "Text", 58).is_err());
+/// ```
+#[inline(always)]
+pub fn try_read_str<'a>(
+    buf: &'a [u8],
+    name: &'static str,
+    tag: u16,
+) -> Result<&'a str, ReadError> {
+    if buf.is_ascii() {
+        return Ok(unsafe { str::from_utf8_unchecked(buf) });
+    }
+    str::from_utf8(buf).map_err(|_| ReadError::InvalidFormat {
+        name,
+        tag: tag.into(),
+        msg: "invalid UTF-8",
+        offset: None,
+    })
+}
+
 /// Parse a u16 from decimal bytes.
 ///
 /// Stops at first non-digit character. Returns 0 for empty input.
@@ -195,6 +449,50 @@ pub fn read_u64(buf: &[u8]) -> u64 {
     acc
 }
 
+/// Strictly parse a u64 from decimal bytes.
+///
+/// Unlike [`read_u64`], every byte must be a digit and the value must fit in a
+/// u64. Returns [`ReadError::InvalidFormat`] for empty or non-digit input and
+/// [`ReadError::InvalidValue`] on overflow.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::try_read_u64;
+/// assert_eq!(try_read_u64(b"12345", "BodyLength", 9).unwrap(), 12345);
+/// assert!(try_read_u64(b"123abc", "BodyLength", 9).is_err());
+/// assert!(try_read_u64(b"18446744073709551616", "BodyLength", 9).is_err());
+/// ```
+#[inline(always)]
+pub fn try_read_u64(
+    buf: &[u8],
+    name: &'static str,
+    tag: u16,
+) -> Result<u64, ReadError> {
+    if buf.is_empty() || !buf.iter().all(|&b| is_digit(b)) {
+        return Err(ReadError::InvalidFormat {
+            name,
+            tag: tag.into(),
+            msg: "expected decimal digits",
+            offset: None,
+        });
+    }
+    let mut acc: u64 = 0;
+    for &b in buf {
+        acc = match acc.checked_mul(10).and_then(|acc| acc.checked_add((b - b'0') as u64)) {
+            Some(acc) => acc,
+            None => {
+                return Err(ReadError::InvalidValue {
+                    name,
+                    tag: tag.into(),
+                    msg: "integer out of range",
+                    offset: None,
+                });
+            }
+        };
+    }
+    Ok(acc)
+}
+
 /// Parse an i16 from decimal bytes.
 ///
 /// Supports optional leading minus sign. Handles i16::MIN correctly.
@@ -284,6 +582,45 @@ pub fn read_i64(buf: &[u8]) -> i64 {
     }
 }
 
+/// Strictly parse an i64 from decimal bytes.
+///
+/// Accepts an optional leading minus sign followed by digits only, as
+/// [`try_read_u64`] does for unsigned values. Returns
+/// [`ReadError::InvalidFormat`] for malformed input and
+/// [`ReadError::InvalidValue`] when the value does not fit in an i64.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::try_read_i64;
+/// assert_eq!(try_read_i64(b"-42", "PegOffsetValue", 211).unwrap(), -42);
+/// assert_eq!(try_read_i64(b"-9223372036854775808", "PegOffsetValue", 211).unwrap(), i64::MIN);
+/// assert!(try_read_i64(b"-", "PegOffsetValue", 211).is_err());
+/// assert!(try_read_i64(b"9223372036854775808", "PegOffsetValue", 211).is_err());
+/// ```
+#[inline(always)]
+pub fn try_read_i64(
+    buf: &[u8],
+    name: &'static str,
+    tag: u16,
+) -> Result<i64, ReadError> {
+    let (neg, digits) = match buf {
+        [b'-', rest @ ..] => (true, rest),
+        _ => (false, buf),
+    };
+    let mag = try_read_u64(digits, name, tag)?;
+    let value = if neg {
+        0i64.checked_sub_unsigned(mag)
+    } else {
+        i64::try_from(mag).ok()
+    };
+    value.ok_or(ReadError::InvalidValue {
+        name,
+        tag: tag.into(),
+        msg: "integer out of range",
+        offset: None,
+    })
+}
+
 /// Parse an f32 from decimal bytes.
 ///
 /// Format: `[-]? [0-9]* ('.' [0-9]*)?`
@@ -428,11 +765,49 @@ pub fn read_f64(buf: &[u8]) -> f64 {
     }
 }
 
+/// Strictly parse an f64 from decimal bytes.
+///
+/// The whole input must match `[-]? [0-9]* ('.' [0-9]*)?` with at least one
+/// digit, so `1.`, `.5` and `-1.5` are accepted but `.`, `1e5` and `1.2.3` are
+/// not. The value is then read as by [`read_f64`]. Returns
+/// [`ReadError::InvalidFormat`] for malformed input.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::try_read_f64;
+/// assert_eq!(try_read_f64(b"-1.5", "Price", 44).unwrap(), -1.5);
+/// assert_eq!(try_read_f64(b".5", "Price", 44).unwrap(), 0.5);
+/// assert!(try_read_f64(b"1e5", "Price", 44).is_err());
+/// ```
+#[inline(always)]
+pub fn try_read_f64(
+    buf: &[u8],
+    name: &'static str,
+    tag: u16,
+) -> Result<f64, ReadError> {
+    let unsigned = buf.strip_prefix(b"-").unwrap_or(buf);
+    let (int, frac) = match unsigned.iter().position(|&b| b == b'.') {
+        Some(dot) => (&unsigned[..dot], &unsigned[dot + 1..]),
+        None => (unsigned, &[][..]),
+    };
+    let all_digits = |part: &[u8]| part.iter().all(|&b| is_digit(b));
+    if (int.is_empty() && frac.is_empty()) || !all_digits(int) || !all_digits(frac) {
+        return Err(ReadError::InvalidFormat {
+            name,
+            tag: tag.into(),
+            msg: "expected a decimal number",
+            offset: None,
+        });
+    }
+    Ok(read_f64(buf))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_read_bool() {
         assert_eq!(read_bool(b"Y"), true);
         assert_eq!(read_bool(b"N"), false);
@@ -440,6 +815,34 @@ mod tests {
         assert_eq!(read_bool(b"yes"), false);
     }
 
+    #[test]
+    fn test_try_read_bool() {
+        assert!(try_read_bool(b"Y", "PossDupFlag", 43).unwrap());
+        assert!(!try_read_bool(b"N", "PossDupFlag", 43).unwrap());
+        for bad in [&b""[..], b"y", b"n", b"1", b"0", b"YES", b"true", b"\x01"] {
+            match try_read_bool(bad, "PossDupFlag", 43) {
+                Err(ReadError::InvalidValue { name, tag, .. }) => {
+                    assert_eq!(name, "PossDupFlag");
+                    assert_eq!(tag, 43);
+                }
+                other => panic!("expected InvalidValue for {bad:?}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_read_bool_lenient() {
+        for good in [&b"Y"[..], b"1", b"true"] {
+            assert!(try_read_bool_lenient(good, "PossResend", 97).unwrap());
+        }
+        for good in [&b"N"[..], b"0", b"false"] {
+            assert!(!try_read_bool_lenient(good, "PossResend", 97).unwrap());
+        }
+        for bad in [&b""[..], b"y", b"TRUE", b"2", b"Yes"] {
+            assert!(try_read_bool_lenient(bad, "PossResend", 97).is_err());
+        }
+    }
+
     #[test]
     fn test_read_str() {
         assert_eq!(read_str(b"hello"), "hello");
@@ -447,6 +850,71 @@ mod tests {
         assert_eq!(read_str(b"test123"), "test123");
     }
 
+    #[test]
+    fn test_read_ascii_str() {
+        assert_eq!(read_ascii_str(b"EURUSD", "Symbol", 55).unwrap(), "EURUSD");
+        assert_eq!(read_ascii_str(b"", "Symbol", 55).unwrap(), "");
+        assert!(matches!(
+            read_ascii_str("Zürich".as_bytes(), "Symbol", 55),
+            Err(ReadError::InvalidFormat { tag: 55, .. })
+        ));
+    }
+
+    #[test]
+    fn test_try_read_str() {
+        assert_eq!(try_read_str(b"hello", "Text", 58).unwrap(), "hello");
+        assert_eq!(try_read_str("Zürich".as_bytes(), "Text", 58).unwrap(), "Zürich");
+        // Truncated multi-byte sequence and lone continuation byte
+        assert!(try_read_str(b"Z\xc3", "Text", 58).is_err());
+        assert!(try_read_str(b"\x80", "Text", 58).is_err());
+        assert!(matches!(
+            try_read_str(b"\xff", "Text", 58),
+            Err(ReadError::InvalidFormat { name: "Text", .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_char() {
+        assert_eq!(read_char(b"1"), b'1');
+        assert_eq!(read_char(b"AB"), b'A');
+        assert_eq!(read_char(b""), 0);
+    }
+
+    #[test]
+    fn test_read_multi_values() {
+        let chars: Vec<u8> = read_multi_char(b"1 2 G").collect();
+        assert_eq!(chars, b"12G");
+
+        let strs: Vec<&str> = read_multi_str(b" A  AB C ").collect();
+        assert_eq!(strs, ["A", "AB", "C"]);
+
+        assert_eq!(read_multi_str(b"").count(), 0);
+        assert_eq!(read_multi_char(b"   ").count(), 0);
+
+        let mut it = read_multi_str(b"A \xff\xfe B");
+        assert_eq!(it.next(), Some("A"));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.remainder(), b" \xff\xfe B");
+
+        let strs: Vec<&str> = read_multi_str("A café".as_bytes()).collect();
+        assert_eq!(strs, ["A", "café"]);
+
+        let mut it = read_multi_char(b"1 AB 2");
+        assert_eq!(it.next(), Some(b'1'));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.remainder(), b" AB 2");
+
+        let mut it = read_multi_char(b"1 \xff");
+        assert_eq!(it.next(), Some(b'1'));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.remainder(), b" \xff");
+
+        let mut it = read_multi_char(b"1 2 ");
+        assert_eq!(it.by_ref().count(), 2);
+        assert_eq!(it.remainder(), b"");
+    }
+
     #[test]
     fn test_read_u16() {
         assert_eq!(read_u16(b"0"), 0);
@@ -529,4 +997,37 @@ mod tests {
         assert_eq!(read_f64(b""), 0.0);
         assert!(read_f64(b"-0").is_sign_negative());
     }
+
+    #[test]
+    fn test_try_read_numbers() {
+        assert_eq!(try_read_u64(b"0", "", 0).unwrap(), 0);
+        assert_eq!(try_read_u64(b"18446744073709551615", "", 0).unwrap(), u64::MAX);
+        assert!(matches!(
+            try_read_u64(b"18446744073709551616", "BodyLength", 9),
+            Err(ReadError::InvalidValue { tag: 9, .. })
+        ));
+        for bad in [&b""[..], b"-1", b"12a", b" 1", b"+1"] {
+            assert!(matches!(
+                try_read_u64(bad, "BodyLength", 9),
+                Err(ReadError::InvalidFormat { tag: 9, .. })
+            ));
+        }
+
+        assert_eq!(try_read_i64(b"-0", "", 0).unwrap(), 0);
+        assert_eq!(try_read_i64(b"9223372036854775807", "", 0).unwrap(), i64::MAX);
+        assert_eq!(try_read_i64(b"-9223372036854775808", "", 0).unwrap(), i64::MIN);
+        assert!(try_read_i64(b"-9223372036854775809", "", 0).is_err());
+        assert!(try_read_i64(b"--1", "", 0).is_err());
+        assert!(try_read_i64(b"-", "", 0).is_err());
+
+        assert_eq!(try_read_f64(b"42", "", 0).unwrap(), 42.0);
+        assert_eq!(try_read_f64(b"1.", "", 0).unwrap(), 1.0);
+        assert!(try_read_f64(b"-0", "", 0).unwrap().is_sign_negative());
+        for bad in [&b""[..], b"-", b".", b"-.", b"1.2.3", b"1e5", b"1,5", b" 1"] {
+            assert!(matches!(
+                try_read_f64(bad, "Price", 44),
+                Err(ReadError::InvalidFormat { tag: 44, .. })
+            ));
+        }
+    }
 }
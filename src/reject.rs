@@ -0,0 +1,185 @@
+//! Session-level Reject (35=3) message construction from parse errors.
+//!
+//! When an inbound message fails to parse, FIX requires a session-level Reject
+//! referencing the offending message: RefSeqNum(45), RefTagID(371),
+//! RefMsgType(372) and SessionRejectReason(373). This module builds that message
+//! directly into a forge buffer from a [`ReadError`] and the offending
+//! [`MessageHeader`], with no allocation.
+
+use crate::buffer::{CHECKSUM_FIELD_LEN, FORGE_WRITE_START, finalize_message};
+use crate::errors::ReadError;
+use crate::fields::MessageHeader;
+use crate::special::format_timestamp;
+use crate::writing::{write_tag_and_bytes, write_tag_and_u16, write_tag_and_u32};
+use core::fmt::{self, Write};
+
+/// `core::fmt::Write` adapter over a byte slice that silently truncates at `limit`.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+    limit: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let n = s.len().min(self.limit.saturating_sub(self.pos));
+        self.buf[self.pos..self.pos + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.pos += n;
+        Ok(())
+    }
+}
+
+/// Write a complete session-level Reject (35=3) into a forge buffer.
+///
+/// The reject is addressed back to the sender of the offending message
+/// (SenderCompID/TargetCompID swapped), references it through RefSeqNum(45) and
+/// RefMsgType(372), and carries RefTagID(371), SessionRejectReason(373) and the
+/// error's `Display` text in Text(58). BodyLength and CheckSum are finalized.
+///
+/// # Arguments
+/// * `buffer` - A buffer created with `forge_out_buffer()`
+/// * `offending` - Header of the message being rejected
+/// * `msg_seq_num` - Our outbound MsgSeqNum(34) for the Reject
+/// * `sending_secs` / `sending_nanos` - SendingTime(52) as time since the Unix epoch
+/// * `error` - The parse error being reported
+///
+/// Returns the total message length. Text(58) is truncated if the buffer is
+/// too small to hold it.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let inbound = b"8=FIX.4.4\x019=30\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=7\x0154=9\x01";
+/// let header = MessageHeader::parse(inbound);
+/// let error = ReadError::InvalidValue { name: "Side", tag: 54, msg: "unknown side", offset: None };
+///
+/// let mut buffer = forge_out_buffer("FIX.4.4");
+/// let len = write_reject(&mut buffer, &header, 1, 0, 0, &error);
+/// let reject = &buffer[..len];
+/// assert!(reject.starts_with(b"8=FIX.4.4\x019="));
+/// assert!(reject.windows(5).any(|w| w == b"45=7\x01"));
+/// assert!(reject.windows(7).any(|w| w == b"371=54\x01"));
+/// assert!(reject.windows(6).any(|w| w == b"373=5\x01"));
+/// ```
+pub fn write_reject(
+    buffer: &mut [u8],
+    offending: &MessageHeader<'_>,
+    msg_seq_num: u32,
+    sending_secs: u64,
+    sending_nanos: u32,
+    error: &ReadError,
+) -> usize {
+    let mut pos = FORGE_WRITE_START;
+    buffer[pos] = b'3';
+    buffer[pos + 1] = 0x01;
+    pos += 2;
+
+    pos += write_tag_and_bytes(buffer, pos, b"49=", offending.target_comp_id);
+    pos += write_tag_and_bytes(buffer, pos, b"56=", offending.sender_comp_id);
+    pos += write_tag_and_u32(buffer, pos, b"34=", msg_seq_num);
+    pos += format_timestamp(buffer, pos, b"52=", sending_secs, sending_nanos);
+
+    pos += write_tag_and_u32(buffer, pos, b"45=", offending.msg_seq_num);
+    if let Some(ref_tag) = error.ref_tag_id() {
+        pos += write_tag_and_u32(buffer, pos, b"371=", ref_tag);
+    }
+    if !offending.msg_type.is_empty() {
+        pos += write_tag_and_bytes(buffer, pos, b"372=", offending.msg_type);
+    }
+    pos += write_tag_and_u16(buffer, pos, b"373=", error.session_reject_reason());
+
+    // Text(58): error description, truncated to leave room for SOH + CheckSum
+    buffer[pos..pos + 3].copy_from_slice(b"58=");
+    pos += 3;
+    let limit = buffer
+        .len()
+        .saturating_sub(1 + CHECKSUM_FIELD_LEN)
+        .max(pos);
+    let mut text = SliceWriter {
+        buf: buffer,
+        pos,
+        limit,
+    };
+    let _ = write!(text, "{error}");
+    pos = text.pos;
+    buffer[pos] = 0x01;
+    pos += 1;
+
+    finalize_message(buffer, pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{checksum, forge_out_buffer};
+    use crate::errors::{MemberKind, MissingMask};
+    use crate::fields::TagValueIter;
+    use crate::reading::read_u16;
+
+    #[test]
+    fn test_write_reject_fields() {
+        let inbound = b"8=FIX.4.4\x019=40\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=42\x0110=000\x01";
+        let header = MessageHeader::parse(inbound);
+        let error = ReadError::DuplicateTag {
+            offset: 30,
+            tag: 55,
+        };
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = write_reject(&mut buffer, &header, 9, 0, 5_000_000, &error);
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+
+        let expected_text = error.to_string();
+        assert_eq!(
+            fields,
+            [
+                (8, &b"FIX.4.4"[..]),
+                (9, &buffer[12..16]),
+                (35, b"3"),
+                (49, b"BROKER"),
+                (56, b"CLIENT"),
+                (34, b"9"),
+                (52, b"19700101-00:00:00.005"),
+                (45, b"42"),
+                (371, b"55"),
+                (372, b"D"),
+                (373, b"13"),
+                (58, expected_text.as_bytes()),
+                (10, &buffer[len - 4..len - 1]),
+            ]
+        );
+
+        // BodyLength and CheckSum are consistent
+        assert_eq!(read_u16(&buffer[12..16]) as usize, len - 7 - 17);
+        assert_eq!(
+            read_u16(&buffer[len - 4..len - 1]),
+            checksum(&buffer[..len - 7]) as u16
+        );
+    }
+
+    #[test]
+    fn test_write_reject_truncates_text() {
+        let header = MessageHeader {
+            begin_string: b"FIX.4.4",
+            msg_type: b"",
+            msg_seq_num: 1,
+            sender_comp_id: b"A",
+            target_comp_id: b"B",
+        };
+        let error = ReadError::MissingRequiredFields {
+            missing_mask: MissingMask::from_bits(0b1),
+            meta: &[("Symbol", 55, MemberKind::Field)],
+            tag: None,
+        };
+        let mut buffer = [0u8; 120];
+        buffer[..FORGE_WRITE_START].copy_from_slice(b"8=FIX.4.4\x019=0000\x0135=");
+        let len = write_reject(&mut buffer, &header, 2, 0, 0, &error);
+        assert_eq!(len, buffer.len());
+        assert_eq!(&buffer[len - 7..len - 4], b"10=");
+
+        // No RefMsgType for an unparsed MsgType, RefTagID from the missing member
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        assert!(fields.iter().all(|(tag, _)| *tag != 372));
+        assert!(fields.contains(&(371, &b"55"[..])));
+        assert!(fields.contains(&(373, &b"1"[..])));
+    }
+}
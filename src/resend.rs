@@ -0,0 +1,261 @@
+//! PossDup rewriting of stored messages for resend.
+//!
+//! A message answered to a ResendRequest(2) goes out with its original
+//! MsgSeqNum and body, but with PossDupFlag(43)=Y, OrigSendingTime(122) set to
+//! the original SendingTime(52), and a fresh SendingTime. [`rewrite_for_resend`]
+//! does this in a single pass over the stored bytes: unchanged runs are copied
+//! in bulk, only 43, 52 and 122 are touched, and BodyLength(9) and CheckSum(10)
+//! are recomputed with [`finalize_message`].
+//!
+//! The input must be a complete message built in a forge buffer (4-digit
+//! BodyLength, as kept by a [`MessageStore`](crate::MessageStore)). Any 43 or
+//! 122 already present is dropped; the new 43 and 122 follow the new 52 in the
+//! header. A message stored without a SendingTime gets 52, 43 and 122 (both
+//! timestamps set to now) inserted after MsgSeqNum(34), or after MsgType(35)
+//! if 34 is missing too. Length-prefixed data fields are skipped by length, so
+//! an embedded `52=` is never mistaken for a field.
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let mut buffer = forge_out_buffer("FIX.4.4");
+//! let mut pos = FORGE_WRITE_START;
+//! buffer[pos] = b'D';
+//! buffer[pos + 1] = 0x01;
+//! pos += 2;
+//! pos += write_tag_and_u32(&mut buffer, pos, b"34=", 7);
+//! pos += format_timestamp(&mut buffer, pos, b"52=", 1_700_000_000, 0);
+//! pos += write_tag_and_str(&mut buffer, pos, b"11=", "ORD-7");
+//! let len = finalize_message(&mut buffer, pos);
+//!
+//! let mut out = [0u8; FORGE_BUFFER_SIZE];
+//! let out_len = rewrite_for_resend_at(&buffer[..len], &mut out, 1_700_000_060, 0);
+//! assert_eq!(
+//!     &out[..out_len - CHECKSUM_FIELD_LEN],
+//!     b"8=FIX.4.4\x019=0075\x0135=D\x0134=7\x0152=20231114-22:14:20.000\x0143=Y\x01\
+//!       122=20231114-22:13:20.000\x0111=ORD-7\x01"
+//! );
+//! ```
+
+use crate::buffer::{BODY_LENGTH_VALUE_POS, CHECKSUM_FIELD_LEN, finalize_message};
+use crate::fields::TagValueIter;
+use crate::special::format_timestamp;
+use crate::writing::write_tag_and_bytes;
+
+/// Most bytes [`rewrite_for_resend`] adds to a message: `43=Y|` plus a new
+/// SendingTime(52) and OrigSendingTime(122) of 21 digits each, when the stored
+/// message has no SendingTime.
+pub const RESEND_EXTRA_LEN: usize = 56;
+
+/// Length of a `52=` or `122=` field holding a millisecond timestamp, minus the tag.
+const TIMESTAMP_FIELD_LEN: usize = 21 + 1;
+
+/// Rewrite stored message `msg` into `out` for resend, with SendingTime(52) `now`.
+///
+/// Requires the `clock` feature; see [`rewrite_for_resend_at`] for the clock-free form.
+///
+/// Buffer requirements:
+/// - `out` must hold `msg.len() + RESEND_EXTRA_LEN` bytes.
+///
+/// Returns the length of the rewritten message.
+#[cfg(feature = "clock")]
+#[inline]
+pub fn rewrite_for_resend(
+    msg: &[u8],
+    out: &mut [u8],
+    now: &libc::timespec,
+) -> usize {
+    rewrite_for_resend_at(msg, out, now.tv_sec as u64, now.tv_nsec as u32)
+}
+
+/// Rewrite stored message `msg` into `out` for resend, with SendingTime(52)
+/// given as seconds and nanoseconds since the Unix epoch.
+///
+/// Buffer requirements:
+/// - `out` must hold `msg.len() + RESEND_EXTRA_LEN` bytes.
+///
+/// Returns the length of the rewritten message.
+pub fn rewrite_for_resend_at(
+    msg: &[u8],
+    out: &mut [u8],
+    secs: u64,
+    nanos: u32,
+) -> usize {
+    debug_assert!(msg.len() >= BODY_LENGTH_VALUE_POS + 5 + CHECKSUM_FIELD_LEN);
+    debug_assert!(
+        &msg[BODY_LENGTH_VALUE_POS - 2..BODY_LENGTH_VALUE_POS] == b"9="
+            && msg[BODY_LENGTH_VALUE_POS + 4] == 0x01,
+        "not a forge buffer message"
+    );
+    debug_assert!(out.len() >= msg.len() + RESEND_EXTRA_LEN);
+
+    // Everything before CheckSum(10); finalize_message writes a new one
+    let body_end = msg.len() - CHECKSUM_FIELD_LEN;
+    let mut it = TagValueIter::new(&msg[..body_end]);
+    let mut pos = 0;
+    // msg[..copied] has been written to out
+    let mut copied = 0;
+    // Position in out after 34 (or 35), where a missing 52 is inserted
+    let mut anchor = 0;
+    let mut has_sending_time = false;
+    loop {
+        let start = it.position();
+        let Some((tag, value)) = it.next() else {
+            break;
+        };
+        if !matches!(tag, 34 | 35 | 43 | 52 | 122) {
+            continue;
+        }
+        if matches!(tag, 34 | 35) {
+            // Kept as is; only its end position is needed
+            let end = it.position();
+            out[pos..pos + end - copied].copy_from_slice(&msg[copied..end]);
+            pos += end - copied;
+            copied = end;
+            anchor = pos;
+            continue;
+        }
+        out[pos..pos + start - copied].copy_from_slice(&msg[copied..start]);
+        pos += start - copied;
+        copied = it.position();
+        if tag == 52 {
+            has_sending_time = true;
+            pos += format_timestamp(out, pos, b"52=", secs, nanos);
+            pos += write_tag_and_bytes(out, pos, b"43=", b"Y");
+            pos += write_tag_and_bytes(out, pos, b"122=", value);
+        }
+    }
+    out[pos..pos + body_end - copied].copy_from_slice(&msg[copied..body_end]);
+    pos += body_end - copied;
+    if !has_sending_time {
+        // No original SendingTime: OrigSendingTime(122) is now as well
+        let inserted = 3 + TIMESTAMP_FIELD_LEN + 5 + 4 + TIMESTAMP_FIELD_LEN;
+        out.copy_within(anchor..pos, anchor + inserted);
+        let mut at = anchor;
+        at += format_timestamp(out, at, b"52=", secs, nanos);
+        at += write_tag_and_bytes(out, at, b"43=", b"Y");
+        at += format_timestamp(out, at, b"122=", secs, nanos);
+        debug_assert_eq!(at, anchor + inserted);
+        pos += inserted;
+    }
+    finalize_message(out, pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{FORGE_BUFFER_SIZE, FORGE_WRITE_START, checksum, forge_out_buffer};
+    use crate::test_util::forge;
+    use crate::writing::{write_tag_and_data, write_tag_and_str};
+
+    /// Body length and checksum of `msg` are consistent.
+    fn assert_framed(msg: &[u8]) {
+        let body_len = crate::reading::read_u32(&msg[12..16]) as usize;
+        assert_eq!(body_len, msg.len() - 17 - CHECKSUM_FIELD_LEN);
+        let sum = checksum(&msg[..msg.len() - CHECKSUM_FIELD_LEN]);
+        assert_eq!(crate::reading::read_u32(&msg[msg.len() - 4..msg.len() - 1]), sum as u32);
+    }
+
+    #[test]
+    fn test_rewrite_for_resend() {
+        let (buffer, len) = forge(|buf, offset| {
+            let mut pos = offset;
+            pos += format_timestamp(buf, pos, b"52=", 1_700_000_000, 123_000_000);
+            pos += write_tag_and_str(buf, pos, b"11=", "ORD-12");
+            pos - offset
+        });
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = rewrite_for_resend_at(&buffer[..len], &mut out, 1_700_000_100, 0);
+        assert_eq!(n, len + 5 + 26);
+        assert_eq!(
+            &out[17..n - CHECKSUM_FIELD_LEN],
+            b"35=D\x0149=CLIENT\x0156=BROKER\x0134=12\x0152=20231114-22:15:00.000\x0143=Y\x01\
+              122=20231114-22:13:20.123\x0111=ORD-12\x01"
+        );
+        assert_framed(&out[..n]);
+    }
+
+    #[test]
+    fn test_rewrite_replaces_existing_poss_dup_fields() {
+        // A message already marked 43=N, and one with a stale 122
+        let (buffer, len) = forge(|buf, offset| {
+            let mut pos = offset;
+            pos += write_tag_and_str(buf, pos, b"43=", "N");
+            pos += format_timestamp(buf, pos, b"52=", 1_700_000_000, 0);
+            pos += format_timestamp(buf, pos, b"122=", 1_600_000_000, 0);
+            pos += write_tag_and_str(buf, pos, b"58=", "hello");
+            pos - offset
+        });
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = rewrite_for_resend_at(&buffer[..len], &mut out, 1_700_000_100, 0);
+        let rewritten = &out[..n];
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(rewritten).collect();
+        assert_eq!(fields.iter().filter(|(tag, _)| *tag == 43).count(), 1);
+        assert_eq!(fields.iter().filter(|(tag, _)| *tag == 122).count(), 1);
+        assert!(fields.contains(&(43, &b"Y"[..])));
+        assert!(fields.contains(&(122, &b"20231114-22:13:20.000"[..])));
+        assert!(fields.contains(&(58, &b"hello"[..])));
+        assert_framed(rewritten);
+    }
+
+    #[test]
+    fn test_rewrite_inserts_missing_sending_time() {
+        // Stored without 52, but with a stale 43 and 122
+        let (buffer, len) = forge(|buf, offset| {
+            let mut pos = offset;
+            pos += write_tag_and_str(buf, pos, b"43=", "N");
+            pos += format_timestamp(buf, pos, b"122=", 1_600_000_000, 0);
+            pos += write_tag_and_str(buf, pos, b"11=", "ORD-12");
+            pos - offset
+        });
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = rewrite_for_resend_at(&buffer[..len], &mut out, 1_700_000_100, 0);
+        assert_eq!(
+            &out[17..n - CHECKSUM_FIELD_LEN],
+            b"35=D\x0149=CLIENT\x0156=BROKER\x0134=12\x0152=20231114-22:15:00.000\x0143=Y\x01\
+              122=20231114-22:15:00.000\x0111=ORD-12\x01"
+        );
+        assert_framed(&out[..n]);
+
+        // Without 34 either, the fields follow 35
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        buffer[FORGE_WRITE_START..FORGE_WRITE_START + 2].copy_from_slice(b"0\x01");
+        let len = finalize_message(&mut buffer, FORGE_WRITE_START + 2);
+        let n = rewrite_for_resend_at(&buffer[..len], &mut out, 1_700_000_100, 0);
+        assert_eq!(n, len + RESEND_EXTRA_LEN);
+        assert_eq!(
+            &out[17..n - CHECKSUM_FIELD_LEN],
+            b"35=0\x0152=20231114-22:15:00.000\x0143=Y\x01122=20231114-22:15:00.000\x01"
+        );
+        assert_framed(&out[..n]);
+    }
+
+    #[test]
+    fn test_rewrite_skips_data_fields() {
+        let (buffer, len) = forge(|buf, offset| {
+            let mut pos = offset;
+            pos += format_timestamp(buf, pos, b"52=", 1_700_000_000, 0);
+            pos += write_tag_and_data(buf, pos, b"95=", b"96=", b"\x0152=x\x0143=N\x01");
+            pos - offset
+        });
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = rewrite_for_resend_at(&buffer[..len], &mut out, 1_700_000_100, 0);
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&out[..n]).collect();
+        assert!(fields.contains(&(96, &b"\x0152=x\x0143=N\x01"[..])));
+        assert_framed(&out[..n]);
+    }
+
+    #[cfg(feature = "clock")]
+    #[test]
+    fn test_rewrite_for_resend_from_timespec() {
+        let (buffer, len) = forge(|buf, offset| format_timestamp(buf, offset, b"52=", 1_700_000_000, 0));
+        let now = libc::timespec {
+            tv_sec: 1_700_000_100,
+            tv_nsec: 0,
+        };
+        let mut a = [0u8; FORGE_BUFFER_SIZE];
+        let mut b = [0u8; FORGE_BUFFER_SIZE];
+        let n = rewrite_for_resend(&buffer[..len], &mut a, &now);
+        assert_eq!(n, rewrite_for_resend_at(&buffer[..len], &mut b, 1_700_000_100, 0));
+        assert_eq!(a[..n], b[..n]);
+    }
+}
@@ -0,0 +1,323 @@
+//! MsgSeqNum(34) tracking with gap detection and SequenceReset handling.
+//!
+//! [`SequenceManager`] implements the FIX 4.4 session-level sequence rules on
+//! plain numbers (as parsed with [`read_u32`](crate::read_u32)), independent of
+//! any transport or message layout:
+//!
+//! - an inbound MsgSeqNum above the expected one is a gap: send
+//!   ResendRequest(2) with BeginSeqNo(7) = expected and EndSeqNo(16) = 0
+//!   (infinity), once per gap
+//! - one below the expected is a duplicate if PossDupFlag(43)=Y, otherwise a
+//!   fatal error (Logout and disconnect)
+//! - SequenceReset(4) in GapFill mode obeys the rules above for its own
+//!   MsgSeqNum and then moves the expected number to NewSeqNo(36); in Reset
+//!   mode it ignores MsgSeqNum and moves straight to NewSeqNo. A NewSeqNo that
+//!   would move the expected number backwards is rejected, and so is a
+//!   NewSeqNo of `u32::MAX`, after which no message could follow.
+//! - a message at `u32::MAX` when it is the expected number is treated as too
+//!   low: the sequence space is exhausted and the session must be reset
+//!
+//! # Example
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let mut seq = SequenceManager::new();
+//! assert_eq!(seq.on_message(1, false), SeqAction::Accept);
+//! assert_eq!(seq.on_message(5, false), SeqAction::ResendRequest { begin_seq_no: 2, end_seq_no: 0 });
+//!
+//! // The counterparty fills 2..=4 and resends 5
+//! assert_eq!(seq.on_sequence_reset(2, true, true, 5), SeqAction::Accept);
+//! assert_eq!(seq.on_message(5, true), SeqAction::Accept);
+//! assert_eq!(seq.next_inbound(), 6);
+//! assert_eq!(seq.resend_pending(), None);
+//! ```
+
+/// What to do with an inbound message after the sequence check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeqAction {
+    /// In sequence (or a valid reset): process it
+    Accept,
+    /// Gap detected: send ResendRequest(2) and do not process the message
+    ResendRequest {
+        /// BeginSeqNo(7)
+        begin_seq_no: u32,
+        /// EndSeqNo(16); 0 requests everything up to the latest
+        end_seq_no: u32,
+    },
+    /// Gap already being resent: do not process the message, send nothing
+    AwaitingResend,
+    /// Below the expected number with PossDupFlag(43)=Y: ignore it
+    Duplicate,
+    /// Below the expected number without PossDupFlag(43)=Y, or the last
+    /// possible MsgSeqNum (`u32::MAX`): Logout and disconnect
+    TooLow {
+        /// MsgSeqNum expected
+        expected: u32,
+        /// MsgSeqNum received
+        received: u32,
+    },
+    /// SequenceReset(4) whose NewSeqNo(36) would decrease the expected number,
+    /// or is `u32::MAX`: send a session Reject with SessionRejectReason(373) = 5 and RefTagID(371) = 36
+    RejectNewSeqNo,
+}
+
+/// Inbound and outbound MsgSeqNum(34) state of one session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceManager {
+    next_inbound: u32,
+    next_outbound: u32,
+    /// Highest MsgSeqNum received while a gap is being resent
+    resend_target: Option<u32>,
+}
+
+impl Default for SequenceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SequenceManager {
+    /// Both directions start at 1.
+    pub const fn new() -> Self {
+        Self::with_numbers(1, 1)
+    }
+
+    /// Resume with known next sequence numbers (e.g. restored from a message store).
+    pub const fn with_numbers(
+        next_inbound: u32,
+        next_outbound: u32,
+    ) -> Self {
+        SequenceManager {
+            next_inbound,
+            next_outbound,
+            resend_target: None,
+        }
+    }
+
+    /// MsgSeqNum expected on the next inbound message.
+    pub fn next_inbound(&self) -> u32 {
+        self.next_inbound
+    }
+
+    /// MsgSeqNum of the next outbound message.
+    pub fn next_outbound(&self) -> u32 {
+        self.next_outbound
+    }
+
+    /// Take the next outbound MsgSeqNum.
+    pub fn allocate_outbound(&mut self) -> u32 {
+        let seq = self.next_outbound;
+        self.next_outbound += 1;
+        seq
+    }
+
+    /// `(BeginSeqNo, highest MsgSeqNum seen)` of the gap being resent, if any.
+    pub fn resend_pending(&self) -> Option<(u32, u32)> {
+        self.resend_target
+            .map(|target| (self.next_inbound, target))
+    }
+
+    /// Reset both directions to 1 (ResetSeqNumFlag(141)=Y).
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Check the MsgSeqNum of any inbound message other than SequenceReset(4).
+    pub fn on_message(
+        &mut self,
+        msg_seq_num: u32,
+        poss_dup: bool,
+    ) -> SeqAction {
+        let expected = self.next_inbound;
+        if msg_seq_num == expected {
+            let Some(next) = msg_seq_num.checked_add(1) else {
+                return SeqAction::TooLow {
+                    expected,
+                    received: msg_seq_num,
+                };
+            };
+            self.advance_to(next);
+            SeqAction::Accept
+        } else if msg_seq_num > expected {
+            self.gap(msg_seq_num)
+        } else if poss_dup {
+            SeqAction::Duplicate
+        } else {
+            SeqAction::TooLow {
+                expected,
+                received: msg_seq_num,
+            }
+        }
+    }
+
+    /// Handle SequenceReset(4) with GapFillFlag(123) and NewSeqNo(36).
+    pub fn on_sequence_reset(
+        &mut self,
+        msg_seq_num: u32,
+        poss_dup: bool,
+        gap_fill: bool,
+        new_seq_no: u32,
+    ) -> SeqAction {
+        let expected = self.next_inbound;
+        if new_seq_no == u32::MAX {
+            return SeqAction::RejectNewSeqNo;
+        }
+        if !gap_fill {
+            // Reset mode ignores MsgSeqNum
+            if new_seq_no < expected {
+                return SeqAction::RejectNewSeqNo;
+            }
+            self.advance_to(new_seq_no);
+            return SeqAction::Accept;
+        }
+        if msg_seq_num != expected {
+            return self.on_message(msg_seq_num, poss_dup);
+        }
+        if new_seq_no <= msg_seq_num {
+            return SeqAction::RejectNewSeqNo;
+        }
+        self.advance_to(new_seq_no);
+        SeqAction::Accept
+    }
+
+    fn gap(
+        &mut self,
+        msg_seq_num: u32,
+    ) -> SeqAction {
+        match self.resend_target {
+            Some(target) => {
+                self.resend_target = Some(target.max(msg_seq_num));
+                SeqAction::AwaitingResend
+            }
+            None => {
+                self.resend_target = Some(msg_seq_num);
+                SeqAction::ResendRequest {
+                    begin_seq_no: self.next_inbound,
+                    end_seq_no: 0,
+                }
+            }
+        }
+    }
+
+    fn advance_to(
+        &mut self,
+        next: u32,
+    ) {
+        self.next_inbound = next;
+        if self
+            .resend_target
+            .is_some_and(|target| next > target)
+        {
+            self.resend_target = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    enum Input {
+        /// Any message but SequenceReset: (MsgSeqNum, PossDupFlag)
+        Msg(u32, bool),
+        /// SequenceReset: (MsgSeqNum, PossDupFlag, GapFillFlag, NewSeqNo)
+        Reset(u32, bool, bool, u32),
+    }
+
+    struct Case {
+        name: &'static str,
+        next_inbound: u32,
+        resend_target: Option<u32>,
+        input: Input,
+        action: SeqAction,
+        after: u32,
+        resend_after: Option<u32>,
+    }
+
+    const fn case(
+        name: &'static str,
+        next_inbound: u32,
+        resend_target: Option<u32>,
+        input: Input,
+        action: SeqAction,
+        after: u32,
+        resend_after: Option<u32>,
+    ) -> Case {
+        Case {
+            name,
+            next_inbound,
+            resend_target,
+            input,
+            action,
+            after,
+            resend_after,
+        }
+    }
+
+    use Input::*;
+    use SeqAction::*;
+
+    /// FIX 4.4 session-level test cases (numbering from the FIX session test suite).
+    const CASES: &[Case] = &[
+        // 2 - Receive message standard header
+        case("2a in sequence", 5, None, Msg(5, false), Accept, 6, None),
+        case("2b too high", 5, None, Msg(8, false), ResendRequest { begin_seq_no: 5, end_seq_no: 0 }, 5, Some(8)),
+        case("2b too high, resend outstanding", 5, Some(8), Msg(9, false), AwaitingResend, 5, Some(9)),
+        case("2b too high, older than outstanding", 5, Some(8), Msg(7, false), AwaitingResend, 5, Some(8)),
+        case("2b gap filled by resend", 7, Some(8), Msg(7, true), Accept, 8, Some(8)),
+        case("2b last resent message ends the resend", 8, Some(8), Msg(8, true), Accept, 9, None),
+        case("2c too low", 5, None, Msg(3, false), TooLow { expected: 5, received: 3 }, 5, None),
+        case("2d too low, PossDup", 5, None, Msg(3, true), Duplicate, 5, None),
+        case("2d in sequence, PossDup", 5, None, Msg(5, true), Accept, 6, None),
+        case("2 last MsgSeqNum", u32::MAX, None, Msg(u32::MAX, false), TooLow { expected: u32::MAX, received: u32::MAX }, u32::MAX, None),
+        case("2b too high, last MsgSeqNum", 5, None, Msg(u32::MAX, false), ResendRequest { begin_seq_no: 5, end_seq_no: 0 }, 5, Some(u32::MAX)),
+        // 10 - SequenceReset, GapFill mode
+        case("10a gap fill in sequence", 5, None, Reset(5, false, true, 9), Accept, 9, None),
+        case("10a gap fill closes resend", 5, Some(8), Reset(5, true, true, 9), Accept, 9, None),
+        case("10a gap fill partially closes resend", 5, Some(8), Reset(5, true, true, 7), Accept, 7, Some(8)),
+        case("10b gap fill too high", 5, None, Reset(7, false, true, 9), ResendRequest { begin_seq_no: 5, end_seq_no: 0 }, 5, Some(7)),
+        case("10c gap fill too low, PossDup", 5, None, Reset(3, true, true, 9), Duplicate, 5, None),
+        case("10d gap fill too low", 5, None, Reset(3, false, true, 9), TooLow { expected: 5, received: 3 }, 5, None),
+        case("10e gap fill NewSeqNo not above MsgSeqNum", 5, None, Reset(5, false, true, 5), RejectNewSeqNo, 5, None),
+        case("10e gap fill NewSeqNo below MsgSeqNum", 5, None, Reset(5, false, true, 2), RejectNewSeqNo, 5, None),
+        case("10e gap fill NewSeqNo at u32::MAX", 5, None, Reset(5, false, true, u32::MAX), RejectNewSeqNo, 5, None),
+        // 11 - SequenceReset, Reset mode
+        case("11a reset forward", 5, None, Reset(1, false, false, 20), Accept, 20, None),
+        case("11a reset forward past resend", 5, Some(8), Reset(99, false, false, 20), Accept, 20, None),
+        case("11b reset to expected", 5, None, Reset(5, false, false, 5), Accept, 5, None),
+        case("11c reset backwards", 5, None, Reset(5, false, false, 3), RejectNewSeqNo, 5, None),
+        case("11c reset to u32::MAX", 5, None, Reset(5, false, false, u32::MAX), RejectNewSeqNo, 5, None),
+    ];
+
+    #[test]
+    fn test_session_level_cases() {
+        for c in CASES {
+            let mut seq = SequenceManager {
+                next_inbound: c.next_inbound,
+                next_outbound: 1,
+                resend_target: c.resend_target,
+            };
+            let action = match c.input {
+                Msg(n, poss_dup) => seq.on_message(n, poss_dup),
+                Reset(n, poss_dup, gap_fill, new_seq_no) => {
+                    seq.on_sequence_reset(n, poss_dup, gap_fill, new_seq_no)
+                }
+            };
+            assert_eq!(action, c.action, "{}", c.name);
+            assert_eq!(seq.next_inbound(), c.after, "{}", c.name);
+            assert_eq!(seq.resend_target, c.resend_after, "{}", c.name);
+        }
+    }
+
+    #[test]
+    fn test_outbound_and_reset() {
+        let mut seq = SequenceManager::with_numbers(10, 40);
+        assert_eq!(seq.allocate_outbound(), 40);
+        assert_eq!(seq.allocate_outbound(), 41);
+        assert_eq!(seq.next_outbound(), 42);
+        assert_eq!(seq.on_message(12, false), ResendRequest { begin_seq_no: 10, end_seq_no: 0 });
+        assert_eq!(seq.resend_pending(), Some((10, 12)));
+        seq.reset();
+        assert_eq!(seq, SequenceManager::new());
+    }
+}
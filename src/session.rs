@@ -0,0 +1,1291 @@
+//! Sans-IO FIX session layer: Logon, Heartbeat, TestRequest, Logout and
+//! sequence recovery (ResendRequest and SequenceReset).
+//!
+//! [`Session`] performs no I/O and reads no clock. The caller feeds it complete
+//! inbound frames ([`handle_message`](Session::handle_message)) and clock ticks
+//! ([`handle_timeout`](Session::handle_timeout)), then drains outbound messages
+//! into forge buffers ([`poll_transmit`](Session::poll_transmit)) and session
+//! events ([`poll_event`](Session::poll_event)). Every time is passed in as
+//! seconds and nanoseconds since the Unix epoch, so a session can be driven
+//! entirely from scripted byte exchanges and a mock clock.
+//!
+//! Inbound MsgSeqNum(34) checks are delegated to a [`SequenceManager`]: gaps
+//! queue a ResendRequest(2), SequenceReset(4) moves the expected number, and
+//! inbound ResendRequests are answered with a SequenceReset-GapFill over the
+//! requested range (the session keeps no copy of what it sent).
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let config = SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Initiator);
+//! let mut session = Session::new(config, 1_700_000_000, 0);
+//!
+//! // The initiator's first transmit is its Logon
+//! let mut buffer = forge_out_buffer("FIX.4.4");
+//! let len = session.poll_transmit(&mut buffer, 1_700_000_000, 0).unwrap();
+//! assert!(buffer[..len].starts_with(b"8=FIX.4.4\x019="));
+//! assert_eq!(MessageHeader::parse(&buffer[..len]).msg_type, b"A");
+//! assert_eq!(session.state(), SessionState::LogonSent);
+//!
+//! // No Logon response within the logon timeout: disconnect
+//! session.handle_timeout(1_700_000_010, 0);
+//! assert_eq!(
+//!     session.poll_event(),
+//!     Some(SessionEvent::Disconnected(DisconnectReason::LogonTimeout))
+//! );
+//! ```
+
+use crate::buffer::{FORGE_WRITE_START, finalize_message};
+use crate::errors::ReadError;
+use crate::fields::{MessageHeader, TagValueIter};
+use crate::reading::{read_bool, read_u32};
+use crate::reject::write_reject;
+use crate::sequence::{SeqAction, SequenceManager};
+use crate::special::format_timestamp;
+use crate::writing::{write_tag_and_bytes, write_tag_and_str, write_tag_and_u32};
+
+/// Longest TestReqID(112) echoed back in a Heartbeat; a TestRequest with a
+/// longer id is rejected instead of answered.
+pub const MAX_TEST_REQ_ID_LEN: usize = 32;
+
+/// Outbound messages a session can hold before they are transmitted.
+const OUTBOX_CAPACITY: usize = 8;
+
+/// Most replies one inbound message can queue (e.g. Logon and ResendRequest).
+const MAX_REPLIES: usize = 2;
+
+/// Outbox slots kept free for timers and [`Session::logout`]: a TestRequest,
+/// a Heartbeat and a Logout, each queued at most once until transmitted.
+const RESERVED_SLOTS: usize = 3;
+
+/// Events a session can hold before they are polled.
+const EVENT_CAPACITY: usize = 8;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Which side opens the session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionRole {
+    /// Sends the first Logon
+    Initiator,
+    /// Waits for the counterparty's Logon and answers it
+    Acceptor,
+}
+
+/// Static session parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionConfig<'c> {
+    /// BeginString(8), e.g. `FIX.4.4`; must match the forge buffers used
+    pub begin_string: &'c str,
+    /// Our SenderCompID(49)
+    pub sender_comp_id: &'c str,
+    /// The counterparty's CompID, sent as TargetCompID(56)
+    pub target_comp_id: &'c str,
+    /// Initiator or acceptor
+    pub role: SessionRole,
+    /// HeartBtInt(108) in seconds proposed by an initiator; acceptors adopt the initiator's
+    pub heartbeat_interval: u32,
+    /// Seconds to wait for the Logon (or its response) before disconnecting
+    pub logon_timeout: u32,
+    /// Seconds to wait for the Logout response before disconnecting
+    pub logout_timeout: u32,
+    /// Initiator only: send ResetSeqNumFlag(141)=Y on Logon and restart both sequences at 1
+    pub reset_seq_num_on_logon: bool,
+}
+
+impl<'c> SessionConfig<'c> {
+    /// Config with a 30 s heartbeat, 10 s logon timeout, 2 s logout timeout and
+    /// no sequence reset on Logon.
+    pub const fn new(
+        begin_string: &'c str,
+        sender_comp_id: &'c str,
+        target_comp_id: &'c str,
+        role: SessionRole,
+    ) -> Self {
+        SessionConfig {
+            begin_string,
+            sender_comp_id,
+            target_comp_id,
+            role,
+            heartbeat_interval: 30,
+            logon_timeout: 10,
+            logout_timeout: 2,
+            reset_seq_num_on_logon: false,
+        }
+    }
+}
+
+/// Where the session is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    /// Acceptor waiting for the counterparty's Logon
+    AwaitingLogon,
+    /// Initiator waiting for the Logon response
+    LogonSent,
+    /// Logged on; application messages flow
+    Active,
+    /// Logout sent, waiting for the counterparty's Logout
+    LogoutSent,
+    /// Done; the caller should close the connection
+    Disconnected,
+}
+
+/// Why a session ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// Logon exchange not completed within `logon_timeout`
+    LogonTimeout,
+    /// No inbound traffic, not even after a TestRequest
+    HeartbeatTimeout,
+    /// Logout not answered within `logout_timeout`
+    LogoutTimeout,
+    /// Logout exchange completed (either side initiated)
+    LogoutComplete,
+    /// First message was not a Logon, or arrived in the wrong state
+    InvalidLogon,
+    /// SenderCompID/TargetCompID do not match the config
+    CompIdMismatch,
+    /// Inbound MsgSeqNum lower than expected without PossDupFlag(43)=Y
+    SequenceTooLow,
+}
+
+/// Notifications for the application, drained with [`Session::poll_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// Logon exchange completed with the negotiated heartbeat interval
+    LoggedOn {
+        /// HeartBtInt(108) in seconds
+        heartbeat_interval: u32,
+    },
+    /// The session ended; close the connection
+    Disconnected(DisconnectReason),
+}
+
+/// What the caller should do with an inbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inbound {
+    /// In-session application message for the caller to process
+    Application,
+    /// Session-level message handled by the session
+    Admin,
+    /// Dropped: duplicate, wrong state or after disconnect
+    Ignored,
+    /// Not processed: too many replies are waiting to be transmitted. Drain
+    /// [`Session::poll_transmit`] and pass the same message again.
+    OutboxFull,
+}
+
+/// An admin message waiting to be transmitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outbound {
+    Logon {
+        reset_seq_num: bool,
+    },
+    Heartbeat {
+        test_req_id: [u8; MAX_TEST_REQ_ID_LEN],
+        len: u8,
+    },
+    TestRequest {
+        id: u32,
+    },
+    Logout {
+        text: &'static str,
+    },
+    ResendRequest {
+        begin_seq_no: u32,
+        end_seq_no: u32,
+    },
+    /// SequenceReset-GapFill sent as MsgSeqNum `msg_seq_num`
+    GapFill {
+        msg_seq_num: u32,
+        new_seq_no: u32,
+    },
+    /// Reject of an inbound admin message with a bad field value
+    Reject {
+        msg_type: &'static [u8],
+        ref_seq_num: u32,
+        name: &'static str,
+        tag: u32,
+        msg: &'static str,
+        offset: usize,
+    },
+}
+
+/// Fixed-capacity FIFO; callers keep it from overflowing (see `RESERVED_SLOTS`).
+#[derive(Debug, Clone)]
+struct Queue<T: Copy, const N: usize> {
+    items: [Option<T>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<T: Copy, const N: usize> Queue<T, N> {
+    const fn new() -> Self {
+        Queue {
+            items: [None; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn push(
+        &mut self,
+        item: T,
+    ) {
+        debug_assert!(self.len < N, "session queue overflow");
+        if self.len < N {
+            self.items[(self.head + self.len) % N] = Some(item);
+            self.len += 1;
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.items[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        item
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.len).filter_map(move |i| self.items[(self.head + i) % N].as_ref())
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let (head, len) = (self.head, self.len);
+        let (wrapped, tail) = self.items.split_at_mut(head);
+        tail.iter_mut()
+            .chain(wrapped)
+            .take(len)
+            .filter_map(Option::as_mut)
+    }
+
+    fn free(&self) -> usize {
+        N - self.len
+    }
+}
+
+/// Admin fields of an inbound message, gathered in one pass.
+#[derive(Debug, Default)]
+struct AdminFields<'a> {
+    poss_dup: bool,
+    heartbeat_interval: Option<u32>,
+    test_req_id: &'a [u8],
+    /// Offset of TestReqID(112), for rejects
+    test_req_id_offset: usize,
+    reset_seq_num: bool,
+    begin_seq_no: u32,
+    end_seq_no: u32,
+    gap_fill: bool,
+    new_seq_no: u32,
+    /// Offset of NewSeqNo(36), for rejects
+    new_seq_no_offset: usize,
+}
+
+impl<'a> AdminFields<'a> {
+    fn parse(msg: &'a [u8]) -> Self {
+        let mut fields = AdminFields::default();
+        let mut it = TagValueIter::new(msg);
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.next() else {
+                break;
+            };
+            match tag {
+                7 => fields.begin_seq_no = read_u32(value),
+                16 => fields.end_seq_no = read_u32(value),
+                36 => {
+                    fields.new_seq_no = read_u32(value);
+                    fields.new_seq_no_offset = offset;
+                }
+                43 => fields.poss_dup = read_bool(value),
+                108 => fields.heartbeat_interval = Some(read_u32(value)),
+                112 => {
+                    fields.test_req_id = value;
+                    fields.test_req_id_offset = offset;
+                }
+                123 => fields.gap_fill = read_bool(value),
+                141 => fields.reset_seq_num = read_bool(value),
+                _ => {}
+            }
+        }
+        fields
+    }
+}
+
+/// Sans-IO FIX session. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Session<'c> {
+    config: SessionConfig<'c>,
+    state: SessionState,
+    /// Negotiated HeartBtInt(108) in seconds (0 disables heartbeats)
+    heartbeat_interval: u32,
+    sequences: SequenceManager,
+    /// Times in nanoseconds since the Unix epoch
+    last_sent: u64,
+    last_received: u64,
+    /// When the current Logon or Logout wait started
+    state_since: u64,
+    /// When the outstanding TestRequest was sent
+    test_request_sent: Option<u64>,
+    test_request_count: u32,
+    /// Disconnect once the queued Logout has been transmitted
+    disconnect_after_logout: Option<DisconnectReason>,
+    outbox: Queue<Outbound, OUTBOX_CAPACITY>,
+    events: Queue<SessionEvent, EVENT_CAPACITY>,
+}
+
+impl<'c> Session<'c> {
+    /// Start a session on a freshly established connection.
+    ///
+    /// An initiator queues its Logon; an acceptor waits for the counterparty's.
+    pub fn new(
+        config: SessionConfig<'c>,
+        secs: u64,
+        nanos: u32,
+    ) -> Self {
+        Self::with_sequences(config, SequenceManager::new(), secs, nanos)
+    }
+
+    /// Like [`new`](Session::new), but resuming from known sequence numbers
+    /// (e.g. restored from a message store after a restart).
+    ///
+    /// An initiator with `reset_seq_num_on_logon` set starts over at 1 anyway.
+    pub fn with_sequences(
+        config: SessionConfig<'c>,
+        sequences: SequenceManager,
+        secs: u64,
+        nanos: u32,
+    ) -> Self {
+        let now = to_nanos(secs, nanos);
+        let mut session = Session {
+            config,
+            state: SessionState::AwaitingLogon,
+            heartbeat_interval: config.heartbeat_interval,
+            sequences,
+            last_sent: now,
+            last_received: now,
+            state_since: now,
+            test_request_sent: None,
+            test_request_count: 0,
+            disconnect_after_logout: None,
+            outbox: Queue::new(),
+            events: Queue::new(),
+        };
+        if config.role == SessionRole::Initiator {
+            let reset_seq_num = config.reset_seq_num_on_logon;
+            if reset_seq_num {
+                session.sequences.reset();
+            }
+            session.outbox.push(Outbound::Logon { reset_seq_num });
+            session.state = SessionState::LogonSent;
+        }
+        session
+    }
+
+    /// The session config.
+    pub fn config(&self) -> &SessionConfig<'c> {
+        &self.config
+    }
+
+    /// Current lifecycle state.
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    /// Negotiated HeartBtInt(108) in seconds.
+    pub fn heartbeat_interval(&self) -> u32 {
+        self.heartbeat_interval
+    }
+
+    /// MsgSeqNum(34) of the next outbound message.
+    pub fn next_sender_seq(&self) -> u32 {
+        self.sequences.next_outbound()
+    }
+
+    /// MsgSeqNum(34) expected on the next inbound message.
+    pub fn next_target_seq(&self) -> u32 {
+        self.sequences.next_inbound()
+    }
+
+    /// Inbound and outbound sequence state, e.g. to persist across restarts.
+    pub fn sequences(&self) -> &SequenceManager {
+        &self.sequences
+    }
+
+    /// Process one complete inbound message.
+    ///
+    /// Returns [`Inbound::Application`] when the caller should process `msg`
+    /// itself; session-level messages are handled here and may queue replies
+    /// and events. Heartbeats answering TestRequests are coalesced, echoing the
+    /// latest TestReqID(112), and a TestRequest whose id is longer than
+    /// [`MAX_TEST_REQ_ID_LEN`] is rejected; when other replies pile up without
+    /// [`poll_transmit`](Session::poll_transmit) being drained, `msg` is left
+    /// untouched and [`Inbound::OutboxFull`] is returned.
+    pub fn handle_message(
+        &mut self,
+        msg: &[u8],
+        secs: u64,
+        nanos: u32,
+    ) -> Inbound {
+        if self.state == SessionState::Disconnected {
+            return Inbound::Ignored;
+        }
+        if self.outbox.free() < MAX_REPLIES + RESERVED_SLOTS {
+            return Inbound::OutboxFull;
+        }
+        let now = to_nanos(secs, nanos);
+        self.last_received = now;
+        self.test_request_sent = None;
+
+        let header = MessageHeader::parse(msg);
+        let fields = AdminFields::parse(msg);
+        let is_logon = header.msg_type == b"A";
+
+        match self.state {
+            SessionState::AwaitingLogon | SessionState::LogonSent if !is_logon => {
+                self.disconnect(DisconnectReason::InvalidLogon);
+                return Inbound::Ignored;
+            }
+            _ => {}
+        }
+        if header.sender_comp_id != self.config.target_comp_id.as_bytes()
+            || header.target_comp_id != self.config.sender_comp_id.as_bytes()
+        {
+            if matches!(self.state, SessionState::AwaitingLogon | SessionState::LogonSent) {
+                self.disconnect(DisconnectReason::CompIdMismatch);
+            } else {
+                self.logout_and_disconnect("CompID problem", DisconnectReason::CompIdMismatch);
+            }
+            return Inbound::Ignored;
+        }
+
+        if is_logon && fields.reset_seq_num {
+            // The Logon itself is MsgSeqNum 1; an acceptor restarts its side too
+            self.sequences = match self.state {
+                SessionState::AwaitingLogon => SequenceManager::new(),
+                _ => SequenceManager::with_numbers(1, self.sequences.next_outbound()),
+            };
+        }
+        let action = if header.msg_type == b"4" {
+            self.sequences.on_sequence_reset(
+                header.msg_seq_num,
+                fields.poss_dup,
+                fields.gap_fill,
+                fields.new_seq_no,
+            )
+        } else {
+            self.sequences
+                .on_message(header.msg_seq_num, fields.poss_dup)
+        };
+        let resend = match action {
+            SeqAction::Accept => None,
+            SeqAction::ResendRequest {
+                begin_seq_no,
+                end_seq_no,
+            } => Some(Outbound::ResendRequest {
+                begin_seq_no,
+                end_seq_no,
+            }),
+            SeqAction::AwaitingResend => None,
+            SeqAction::Duplicate => return Inbound::Ignored,
+            SeqAction::TooLow { .. } => {
+                self.logout_and_disconnect("MsgSeqNum too low", DisconnectReason::SequenceTooLow);
+                return Inbound::Ignored;
+            }
+            SeqAction::RejectNewSeqNo => {
+                self.outbox.push(Outbound::Reject {
+                    msg_type: b"4",
+                    ref_seq_num: header.msg_seq_num,
+                    name: "NewSeqNo",
+                    tag: 36,
+                    msg: "NewSeqNo below expected MsgSeqNum or at u32::MAX",
+                    offset: fields.new_seq_no_offset,
+                });
+                return Inbound::Admin;
+            }
+        };
+        if !matches!(action, SeqAction::Accept) {
+            // Out of sequence: only Logon and Logout are acted on now, the
+            // rest will be resent
+            let inbound = match header.msg_type {
+                b"A" => self.on_logon(&fields, now),
+                b"5" => self.on_logout(),
+                _ => Inbound::Ignored,
+            };
+            if let Some(request) = resend {
+                self.outbox.push(request);
+            }
+            return inbound;
+        }
+
+        match header.msg_type {
+            b"A" => self.on_logon(&fields, now),
+            b"0" => Inbound::Admin,
+            b"1" if fields.test_req_id.len() > MAX_TEST_REQ_ID_LEN => {
+                // Echoing part of the id would answer a TestRequest never sent
+                self.outbox.push(Outbound::Reject {
+                    msg_type: b"1",
+                    ref_seq_num: header.msg_seq_num,
+                    name: "TestReqID",
+                    tag: 112,
+                    msg: "TestReqID longer than MAX_TEST_REQ_ID_LEN",
+                    offset: fields.test_req_id_offset,
+                });
+                Inbound::Admin
+            }
+            b"1" => {
+                self.queue_heartbeat(fields.test_req_id);
+                Inbound::Admin
+            }
+            b"5" => self.on_logout(),
+            b"2" => {
+                self.on_resend_request(fields.begin_seq_no, fields.end_seq_no);
+                Inbound::Admin
+            }
+            b"3" | b"4" => Inbound::Admin,
+            _ => Inbound::Application,
+        }
+    }
+
+    fn on_logout(&mut self) -> Inbound {
+        if self.state == SessionState::LogoutSent {
+            self.disconnect(DisconnectReason::LogoutComplete);
+        } else {
+            self.logout_and_disconnect("", DisconnectReason::LogoutComplete);
+        }
+        Inbound::Admin
+    }
+
+    /// Nothing sent is kept, so the whole requested range is gap-filled.
+    fn on_resend_request(
+        &mut self,
+        begin_seq_no: u32,
+        end_seq_no: u32,
+    ) {
+        let next = self.sequences.next_outbound();
+        let new_seq_no = match end_seq_no {
+            0 => next,
+            end => end.saturating_add(1).min(next),
+        };
+        if begin_seq_no > 0 && begin_seq_no < new_seq_no {
+            self.outbox.push(Outbound::GapFill {
+                msg_seq_num: begin_seq_no,
+                new_seq_no,
+            });
+        }
+    }
+
+    fn on_logon(
+        &mut self,
+        fields: &AdminFields<'_>,
+        now: u64,
+    ) -> Inbound {
+        match self.state {
+            SessionState::AwaitingLogon => {
+                let Some(interval) = fields.heartbeat_interval else {
+                    self.disconnect(DisconnectReason::InvalidLogon);
+                    return Inbound::Ignored;
+                };
+                self.heartbeat_interval = interval;
+                self.outbox.push(Outbound::Logon {
+                    reset_seq_num: fields.reset_seq_num,
+                });
+            }
+            SessionState::LogonSent => {}
+            // A second Logon on an established session is ignored
+            _ => return Inbound::Ignored,
+        }
+        self.state = SessionState::Active;
+        self.state_since = now;
+        self.events.push(SessionEvent::LoggedOn {
+            heartbeat_interval: self.heartbeat_interval,
+        });
+        Inbound::Admin
+    }
+
+    /// Advance the timers to `now`: heartbeats, TestRequests and timeouts.
+    ///
+    /// Call it whenever [`next_timeout`](Session::next_timeout) passes (or
+    /// simply on a regular tick).
+    pub fn handle_timeout(
+        &mut self,
+        secs: u64,
+        nanos: u32,
+    ) {
+        let now = to_nanos(secs, nanos);
+        match self.state {
+            SessionState::AwaitingLogon | SessionState::LogonSent => {
+                if now >= self.state_since + secs_to_nanos(self.config.logon_timeout) {
+                    self.disconnect(DisconnectReason::LogonTimeout);
+                }
+            }
+            SessionState::Active | SessionState::LogoutSent => {
+                if self.state == SessionState::LogoutSent
+                    && now >= self.state_since + secs_to_nanos(self.config.logout_timeout)
+                {
+                    self.disconnect(DisconnectReason::LogoutTimeout);
+                    return;
+                }
+                if self.heartbeat_interval == 0 {
+                    return;
+                }
+                let interval = secs_to_nanos(self.heartbeat_interval);
+                match self.test_request_sent {
+                    Some(sent) if now >= sent + interval => {
+                        self.disconnect(DisconnectReason::HeartbeatTimeout);
+                        return;
+                    }
+                    Some(_) => {}
+                    None if now >= self.last_received + interval + interval / 5 => {
+                        self.test_request_count += 1;
+                        self.outbox.push(Outbound::TestRequest {
+                            id: self.test_request_count,
+                        });
+                        self.test_request_sent = Some(now);
+                    }
+                    None => {}
+                }
+                let heartbeat_queued = self
+                    .outbox
+                    .iter()
+                    .any(|m| matches!(m, Outbound::Heartbeat { .. } | Outbound::TestRequest { .. }));
+                if now >= self.last_sent + interval && !heartbeat_queued {
+                    self.queue_heartbeat(b"");
+                }
+            }
+            SessionState::Disconnected => {}
+        }
+    }
+
+    /// Next time [`handle_timeout`](Session::handle_timeout) has work to do,
+    /// as `(secs, nanos)` since the Unix epoch.
+    pub fn next_timeout(&self) -> Option<(u64, u32)> {
+        let deadline = match self.state {
+            SessionState::AwaitingLogon | SessionState::LogonSent => {
+                self.state_since + secs_to_nanos(self.config.logon_timeout)
+            }
+            SessionState::Active | SessionState::LogoutSent => {
+                let logout = (self.state == SessionState::LogoutSent)
+                    .then(|| self.state_since + secs_to_nanos(self.config.logout_timeout));
+                let heartbeat = (self.heartbeat_interval > 0).then(|| {
+                    let interval = secs_to_nanos(self.heartbeat_interval);
+                    let inbound = match self.test_request_sent {
+                        Some(sent) => sent + interval,
+                        None => self.last_received + interval + interval / 5,
+                    };
+                    inbound.min(self.last_sent + interval)
+                });
+                match (logout, heartbeat) {
+                    (Some(a), Some(b)) => a.min(b),
+                    (Some(a), None) | (None, Some(a)) => a,
+                    (None, None) => return None,
+                }
+            }
+            SessionState::Disconnected => return None,
+        };
+        Some((deadline / NANOS_PER_SEC, (deadline % NANOS_PER_SEC) as u32))
+    }
+
+    /// Start a graceful logout; the session disconnects once the counterparty
+    /// answers or `logout_timeout` passes.
+    pub fn logout(
+        &mut self,
+        secs: u64,
+        nanos: u32,
+    ) {
+        if matches!(self.state, SessionState::Active) {
+            self.outbox.push(Outbound::Logout { text: "" });
+            self.state = SessionState::LogoutSent;
+            self.state_since = to_nanos(secs, nanos);
+        }
+    }
+
+    /// Write the next queued session message into `buffer`, a buffer created
+    /// with `forge_out_buffer(config.begin_string)`.
+    ///
+    /// Returns the finalized message length, or `None` when nothing is queued.
+    pub fn poll_transmit(
+        &mut self,
+        buffer: &mut [u8],
+        secs: u64,
+        nanos: u32,
+    ) -> Option<usize> {
+        let message = self.outbox.pop()?;
+        let len = match message {
+            Outbound::Logon { reset_seq_num } => {
+                let mut pos = self.begin_message(buffer, b"A", secs, nanos);
+                pos += write_tag_and_u32(buffer, pos, b"98=", 0);
+                pos += write_tag_and_u32(buffer, pos, b"108=", self.heartbeat_interval);
+                if reset_seq_num {
+                    pos += write_tag_and_bytes(buffer, pos, b"141=", b"Y");
+                }
+                finalize_message(buffer, pos)
+            }
+            Outbound::Heartbeat { test_req_id, len } => {
+                let mut pos = self.begin_message(buffer, b"0", secs, nanos);
+                if len > 0 {
+                    pos += write_tag_and_bytes(buffer, pos, b"112=", &test_req_id[..len as usize]);
+                }
+                finalize_message(buffer, pos)
+            }
+            Outbound::TestRequest { id } => {
+                let mut pos = self.begin_message(buffer, b"1", secs, nanos);
+                pos += write_tag_and_u32(buffer, pos, b"112=", id);
+                finalize_message(buffer, pos)
+            }
+            Outbound::Logout { text } => {
+                let mut pos = self.begin_message(buffer, b"5", secs, nanos);
+                if !text.is_empty() {
+                    pos += write_tag_and_str(buffer, pos, b"58=", text);
+                }
+                let len = finalize_message(buffer, pos);
+                if let Some(reason) = self.disconnect_after_logout.take() {
+                    self.disconnect(reason);
+                }
+                len
+            }
+            Outbound::ResendRequest {
+                begin_seq_no,
+                end_seq_no,
+            } => {
+                let mut pos = self.begin_message(buffer, b"2", secs, nanos);
+                pos += write_tag_and_u32(buffer, pos, b"7=", begin_seq_no);
+                pos += write_tag_and_u32(buffer, pos, b"16=", end_seq_no);
+                finalize_message(buffer, pos)
+            }
+            Outbound::GapFill {
+                msg_seq_num,
+                new_seq_no,
+            } => {
+                let mut pos = self.write_header(buffer, b"4", msg_seq_num, secs, nanos);
+                pos += write_tag_and_bytes(buffer, pos, b"43=", b"Y");
+                pos += format_timestamp(buffer, pos, b"122=", secs, nanos);
+                pos += write_tag_and_bytes(buffer, pos, b"123=", b"Y");
+                pos += write_tag_and_u32(buffer, pos, b"36=", new_seq_no);
+                finalize_message(buffer, pos)
+            }
+            Outbound::Reject {
+                msg_type,
+                ref_seq_num,
+                name,
+                tag,
+                msg,
+                offset,
+            } => {
+                let offending = MessageHeader {
+                    begin_string: self.config.begin_string.as_bytes(),
+                    msg_type,
+                    msg_seq_num: ref_seq_num,
+                    sender_comp_id: self.config.target_comp_id.as_bytes(),
+                    target_comp_id: self.config.sender_comp_id.as_bytes(),
+                };
+                let error = ReadError::InvalidValue {
+                    name,
+                    tag,
+                    msg,
+                    offset: Some(offset),
+                };
+                let seq = self.sequences.allocate_outbound();
+                self.last_sent = to_nanos(secs, nanos);
+                write_reject(buffer, &offending, seq, secs, nanos, &error)
+            }
+        };
+        Some(len)
+    }
+
+    /// Next session event, if any.
+    pub fn poll_event(&mut self) -> Option<SessionEvent> {
+        self.events.pop()
+    }
+
+    /// Write MsgType and the standard header (49, 56, 34, 52) of an outbound
+    /// message into a forge buffer, consuming the next MsgSeqNum.
+    ///
+    /// Returns the position after SendingTime(52); write the body there and
+    /// finish with [`finalize_message`]. Session
+    /// messages use this too, so application messages share the sequence.
+    ///
+    /// # Example
+    /// ```
+    /// # use fix44_forge_helpers::*;
+    /// let config = SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Acceptor);
+    /// let mut session = Session::new(config, 0, 0);
+    /// let mut buffer = forge_out_buffer("FIX.4.4");
+    /// let mut pos = session.begin_message(&mut buffer, b"D", 0, 0);
+    /// pos += write_tag_and_str(&mut buffer, pos, b"11=", "ORD-1");
+    /// let len = finalize_message(&mut buffer, pos);
+    /// assert_eq!(
+    ///     &buffer[20..len - 7],
+    ///     b"D\x0149=CLIENT\x0156=BROKER\x0134=1\x0152=19700101-00:00:00.000\x0111=ORD-1\x01"
+    /// );
+    /// assert_eq!(session.next_sender_seq(), 2);
+    /// ```
+    pub fn begin_message(
+        &mut self,
+        buffer: &mut [u8],
+        msg_type: &[u8],
+        secs: u64,
+        nanos: u32,
+    ) -> usize {
+        let seq = self.sequences.allocate_outbound();
+        self.write_header(buffer, msg_type, seq, secs, nanos)
+    }
+
+    fn write_header(
+        &mut self,
+        buffer: &mut [u8],
+        msg_type: &[u8],
+        msg_seq_num: u32,
+        secs: u64,
+        nanos: u32,
+    ) -> usize {
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos..pos + msg_type.len()].copy_from_slice(msg_type);
+        pos += msg_type.len();
+        buffer[pos] = 0x01;
+        pos += 1;
+        pos += write_tag_and_str(buffer, pos, b"49=", self.config.sender_comp_id);
+        pos += write_tag_and_str(buffer, pos, b"56=", self.config.target_comp_id);
+        pos += write_tag_and_u32(buffer, pos, b"34=", msg_seq_num);
+        pos += format_timestamp(buffer, pos, b"52=", secs, nanos);
+        self.last_sent = to_nanos(secs, nanos);
+        pos
+    }
+
+    /// Queue a Heartbeat, or update the one already queued: a single
+    /// Heartbeat echoing the latest TestReqID answers a burst of TestRequests.
+    /// `id` is at most [`MAX_TEST_REQ_ID_LEN`] bytes.
+    fn queue_heartbeat(
+        &mut self,
+        id: &[u8],
+    ) {
+        let len = id.len();
+        let mut test_req_id = [0u8; MAX_TEST_REQ_ID_LEN];
+        test_req_id[..len].copy_from_slice(id);
+        let queued = self
+            .outbox
+            .iter_mut()
+            .find(|m| matches!(m, Outbound::Heartbeat { .. }));
+        match queued {
+            Some(Outbound::Heartbeat {
+                test_req_id: queued_id,
+                len: queued_len,
+            }) => {
+                if len > 0 {
+                    *queued_id = test_req_id;
+                    *queued_len = len as u8;
+                }
+            }
+            _ => self.outbox.push(Outbound::Heartbeat {
+                test_req_id,
+                len: len as u8,
+            }),
+        }
+    }
+
+    fn logout_and_disconnect(
+        &mut self,
+        text: &'static str,
+        reason: DisconnectReason,
+    ) {
+        if self.disconnect_after_logout.is_none() {
+            self.outbox.push(Outbound::Logout { text });
+            self.disconnect_after_logout = Some(reason);
+            self.state = SessionState::LogoutSent;
+        }
+    }
+
+    fn disconnect(
+        &mut self,
+        reason: DisconnectReason,
+    ) {
+        if self.state != SessionState::Disconnected {
+            self.state = SessionState::Disconnected;
+            self.events.push(SessionEvent::Disconnected(reason));
+        }
+    }
+}
+
+#[inline(always)]
+fn to_nanos(
+    secs: u64,
+    nanos: u32,
+) -> u64 {
+    secs * NANOS_PER_SEC + nanos as u64
+}
+
+#[inline(always)]
+fn secs_to_nanos(secs: u32) -> u64 {
+    secs as u64 * NANOS_PER_SEC
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{FORGE_BUFFER_SIZE, forge_out_buffer};
+    use crate::reading::read_u32;
+
+    const T0: u64 = 1_700_000_000;
+
+    fn pair() -> (Session<'static>, Session<'static>) {
+        let initiator = Session::new(
+            SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Initiator),
+            T0,
+            0,
+        );
+        let acceptor = Session::new(
+            SessionConfig::new("FIX.4.4", "BROKER", "CLIENT", SessionRole::Acceptor),
+            T0,
+            0,
+        );
+        (initiator, acceptor)
+    }
+
+    /// Deliver everything `from` has queued to `to`; returns the MsgTypes sent.
+    fn pump(
+        from: &mut Session<'_>,
+        to: &mut Session<'_>,
+        secs: u64,
+    ) -> Vec<(u8, Inbound)> {
+        let mut sent = Vec::new();
+        let mut buffer: [u8; FORGE_BUFFER_SIZE] = forge_out_buffer("FIX.4.4");
+        while let Some(len) = from.poll_transmit(&mut buffer, secs, 0) {
+            let inbound = to.handle_message(&buffer[..len], secs, 0);
+            sent.push((buffer[FORGE_WRITE_START], inbound));
+        }
+        sent
+    }
+
+    fn events(session: &mut Session<'_>) -> Vec<SessionEvent> {
+        core::iter::from_fn(|| session.poll_event()).collect()
+    }
+
+    fn logged_on_pair() -> (Session<'static>, Session<'static>) {
+        let (mut initiator, mut acceptor) = pair();
+        assert_eq!(pump(&mut initiator, &mut acceptor, T0), [(b'A', Inbound::Admin)]);
+        assert_eq!(pump(&mut acceptor, &mut initiator, T0), [(b'A', Inbound::Admin)]);
+        (initiator, acceptor)
+    }
+
+    #[test]
+    fn test_logon_handshake() {
+        let (mut initiator, mut acceptor) = logged_on_pair();
+        let logged_on = [SessionEvent::LoggedOn {
+            heartbeat_interval: 30,
+        }];
+        assert_eq!(events(&mut initiator), logged_on);
+        assert_eq!(events(&mut acceptor), logged_on);
+        assert_eq!(initiator.state(), SessionState::Active);
+        assert_eq!(acceptor.state(), SessionState::Active);
+        assert_eq!((initiator.next_sender_seq(), initiator.next_target_seq()), (2, 2));
+        assert_eq!((acceptor.next_sender_seq(), acceptor.next_target_seq()), (2, 2));
+    }
+
+    #[test]
+    fn test_acceptor_adopts_heartbeat_interval() {
+        let (mut initiator, mut acceptor) = pair();
+        initiator.config.heartbeat_interval = 5;
+        initiator.heartbeat_interval = 5;
+        pump(&mut initiator, &mut acceptor, T0);
+        assert_eq!(acceptor.heartbeat_interval(), 5);
+
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = acceptor.poll_transmit(&mut buffer, T0, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        assert!(fields.contains(&(108, &b"5"[..])));
+    }
+
+    #[test]
+    fn test_heartbeat_and_test_request() {
+        let (mut initiator, mut acceptor) = logged_on_pair();
+
+        // Idle for one interval: both sides heartbeat
+        initiator.handle_timeout(T0 + 30, 0);
+        assert_eq!(pump(&mut initiator, &mut acceptor, T0 + 30), [(b'0', Inbound::Admin)]);
+
+        // Acceptor goes silent; after 1.2 intervals the initiator probes
+        initiator.handle_timeout(T0 + 65, 0);
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = initiator.poll_transmit(&mut buffer, T0 + 65, 0).unwrap();
+        let probe = &buffer[..len];
+        assert_eq!(MessageHeader::parse(probe).msg_type, b"1");
+        assert_eq!(acceptor.handle_message(probe, T0 + 65, 0), Inbound::Admin);
+
+        // The acceptor echoes TestReqID(112) in its Heartbeat
+        let len = acceptor.poll_transmit(&mut buffer, T0 + 65, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        assert_eq!(MessageHeader::parse(&buffer[..len]).msg_type, b"0");
+        assert!(fields.contains(&(112, &b"1"[..])));
+        assert_eq!(initiator.handle_message(&buffer[..len], T0 + 66, 0), Inbound::Admin);
+        assert_eq!(initiator.state(), SessionState::Active);
+
+        // Silence again: TestRequest, then disconnect one interval later
+        initiator.handle_timeout(T0 + 102, 0);
+        let len = initiator.poll_transmit(&mut buffer, T0 + 102, 0).unwrap();
+        assert!(TagValueIter::new(&buffer[..len]).any(|(tag, v)| tag == 112 && read_u32(v) == 2));
+        initiator.handle_timeout(T0 + 131, 0);
+        assert_eq!(initiator.state(), SessionState::Active);
+        initiator.handle_timeout(T0 + 132, 0);
+        assert_eq!(
+            events(&mut initiator).last(),
+            Some(&SessionEvent::Disconnected(DisconnectReason::HeartbeatTimeout))
+        );
+    }
+
+    #[test]
+    fn test_next_timeout() {
+        let (mut initiator, _) = logged_on_pair();
+        assert_eq!(initiator.next_timeout(), Some((T0 + 30, 0)));
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        initiator.begin_message(&mut buffer, b"D", T0 + 10, 500);
+        // Inbound silence deadline (1.2 intervals) is now the earliest
+        assert_eq!(initiator.next_timeout(), Some((T0 + 36, 0)));
+    }
+
+    #[test]
+    fn test_logout_exchange() {
+        let (mut initiator, mut acceptor) = logged_on_pair();
+        events(&mut initiator);
+        events(&mut acceptor);
+
+        initiator.logout(T0 + 1, 0);
+        assert_eq!(initiator.state(), SessionState::LogoutSent);
+        assert_eq!(pump(&mut initiator, &mut acceptor, T0 + 1), [(b'5', Inbound::Admin)]);
+        assert_eq!(pump(&mut acceptor, &mut initiator, T0 + 1), [(b'5', Inbound::Admin)]);
+        let done = [SessionEvent::Disconnected(DisconnectReason::LogoutComplete)];
+        assert_eq!(events(&mut initiator), done);
+        assert_eq!(events(&mut acceptor), done);
+        assert_eq!(acceptor.handle_message(b"35=0\x01", T0 + 2, 0), Inbound::Ignored);
+    }
+
+    #[test]
+    fn test_logout_timeout() {
+        let (mut initiator, _) = logged_on_pair();
+        events(&mut initiator);
+        initiator.logout(T0 + 1, 0);
+        initiator.handle_timeout(T0 + 3, 0);
+        assert_eq!(
+            events(&mut initiator),
+            [SessionEvent::Disconnected(DisconnectReason::LogoutTimeout)]
+        );
+    }
+
+    #[test]
+    fn test_protocol_violations() {
+        // Acceptor: first message must be a Logon
+        let (_, mut acceptor) = pair();
+        let order = b"8=FIX.4.4\x019=40\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=1\x0111=A\x01";
+        assert_eq!(acceptor.handle_message(order, T0, 0), Inbound::Ignored);
+        assert_eq!(
+            events(&mut acceptor),
+            [SessionEvent::Disconnected(DisconnectReason::InvalidLogon)]
+        );
+
+        // Wrong CompIDs on the Logon
+        let (_, mut acceptor) = pair();
+        let logon = b"8=FIX.4.4\x019=40\x0135=A\x0149=OTHER\x0156=BROKER\x0134=1\x01108=30\x01";
+        acceptor.handle_message(logon, T0, 0);
+        assert_eq!(
+            events(&mut acceptor),
+            [SessionEvent::Disconnected(DisconnectReason::CompIdMismatch)]
+        );
+
+        // MsgSeqNum too low: Logout, then disconnect once it is sent
+        let (mut initiator, mut acceptor) = logged_on_pair();
+        events(&mut acceptor);
+        let stale = b"8=FIX.4.4\x019=40\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=1\x0111=A\x01";
+        assert_eq!(acceptor.handle_message(stale, T0 + 1, 0), Inbound::Ignored);
+        let possdup = b"8=FIX.4.4\x019=40\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=1\x0143=Y\x01";
+        assert_eq!(acceptor.handle_message(possdup, T0 + 1, 0), Inbound::Ignored);
+        assert_eq!(pump(&mut acceptor, &mut initiator, T0 + 1), [(b'5', Inbound::Admin)]);
+        assert_eq!(
+            events(&mut acceptor),
+            [SessionEvent::Disconnected(DisconnectReason::SequenceTooLow)]
+        );
+
+        // In-sequence application messages are handed to the caller
+        let (_, mut acceptor) = logged_on_pair();
+        let order = b"8=FIX.4.4\x019=40\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=2\x0111=A\x01";
+        assert_eq!(acceptor.handle_message(order, T0 + 1, 0), Inbound::Application);
+        assert_eq!(acceptor.next_target_seq(), 3);
+    }
+
+    #[test]
+    fn test_gap_recovered_with_gap_fill() {
+        let (mut initiator, mut acceptor) = logged_on_pair();
+        let mut buffer = forge_out_buffer("FIX.4.4");
+
+        // Orders 2 and 3 are lost; 4 arrives out of sequence and is dropped
+        let mut len = 0;
+        for _ in 0..3 {
+            let pos = initiator.begin_message(&mut buffer, b"D", T0 + 1, 0);
+            len = finalize_message(&mut buffer, pos);
+        }
+        assert_eq!(acceptor.handle_message(&buffer[..len], T0 + 1, 0), Inbound::Ignored);
+        assert_eq!(acceptor.sequences().resend_pending(), Some((2, 4)));
+
+        // ResendRequest(2..0); a second gap message asks for nothing new
+        assert_eq!(acceptor.handle_message(&buffer[..len], T0 + 1, 0), Inbound::Ignored);
+        let len = acceptor.poll_transmit(&mut buffer, T0 + 1, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        assert_eq!(MessageHeader::parse(&buffer[..len]).msg_type, b"2");
+        assert!(fields.contains(&(7, &b"2"[..])) && fields.contains(&(16, &b"0"[..])));
+        assert_eq!(acceptor.poll_transmit(&mut buffer, T0 + 1, 0), None);
+
+        // The initiator keeps no copies, so it gap-fills 2..=4 as MsgSeqNum 2
+        assert_eq!(initiator.handle_message(&buffer[..len], T0 + 1, 0), Inbound::Admin);
+        let len = initiator.poll_transmit(&mut buffer, T0 + 1, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        assert_eq!(MessageHeader::parse(&buffer[..len]).msg_seq_num, 2);
+        for field in [(35, &b"4"[..]), (43, b"Y"), (123, b"Y"), (36, b"5")] {
+            assert!(fields.contains(&field), "{field:?}");
+        }
+        assert!(fields.iter().any(|&(tag, _)| tag == 122));
+        assert_eq!(initiator.next_sender_seq(), 5);
+
+        assert_eq!(acceptor.handle_message(&buffer[..len], T0 + 1, 0), Inbound::Admin);
+        assert_eq!(acceptor.next_target_seq(), 5);
+        assert_eq!(acceptor.sequences().resend_pending(), None);
+        assert_eq!(acceptor.state(), SessionState::Active);
+    }
+
+    #[test]
+    fn test_resend_request_up_to_max_seq_num() {
+        let (_, mut acceptor) = logged_on_pair();
+        let msg = b"8=FIX.4.4\x019=0\x0135=2\x0149=CLIENT\x0156=BROKER\x0134=2\x017=1\x0116=4294967295\x01";
+        assert_eq!(acceptor.handle_message(msg, T0 + 1, 0), Inbound::Admin);
+
+        // Gap-filled up to the next outbound MsgSeqNum, as for an open-ended request
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = acceptor.poll_transmit(&mut buffer, T0 + 1, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        for field in [(35, &b"4"[..]), (34, b"1"), (36, b"2")] {
+            assert!(fields.contains(&field), "{field:?}");
+        }
+        assert_eq!(acceptor.next_sender_seq(), 2);
+    }
+
+    #[test]
+    fn test_test_request_burst_is_answered_by_one_heartbeat() {
+        let (_, mut acceptor) = logged_on_pair();
+        for seq in 2..=11 {
+            let msg = format!("8=FIX.4.4\x019=0\x0135=1\x0149=CLIENT\x0156=BROKER\x0134={seq}\x01112=T{seq}\x01");
+            assert_eq!(acceptor.handle_message(msg.as_bytes(), T0 + 1, 0), Inbound::Admin);
+        }
+        assert_eq!(acceptor.next_target_seq(), 12);
+
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = acceptor.poll_transmit(&mut buffer, T0 + 1, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        assert!(fields.contains(&(35, &b"0"[..])) && fields.contains(&(112, &b"T11"[..])));
+        assert_eq!(acceptor.poll_transmit(&mut buffer, T0 + 1, 0), None);
+    }
+
+    #[test]
+    fn test_test_request_with_long_id_is_rejected() {
+        let (_, mut acceptor) = logged_on_pair();
+        let longest = "I".repeat(MAX_TEST_REQ_ID_LEN);
+        let msg = format!("8=FIX.4.4\x019=0\x0135=1\x0149=CLIENT\x0156=BROKER\x0134=2\x01112={longest}\x01");
+        assert_eq!(acceptor.handle_message(msg.as_bytes(), T0 + 1, 0), Inbound::Admin);
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = acceptor.poll_transmit(&mut buffer, T0 + 1, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        assert!(fields.contains(&(35, &b"0"[..])) && fields.contains(&(112, longest.as_bytes())));
+
+        // One byte longer: rejected, never echoed truncated
+        let msg = format!("8=FIX.4.4\x019=0\x0135=1\x0149=CLIENT\x0156=BROKER\x0134=3\x01112={longest}J\x01");
+        assert_eq!(acceptor.handle_message(msg.as_bytes(), T0 + 1, 0), Inbound::Admin);
+        let len = acceptor.poll_transmit(&mut buffer, T0 + 1, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        for field in [(35, &b"3"[..]), (45, b"3"), (371, b"112"), (372, b"1"), (373, b"5")] {
+            assert!(fields.contains(&field), "{field:?}");
+        }
+        assert_eq!(acceptor.poll_transmit(&mut buffer, T0 + 1, 0), None);
+        assert_eq!(acceptor.next_target_seq(), 4);
+    }
+
+    #[test]
+    fn test_outbox_backpressure() {
+        let (_, mut acceptor) = logged_on_pair();
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        // Advance the acceptor's outbound sequence so there is something to gap-fill
+        for _ in 0..5 {
+            acceptor.begin_message(&mut buffer, b"D", T0, 0);
+        }
+        let resend = |seq: u32| {
+            format!("8=FIX.4.4\x019=0\x0135=2\x0149=CLIENT\x0156=BROKER\x0134={seq}\x017=2\x0116=0\x01")
+        };
+        let mut seq = 2;
+        while acceptor.handle_message(resend(seq).as_bytes(), T0 + 1, 0) == Inbound::Admin {
+            seq += 1;
+        }
+        // The refused message was not consumed
+        assert_eq!(acceptor.next_target_seq(), seq);
+        assert_eq!(
+            acceptor.handle_message(resend(seq).as_bytes(), T0 + 1, 0),
+            Inbound::OutboxFull
+        );
+
+        // Timers and logout still have room
+        acceptor.handle_timeout(T0 + 60, 0);
+        acceptor.logout(T0 + 60, 0);
+        let mut sent = Vec::new();
+        while let Some(len) = acceptor.poll_transmit(&mut buffer, T0 + 60, 0) {
+            sent.push(MessageHeader::parse(&buffer[..len]).msg_type[0]);
+        }
+        assert_eq!(sent.iter().filter(|&&t| t == b'4').count(), seq as usize - 2);
+        assert!(sent.ends_with(b"5"), "{sent:?}");
+        assert!(sent.contains(&b'1'), "{sent:?}");
+
+        // Drained: the same message is processed now
+        assert_eq!(acceptor.handle_message(resend(seq).as_bytes(), T0 + 60, 0), Inbound::Admin);
+        assert_eq!(acceptor.next_target_seq(), seq + 1);
+    }
+
+    #[test]
+    fn test_sequence_reset_backwards_is_rejected() {
+        let (_, mut acceptor) = logged_on_pair();
+        let reset = b"8=FIX.4.4\x019=40\x0135=4\x0149=CLIENT\x0156=BROKER\x0134=2\x0136=1\x01";
+        assert_eq!(acceptor.handle_message(reset, T0 + 1, 0), Inbound::Admin);
+        assert_eq!(acceptor.next_target_seq(), 2);
+
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = acceptor.poll_transmit(&mut buffer, T0 + 1, 0).unwrap();
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buffer[..len]).collect();
+        for field in [(35, &b"3"[..]), (34, b"2"), (45, b"2"), (371, b"36"), (372, b"4"), (373, b"5")] {
+            assert!(fields.contains(&field), "{field:?}");
+        }
+        assert_eq!(acceptor.next_sender_seq(), 3);
+
+        // Reset mode forward ignores MsgSeqNum
+        let reset = b"8=FIX.4.4\x019=40\x0135=4\x0149=CLIENT\x0156=BROKER\x0134=9\x0136=20\x01";
+        assert_eq!(acceptor.handle_message(reset, T0 + 1, 0), Inbound::Admin);
+        assert_eq!(acceptor.next_target_seq(), 20);
+    }
+
+    #[test]
+    fn test_reset_seq_num_on_logon() {
+        let mut config = SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Initiator);
+        config.reset_seq_num_on_logon = true;
+        let mut initiator = Session::with_sequences(config, SequenceManager::with_numbers(50, 70), T0, 0);
+        let acceptor_config = SessionConfig::new("FIX.4.4", "BROKER", "CLIENT", SessionRole::Acceptor);
+        let mut acceptor = Session::with_sequences(acceptor_config, SequenceManager::with_numbers(70, 50), T0, 0);
+
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = initiator.poll_transmit(&mut buffer, T0, 0).unwrap();
+        assert!(TagValueIter::new(&buffer[..len]).any(|field| field == (141, &b"Y"[..])));
+        assert_eq!(acceptor.handle_message(&buffer[..len], T0, 0), Inbound::Admin);
+
+        // The acceptor answers with 141=Y as MsgSeqNum 1
+        let len = acceptor.poll_transmit(&mut buffer, T0, 0).unwrap();
+        assert_eq!(MessageHeader::parse(&buffer[..len]).msg_seq_num, 1);
+        assert!(TagValueIter::new(&buffer[..len]).any(|field| field == (141, &b"Y"[..])));
+        assert_eq!(initiator.handle_message(&buffer[..len], T0, 0), Inbound::Admin);
+        for session in [&initiator, &acceptor] {
+            assert_eq!(session.state(), SessionState::Active);
+            assert_eq!(*session.sequences(), SequenceManager::with_numbers(2, 2));
+        }
+    }
+}
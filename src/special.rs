@@ -11,13 +11,19 @@
 //! - Publication order: we write the cached bytes first, then publish the day with a
 //!   `Release` store. Readers load the day with `Acquire` to ensure they observe
 //!   corresponding digits (or recompute if mismatch).
+//!
+//! Feature gating:
+//! - The pure formatters (`format_timestamp`, `format_logging_timestamp`,
+//!   `encode_base36_fixed13`) are always available, including `no_std`.
+//! - Functions reading the system clock or taking a `libc::timespec` need `clock`.
+//! - ClOrdID generation needs `std` (process id and `OnceLock`).
 
 use crate::DIGIT_PAIRS;
 use core::ptr;
-use std::sync::{
-    OnceLock,
-    atomic::{AtomicU64, Ordering},
-};
+#[cfg(any(feature = "std", target_has_atomic = "64"))]
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
 
 // -----------------------------------------------------------------------------------------
 // Date cache (days since Unix epoch -> pre-rendered "YYYYMMDD" ASCII)
@@ -25,14 +31,21 @@ use std::sync::{
 // CACHED_DAY acts as the version/publish flag. When a new day is detected, we recompute
 // and store the 8 ASCII digits, then store the day with `Release`. Readers load the day
 // with `Acquire` before copying the digits (or recomputing if mismatch).
+// Targets without 64-bit atomics render the digits on every call instead.
 // -----------------------------------------------------------------------------------------
+#[cfg(target_has_atomic = "64")]
 static CACHED_DAY: AtomicU64 = AtomicU64::new(u64::MAX); // Sentinel invalid day
+#[cfg(target_has_atomic = "64")]
 static CACHED_YYYYMMDD: AtomicU64 = AtomicU64::new(0); // 8 ASCII bytes (native endian)
 
 // ClOrdID generation state
+#[cfg(feature = "std")]
 const CNT_BITS: u64 = 32;
+#[cfg(feature = "std")]
 const CNT_MASK: u64 = (1u64 << CNT_BITS) - 1;
+#[cfg(feature = "std")]
 static COUNTER: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "std")]
 static PROCESS_TAG: OnceLock<u32> = OnceLock::new();
 
 // Constants
@@ -49,12 +62,13 @@ static LOG_TS_SKELETON: [u8; 31] = *b"0000-00-00 00:00:00.000.000.000";
 #[cfg(test)]
 pub(crate) fn __reset_date_cache_for_test() {
     // Invalidate cached day so next call recomputes
+    #[cfg(target_has_atomic = "64")]
     CACHED_DAY.store(u64::MAX, Ordering::Release);
 }
 
-/// Recompute and publish cached date digits for the given day number (days since Unix epoch).
+/// Render the "YYYYMMDD" digits for the given day number (days since Unix epoch).
 #[inline(always)]
-fn publish_date_for_day(day_number: u64) {
+fn render_date(day_number: u64) -> [u8; 8] {
     // Convert day_number back to civil date (UTC) using the algorithm from
     // Howard Hinnant's date algorithms (same as original implementation).
     let days = day_number as i64;
@@ -101,21 +115,30 @@ fn publish_date_for_day(day_number: u64) {
         );
     }
 
-    // Store digits (Relaxed) then publish the day (Release)
-    let packed = u64::from_ne_bytes(buf);
-    CACHED_YYYYMMDD.store(packed, Ordering::Relaxed);
-    CACHED_DAY.store(day_number, Ordering::Release);
+    buf
 }
 
-/// Ensure the date cache is initialized / up to date for the provided day number.
+/// "YYYYMMDD" digits for the given day number, from the date cache when current.
+#[cfg(target_has_atomic = "64")]
 #[inline(always)]
-fn ensure_date_cache(day_number: u64) {
+fn date_digits(day_number: u64) -> [u8; 8] {
     // Fast path: already current
     if CACHED_DAY.load(Ordering::Acquire) == day_number {
-        return;
+        return CACHED_YYYYMMDD.load(Ordering::Relaxed).to_ne_bytes();
     }
-    // Slow path: recompute (possible benign races on day boundary; last writer wins)
-    publish_date_for_day(day_number);
+    // Slow path: recompute (possible benign races on day boundary; last writer wins).
+    // Store digits (Relaxed) then publish the day (Release).
+    let date = render_date(day_number);
+    CACHED_YYYYMMDD.store(u64::from_ne_bytes(date), Ordering::Relaxed);
+    CACHED_DAY.store(day_number, Ordering::Release);
+    date
+}
+
+/// "YYYYMMDD" digits for the given day number (uncached: no 64-bit atomics).
+#[cfg(not(target_has_atomic = "64"))]
+#[inline(always)]
+fn date_digits(day_number: u64) -> [u8; 8] {
+    render_date(day_number)
 }
 
 /// Write a FIX-format UTC timestamp (YYYYMMDD-HH:MM:SS.mmm) with tag prefix.
@@ -124,6 +147,8 @@ fn ensure_date_cache(day_number: u64) {
 /// timestamp value, and SOH delimiter. The timestamp is always 21 characters in the
 /// format YYYYMMDD-HH:MM:SS.mmm.
 ///
+/// Requires the `clock` feature.
+///
 /// # Performance
 ///
 /// - Uses `libc::clock_gettime` (CLOCK_REALTIME) for speed.
@@ -136,81 +161,27 @@ fn ensure_date_cache(day_number: u64) {
 /// let written = write_tag_and_current_timestamp(&mut buf, 0, b"52=");
 /// assert!(written > 0);
 /// ```
+#[cfg(feature = "clock")]
 #[inline(always)]
 pub fn write_tag_and_current_timestamp(
     bytes: &mut [u8],
     offset: usize,
     tag_and_eq: &[u8],
 ) -> usize {
-    debug_assert!(bytes.len() >= offset + tag_and_eq.len() + 22);
-
-    // Copy tag= prefix
-    unsafe {
-        ptr::copy_nonoverlapping(
-            tag_and_eq.as_ptr(),
-            bytes.as_mut_ptr().add(offset),
-            tag_and_eq.len(),
-        );
-    }
+    let ts = clock_realtime();
+    format_timestamp_from_timespec(bytes, offset, tag_and_eq, &ts)
+}
 
-    // Get current realtime
-    let (secs_u64, millis) = {
-        let mut ts = libc::timespec {
-            tv_sec: 0,
-            tv_nsec: 0,
-        };
-        unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut ts) };
-        let secs = ts.tv_sec as i64;
-        let secs_u64 = secs as u64;
-        let millis = (ts.tv_nsec / 1_000_000) as u32;
-        (secs_u64, millis)
+/// Read CLOCK_REALTIME.
+#[cfg(feature = "clock")]
+#[inline(always)]
+fn clock_realtime() -> libc::timespec {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
     };
-
-    let day_number = secs_u64 / SECS_PER_DAY;
-    let sec_of_day = (secs_u64 - day_number * SECS_PER_DAY) as u32;
-
-    // Time components
-    let hour = (sec_of_day / 3600) as u8;
-    let minute = ((sec_of_day % 3600) / 60) as u8;
-    let second = (sec_of_day % 60) as u8;
-
-    // Ensure date cache up-to-date
-    ensure_date_cache(day_number);
-
-    // Pointer to where date/time digits start (right after tag=)
-    let p = unsafe { bytes.as_mut_ptr().add(offset + tag_and_eq.len()) };
-
-    unsafe {
-        // Copy cached YYYYMMDD
-        let packed = CACHED_YYYYMMDD.load(Ordering::Relaxed);
-        ptr::copy_nonoverlapping((&packed as *const u64) as *const u8, p, 8);
-
-        // '-'
-        *p.add(8) = b'-';
-
-        // Hour
-        ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(hour as usize * 2), p.add(9), 2);
-        *p.add(11) = b':';
-
-        // Minute
-        ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(minute as usize * 2), p.add(12), 2);
-        *p.add(14) = b':';
-
-        // Second
-        ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(second as usize * 2), p.add(15), 2);
-        *p.add(17) = b'.';
-
-        // Milliseconds: first two digits via pair, last digit single
-        let millis_pair = (millis / 10) as usize;
-        let millis_last = (millis % 10) as u8;
-        ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(millis_pair * 2), p.add(18), 2);
-        *p.add(20) = b'0' + millis_last;
-
-        // SOH
-        *p.add(21) = 0x01;
-    }
-
-    tag_and_eq.len() + 22
+    unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut ts) };
+    ts
 }
 
 /// Write a FIX-format UTC timestamp using a pre-fetched `libc::timespec`.
@@ -219,16 +190,47 @@ pub fn write_tag_and_current_timestamp(
 /// reuse it for multiple timestamp tags (52=, 60=, etc.) without multiple
 /// syscalls.
 ///
+/// Requires the `clock` feature; see [`format_timestamp`] for the clock-free form.
+///
 /// Buffer requirements:
 /// - `bytes[offset..]` must have capacity for `tag_and_eq.len() + 22` bytes.
 ///
 /// Returns the total number of bytes written: `tag_and_eq.len() + 22`.
+#[cfg(feature = "clock")]
 #[inline(always)]
 pub fn format_timestamp_from_timespec(
     bytes: &mut [u8],
     offset: usize,
     tag_and_eq: &[u8],
     ts: &libc::timespec,
+) -> usize {
+    format_timestamp(bytes, offset, tag_and_eq, ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+/// Write a FIX-format UTC timestamp from seconds and nanoseconds since the Unix epoch.
+///
+/// Clock-free core of [`format_timestamp_from_timespec`], available without
+/// `std` or `libc`. Sub-millisecond precision is truncated.
+///
+/// Buffer requirements:
+/// - `bytes[offset..]` must have capacity for `tag_and_eq.len() + 22` bytes.
+///
+/// Returns the total number of bytes written: `tag_and_eq.len() + 22`.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::format_timestamp;
+/// let mut buf = [0u8; 32];
+/// let written = format_timestamp(&mut buf, 0, b"52=", 86_400 + 3_723, 456_789_000);
+/// assert_eq!(&buf[..written], b"52=19700102-01:02:03.456\x01");
+/// ```
+#[inline(always)]
+pub fn format_timestamp(
+    bytes: &mut [u8],
+    offset: usize,
+    tag_and_eq: &[u8],
+    secs: u64,
+    nanos: u32,
 ) -> usize {
     debug_assert!(bytes.len() >= offset + tag_and_eq.len() + 22);
 
@@ -241,18 +243,17 @@ pub fn format_timestamp_from_timespec(
         );
     }
 
-    let secs_u64 = ts.tv_sec as u64;
-    let millis = (ts.tv_nsec / 1_000_000) as u32;
+    let millis = nanos / 1_000_000;
 
-    let day_number = secs_u64 / SECS_PER_DAY;
-    let sec_of_day = (secs_u64 - day_number * SECS_PER_DAY) as u32;
+    let day_number = secs / SECS_PER_DAY;
+    let sec_of_day = (secs - day_number * SECS_PER_DAY) as u32;
 
     // Time components
     let hour = (sec_of_day / 3600) as u8;
     let minute = ((sec_of_day % 3600) / 60) as u8;
     let second = (sec_of_day % 60) as u8;
 
-    ensure_date_cache(day_number);
+    let date = date_digits(day_number);
 
     let p = unsafe { bytes.as_mut_ptr().add(offset + tag_and_eq.len()) };
 
@@ -263,8 +264,7 @@ pub fn format_timestamp_from_timespec(
         ptr::copy_nonoverlapping(FIX_TS_SKELETON.as_ptr(), p, 21);
 
         // Date (cached) overwrite 8 digits
-        let packed = CACHED_YYYYMMDD.load(Ordering::Relaxed);
-        ptr::copy_nonoverlapping((&packed as *const u64) as *const u8, p, 8);
+        ptr::copy_nonoverlapping(date.as_ptr(), p, 8);
 
         // Hour
         ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(hour as usize * 2), p.add(9), 2);
@@ -287,6 +287,7 @@ pub fn format_timestamp_from_timespec(
 }
 
 /// Simple 64-bit PRNG for process tag generation
+#[cfg(feature = "std")]
 #[inline(always)]
 fn splitmix64(mut x: u64) -> u64 {
     x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
@@ -297,6 +298,7 @@ fn splitmix64(mut x: u64) -> u64 {
 }
 
 /// Generate a unique process tag from PID and memory addresses
+#[cfg(feature = "std")]
 #[inline(always)]
 fn process_tag() -> u32 {
     *PROCESS_TAG.get_or_init(|| {
@@ -327,6 +329,7 @@ fn process_tag() -> u32 {
 }
 
 /// Generate next unique 64-bit ID (32-bit process tag + 32-bit counter)
+#[cfg(feature = "std")]
 #[inline(always)]
 fn next_id_u64() -> u64 {
     let tag = process_tag() as u64;
@@ -380,6 +383,8 @@ pub fn encode_base36_fixed13(dst: &mut [u8], offset: usize, mut n: u64) -> usize
 /// The ClOrdID combines a process-unique tag (derived from PID and memory layout)
 /// with an atomic counter to ensure uniqueness within and across processes.
 ///
+/// Requires the `std` feature.
+///
 /// # Example
 /// ```
 /// # use fix44_forge_helpers::write_tag_and_ClOrdID;
@@ -387,6 +392,7 @@ pub fn encode_base36_fixed13(dst: &mut [u8], offset: usize, mut n: u64) -> usize
 /// let written = write_tag_and_ClOrdID(&mut buf, 0, b"11=");
 /// assert_eq!(written, 17); // 3 (tag) + 13 (ID) + 1 (SOH)
 /// ```
+#[cfg(feature = "std")]
 #[inline(always)]
 #[allow(non_snake_case)]
 pub fn write_tag_and_ClOrdID(bytes: &mut [u8], offset: usize, tag_and_eq: &[u8]) -> usize {
@@ -413,33 +419,50 @@ pub fn write_tag_and_ClOrdID(bytes: &mut [u8], offset: usize, tag_and_eq: &[u8])
 /// Uses cached date digits (YYYYMMDD) and inserts '-' separators.
 /// Nanosecond subsecond partitioned into millisecond / microsecond / nanosecond groups.
 ///
+/// Requires the `clock` feature; see [`format_logging_timestamp`] for the clock-free form.
+///
 /// Returns 31 on success (debug asserts sufficient capacity).
+#[cfg(feature = "clock")]
 #[inline(always)]
 pub fn format_logging_timestamp_from_timespec(
     bytes: &mut [u8],
     offset: usize,
     ts: &libc::timespec,
+) -> usize {
+    format_logging_timestamp(bytes, offset, ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+/// Format a high-resolution logging timestamp from seconds and nanoseconds since the Unix epoch.
+///
+/// Clock-free core of [`format_logging_timestamp_from_timespec`], available
+/// without `std` or `libc`. Writes the same 31-byte
+/// "YYYY-MM-DD HH:MM:SS.mmm.uuu.nnn" form.
+///
+/// Returns 31 on success (debug asserts sufficient capacity).
+#[inline(always)]
+pub fn format_logging_timestamp(
+    bytes: &mut [u8],
+    offset: usize,
+    secs: u64,
+    nanos: u32,
 ) -> usize {
     debug_assert!(bytes.len() >= offset + 31);
 
     // Copy static template with separators, then overwrite digit positions.
 
-    let secs_u64 = ts.tv_sec as u64;
-    let ns = ts.tv_nsec as u32;
-
-    let day_number = secs_u64 / SECS_PER_DAY;
-    let sec_of_day = (secs_u64 - day_number * SECS_PER_DAY) as u32;
+    let day_number = secs / SECS_PER_DAY;
+    let sec_of_day = (secs - day_number * SECS_PER_DAY) as u32;
 
     let hour = (sec_of_day / 3600) as u8;
     let minute = ((sec_of_day % 3600) / 60) as u8;
     let second = (sec_of_day % 60) as u8;
 
     // Subsecond groups (always 0..999)
-    let millis = ns / 1_000_000;
-    let micros = (ns / 1_000) % 1000;
-    let nanos = ns % 1000;
+    let millis = nanos / 1_000_000;
+    let micros = (nanos / 1_000) % 1000;
+    let nanos = nanos % 1000;
 
-    ensure_date_cache(day_number);
+    let date = date_digits(day_number); // [Y,Y,Y,Y,M,M,D,D]
 
     unsafe {
         let p = bytes.as_mut_ptr().add(offset);
@@ -448,8 +471,6 @@ pub fn format_logging_timestamp_from_timespec(
         ptr::copy_nonoverlapping(LOG_TS_SKELETON.as_ptr(), p, 31);
 
         // Cached date digits YYYYMMDD -> expand into YYYY-MM-DD
-        let packed = CACHED_YYYYMMDD.load(Ordering::Relaxed);
-        let date = packed.to_ne_bytes(); // [Y,Y,Y,Y,M,M,D,D]
 
         // Year
         ptr::copy_nonoverlapping(date.as_ptr().add(0), p.add(0), 4);
@@ -486,16 +507,11 @@ pub fn format_logging_timestamp_from_timespec(
 
 /// Convenience wrapper that fetches current time and formats logging timestamp.
 ///
-/// Returns 31 bytes written.
+/// Requires the `clock` feature. Returns 31 bytes written.
+#[cfg(feature = "clock")]
 #[inline(always)]
 pub fn write_current_logging_timestamp(bytes: &mut [u8], offset: usize) -> usize {
-    let mut ts = libc::timespec {
-        tv_sec: 0,
-        tv_nsec: 0,
-    };
-    unsafe {
-        libc::clock_gettime(libc::CLOCK_REALTIME, &mut ts);
-    }
+    let ts = clock_realtime();
     format_logging_timestamp_from_timespec(bytes, offset, &ts)
 }
 
@@ -529,6 +545,7 @@ mod tests {
         assert_eq!(digit36(35), b'Z');
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_process_tag_consistency() {
         let tag1 = process_tag();
@@ -537,6 +554,7 @@ mod tests {
         assert_ne!(tag1, 0);
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_next_id_u64_uniqueness() {
         let id1 = next_id_u64();
@@ -544,7 +562,9 @@ mod tests {
         assert_ne!(id1, id2);
     }
 
+    #[cfg(feature = "std")]
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_write_tag_and_clordid() {
         let mut buf = [0u8; 30];
         let written = write_tag_and_ClOrdID(&mut buf, 0, b"11=");
@@ -566,7 +586,9 @@ mod tests {
         // (rollover test moved to module scope below)
     }
 
+    #[cfg(feature = "clock")]
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_write_tag_and_current_timestamp_format() {
         let mut buf = [0u8; 50];
         let written = write_tag_and_current_timestamp(&mut buf, 0, b"52=");
@@ -593,6 +615,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "clock")]
     #[test]
     fn test_format_logging_timestamp_from_timespec_epoch() {
         __reset_date_cache_for_test();
@@ -607,6 +630,7 @@ mod tests {
         assert_eq!(s, "1970-01-01 00:00:00.987.654.321");
     }
 
+    #[cfg(feature = "clock")]
     #[test]
     fn test_write_current_logging_timestamp_basic() {
         let mut buf = [0u8; 64];
@@ -622,7 +646,9 @@ mod tests {
         assert_eq!(buf[27], b'.');
     }
 
+    #[cfg(feature = "clock")]
     #[test]
+    #[allow(clippy::identity_op)]
     fn test_timestamp_date_cache_rollover() {
         __reset_date_cache_for_test();
         let day_n: i64 = 10;
@@ -656,6 +682,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "clock")]
     #[test]
     fn test_format_timestamp_from_timespec_epoch() {
         __reset_date_cache_for_test();
@@ -671,4 +698,17 @@ mod tests {
         assert_eq!(ts_bytes, b"19700101-00:00:00.123");
         assert_eq!(buf[written - 1], 0x01);
     }
+
+    #[test]
+    fn test_format_timestamp_pure() {
+        __reset_date_cache_for_test();
+        let mut buf = [0u8; 64];
+        // 2000-02-29 23:59:59.999 (leap day)
+        let written = format_timestamp(&mut buf, 0, b"60=", 951_868_799, 999_999_999);
+        assert_eq!(&buf[..written], b"60=20000229-23:59:59.999\x01");
+
+        let written = format_logging_timestamp(&mut buf, 0, 951_868_799, 1_002_003);
+        assert_eq!(written, 31);
+        assert_eq!(&buf[..written], b"2000-02-29 23:59:59.001.002.003");
+    }
 }
@@ -0,0 +1,267 @@
+//! Outbound message store for answering ResendRequest(2).
+//!
+//! A [`MessageStore`] keeps the exact bytes of every sent message, as produced
+//! in the forge buffer, keyed by MsgSeqNum(34), together with the session's
+//! next sender and target sequence numbers. Two implementations ship with the
+//! crate:
+//!
+//! - [`MemoryStore`] (`alloc`) - a `Vec`-backed store that lives as long as the process
+//! - [`FileStore`](crate::FileStore) (`file-store`) - an append-only,
+//!   memory-mapped log plus index that survives restarts
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let mut store = MemoryStore::new();
+//! let mut buffer = forge_out_buffer("FIX.4.4");
+//! let mut pos = FORGE_WRITE_START;
+//! buffer[pos] = b'0';
+//! buffer[pos + 1] = 0x01;
+//! pos += 2;
+//! pos += write_tag_and_u32(&mut buffer, pos, b"34=", 1);
+//! let len = finalize_message(&mut buffer, pos);
+//!
+//! store.store(1, &buffer[..len]).unwrap();
+//! assert_eq!(store.get(1), Some(&buffer[..len]));
+//! assert_eq!(store.next_sender_seq(), 2);
+//! assert_eq!(store.sequences(), SequenceManager::with_numbers(1, 2));
+//! ```
+
+use core::fmt;
+
+use crate::sequence::SequenceManager;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Errors from a [`MessageStore`].
+#[derive(Debug)]
+pub enum StoreError {
+    /// Messages must be stored with strictly increasing MsgSeqNum
+    SequenceNotIncreasing {
+        /// MsgSeqNum being stored
+        seq: u32,
+        /// Highest MsgSeqNum already stored
+        last: u32,
+    },
+    /// Underlying file operation failed
+    #[cfg(feature = "file-store")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            StoreError::SequenceNotIncreasing { seq, last } => write!(
+                f,
+                "MsgSeqNum {seq} not above last stored MsgSeqNum {last}"
+            ),
+            #[cfg(feature = "file-store")]
+            StoreError::Io(e) => write!(f, "message store I/O error: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for StoreError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "file-store")]
+            StoreError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "file-store")]
+impl From<std::io::Error> for StoreError {
+    fn from(e: std::io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+/// Sent messages keyed by MsgSeqNum(34), plus the session sequence numbers.
+pub trait MessageStore {
+    /// Store a complete outbound message sent as MsgSeqNum `seq`.
+    ///
+    /// `seq` must be above every stored MsgSeqNum; the next sender sequence
+    /// number moves past it.
+    fn store(
+        &mut self,
+        seq: u32,
+        msg: &[u8],
+    ) -> Result<(), StoreError>;
+
+    /// The exact bytes stored for `seq`, if any.
+    fn get(
+        &self,
+        seq: u32,
+    ) -> Option<&[u8]>;
+
+    /// MsgSeqNum(34) of the next outbound message.
+    fn next_sender_seq(&self) -> u32;
+
+    /// MsgSeqNum(34) expected on the next inbound message.
+    fn next_target_seq(&self) -> u32;
+
+    /// Record the next outbound MsgSeqNum (e.g. after a message that is not stored).
+    fn set_next_sender_seq(
+        &mut self,
+        seq: u32,
+    ) -> Result<(), StoreError>;
+
+    /// Record the next expected inbound MsgSeqNum.
+    fn set_next_target_seq(
+        &mut self,
+        seq: u32,
+    ) -> Result<(), StoreError>;
+
+    /// Drop every stored message and restart both sequences at 1.
+    fn reset(&mut self) -> Result<(), StoreError>;
+
+    /// Make everything stored so far durable. A no-op for in-memory stores.
+    fn sync(&mut self) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    /// Sequence numbers to resume a [`Session`](crate::Session) with.
+    fn sequences(&self) -> SequenceManager {
+        SequenceManager::with_numbers(self.next_target_seq(), self.next_sender_seq())
+    }
+}
+
+/// In-memory [`MessageStore`]: all messages in one buffer plus a sorted index.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    data: Vec<u8>,
+    /// `(seq, start, end)` into `data`, sorted by seq
+    index: Vec<(u32, usize, usize)>,
+    next_sender_seq: u32,
+    next_target_seq: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl MemoryStore {
+    /// Empty store with both sequences at 1.
+    pub const fn new() -> Self {
+        MemoryStore {
+            data: Vec::new(),
+            index: Vec::new(),
+            next_sender_seq: 1,
+            next_target_seq: 1,
+        }
+    }
+
+    /// Number of stored messages.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// No messages stored.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl MessageStore for MemoryStore {
+    fn store(
+        &mut self,
+        seq: u32,
+        msg: &[u8],
+    ) -> Result<(), StoreError> {
+        if let Some(&(last, _, _)) = self.index.last()
+            && seq <= last
+        {
+            return Err(StoreError::SequenceNotIncreasing { seq, last });
+        }
+        let start = self.data.len();
+        self.data.extend_from_slice(msg);
+        self.index.push((seq, start, self.data.len()));
+        self.next_sender_seq = self.next_sender_seq.max(seq + 1);
+        Ok(())
+    }
+
+    fn get(
+        &self,
+        seq: u32,
+    ) -> Option<&[u8]> {
+        let i = self
+            .index
+            .binary_search_by_key(&seq, |&(s, _, _)| s)
+            .ok()?;
+        let (_, start, end) = self.index[i];
+        Some(&self.data[start..end])
+    }
+
+    fn next_sender_seq(&self) -> u32 {
+        self.next_sender_seq
+    }
+
+    fn next_target_seq(&self) -> u32 {
+        self.next_target_seq
+    }
+
+    fn set_next_sender_seq(
+        &mut self,
+        seq: u32,
+    ) -> Result<(), StoreError> {
+        self.next_sender_seq = seq;
+        Ok(())
+    }
+
+    fn set_next_target_seq(
+        &mut self,
+        seq: u32,
+    ) -> Result<(), StoreError> {
+        self.next_target_seq = seq;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), StoreError> {
+        *self = Self::new();
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_store() {
+        let mut store = MemoryStore::new();
+        assert!(store.is_empty());
+        assert_eq!(store.get(1), None);
+
+        store.store(1, b"first").unwrap();
+        store.store(3, b"third").unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(1), Some(&b"first"[..]));
+        assert_eq!(store.get(2), None);
+        assert_eq!(store.get(3), Some(&b"third"[..]));
+        assert_eq!(store.next_sender_seq(), 4);
+
+        assert!(matches!(
+            store.store(3, b"again"),
+            Err(StoreError::SequenceNotIncreasing { seq: 3, last: 3 })
+        ));
+
+        store.set_next_sender_seq(6).unwrap();
+        store.set_next_target_seq(9).unwrap();
+        assert_eq!(store.sequences(), SequenceManager::with_numbers(9, 6));
+
+        store.reset().unwrap();
+        assert!(store.is_empty());
+        assert_eq!(store.sequences(), SequenceManager::new());
+    }
+}
@@ -0,0 +1,848 @@
+//! Pre-rendered message headers and whole messages with patchable slots.
+//!
+//! [`forge_out_buffer`](crate::forge_out_buffer) pre-renders BeginString,
+//! BodyLength and `35=`. A [`HeaderTemplate`] goes further and renders the
+//! whole standard header of one session and MsgType once:
+//!
+//! ```text
+//! 8=FIX.4.4|9=0000|35=D|49=CLIENT|56=BROKER|34=0000000000|52=00000000-00:00:00.000|
+//! ```
+//!
+//! MsgSeqNum(34) gets a fixed-width, zero-padded slot of [`SEQ_NUM_WIDTH`]
+//! digits (FIX `int` values may carry leading zeros) and SendingTime(52) a
+//! 21-character millisecond slot. [`stamp`](HeaderTemplate::stamp) copies the
+//! template into a buffer and patches the two slots; the body follows and
+//! [`finalize_message`] finishes it as usual.
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let header = HeaderTemplate::new("FIX.4.4", b"D", "CLIENT", "BROKER");
+//! let mut buffer = [0u8; FORGE_BUFFER_SIZE];
+//! let mut pos = header.stamp_at(&mut buffer, 42, 1_700_000_000, 0);
+//! pos += write_tag_and_str(&mut buffer, pos, b"11=", "ORD-42");
+//! let len = finalize_message(&mut buffer, pos);
+//! assert_eq!(
+//!     &buffer[..len - CHECKSUM_FIELD_LEN],
+//!     b"8=FIX.4.4\x019=0074\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=0000000042\x01\
+//!       52=20231114-22:13:20.000\x0111=ORD-42\x01"
+//! );
+//! assert_eq!(MessageHeader::parse(&buffer[..len]).msg_seq_num, 42);
+//! ```
+//!
+//! A [`MessageTemplate`] renders a complete message once, static fields and
+//! all, and records named fixed-width slots for the fields that change between
+//! sends (ClOrdID, price, quantity, timestamps). Numbers are right-aligned and
+//! zero-padded, so the message length never changes: BodyLength(9) is written
+//! once at build time, and every fill overwrites only its slot and adjusts
+//! CheckSum(10) by the byte delta.
+//!
+//! ```
+//! # use fix44_forge_helpers::*;
+//! let mut builder = MessageTemplate::builder(HeaderTemplate::new("FIX.4.4", b"D", "CLIENT", "BROKER"));
+//! let cl_ord_id = builder.slot("ClOrdID", b"11=", 6);
+//! builder.field(b"55=", b"MSFT");
+//! let qty = builder.slot("OrderQty", b"38=", 6);
+//! let price = builder.slot("Price", b"44=", 8);
+//! let mut order = builder.build();
+//!
+//! order.stamp_at(7, 1_700_000_000, 0);
+//! order.set_bytes(cl_ord_id, b"ORD007").unwrap();
+//! order.set_u64(qty, 100).unwrap();
+//! order.set_decimal(price, 12_345, 2).unwrap();
+//! let msg = order.as_bytes();
+//! assert_eq!(
+//!     &msg[..msg.len() - CHECKSUM_FIELD_LEN],
+//!     b"8=FIX.4.4\x019=0104\x0135=D\x0149=CLIENT\x0156=BROKER\x0134=0000000007\x01\
+//!       52=20231114-22:13:20.000\x0111=ORD007\x0155=MSFT\x0138=000100\x0144=00123.45\x01"
+//! );
+//! let body = &msg[..msg.len() - CHECKSUM_FIELD_LEN];
+//! assert_eq!(read_u32(&msg[msg.len() - 4..]), checksum(body) as u32);
+//! ```
+
+use core::{fmt, ptr};
+
+use crate::DIGIT_PAIRS;
+use crate::buffer::{
+    CHECKSUM_FIELD_LEN, FORGE_BUFFER_SIZE, FORGE_HEADER_LEN, checksum, finalize_message, write_checksum_digits,
+};
+use crate::special::format_timestamp;
+
+/// Most bytes a [`HeaderTemplate`] can hold.
+pub const HEADER_TEMPLATE_CAPACITY: usize = 160;
+
+/// Digits in the MsgSeqNum(34) slot; wide enough for any `u32`.
+pub const SEQ_NUM_WIDTH: usize = 10;
+
+/// The standard header of one session and MsgType, rendered once.
+///
+/// See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderTemplate {
+    bytes: [u8; HEADER_TEMPLATE_CAPACITY],
+    len: usize,
+    /// Offset of the first MsgSeqNum digit
+    seq_num_pos: usize,
+    /// Offset of the first SendingTime character
+    sending_time_pos: usize,
+}
+
+impl HeaderTemplate {
+    /// Render the header for `msg_type` between `sender_comp_id` and `target_comp_id`.
+    ///
+    /// # Panics
+    /// If `begin_string` is not a 7-character FIX 4.x BeginString, or the header
+    /// does not fit in [`HEADER_TEMPLATE_CAPACITY`] bytes.
+    pub fn new(
+        begin_string: &str,
+        msg_type: &[u8],
+        sender_comp_id: &str,
+        target_comp_id: &str,
+    ) -> Self {
+        assert_eq!(begin_string.len(), 7, "forge headers need a FIX 4.x BeginString");
+        let mut template = HeaderTemplate {
+            bytes: [0; HEADER_TEMPLATE_CAPACITY],
+            len: 0,
+            seq_num_pos: 0,
+            sending_time_pos: 0,
+        };
+        template.push(b"8=");
+        template.push(begin_string.as_bytes());
+        template.push(b"\x019=0000\x0135=");
+        debug_assert_eq!(template.len, FORGE_HEADER_LEN);
+        template.push(msg_type);
+        template.push(b"\x0149=");
+        template.push(sender_comp_id.as_bytes());
+        template.push(b"\x0156=");
+        template.push(target_comp_id.as_bytes());
+        template.push(b"\x0134=");
+        template.seq_num_pos = template.len;
+        template.push(&[b'0'; SEQ_NUM_WIDTH]);
+        template.push(b"\x0152=");
+        template.sending_time_pos = template.len;
+        template.push(b"00000000-00:00:00.000\x01");
+        template
+    }
+
+    fn push(
+        &mut self,
+        bytes: &[u8],
+    ) {
+        let end = self.len + bytes.len();
+        assert!(end <= HEADER_TEMPLATE_CAPACITY, "header template too long");
+        self.bytes[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+    }
+
+    /// The rendered header, with unstamped slots.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Copy the header to the start of `buf` and fill MsgSeqNum(34) with `seq`
+    /// and SendingTime(52) with `now`.
+    ///
+    /// Requires the `clock` feature; see [`stamp_at`](HeaderTemplate::stamp_at)
+    /// for the clock-free form.
+    ///
+    /// Returns the position after the header, where the body starts.
+    #[cfg(feature = "clock")]
+    #[inline(always)]
+    pub fn stamp(
+        &self,
+        buf: &mut [u8],
+        seq: u32,
+        now: &libc::timespec,
+    ) -> usize {
+        self.stamp_at(buf, seq, now.tv_sec as u64, now.tv_nsec as u32)
+    }
+
+    /// Copy the header to the start of `buf` and fill MsgSeqNum(34) with `seq`
+    /// and SendingTime(52) from seconds and nanoseconds since the Unix epoch.
+    ///
+    /// Returns the position after the header, where the body starts.
+    #[inline(always)]
+    pub fn stamp_at(
+        &self,
+        buf: &mut [u8],
+        seq: u32,
+        secs: u64,
+        nanos: u32,
+    ) -> usize {
+        assert!(buf.len() >= self.len);
+        unsafe {
+            ptr::copy_nonoverlapping(self.bytes.as_ptr(), buf.as_mut_ptr(), self.len);
+        }
+        self.patch_seq_num(buf, seq);
+        // The slot is followed by SOH in the template; format_timestamp rewrites it
+        format_timestamp(buf, self.sending_time_pos, b"", secs, nanos);
+        self.len
+    }
+
+    /// Overwrite only the MsgSeqNum(34) slot of a stamped header.
+    #[inline(always)]
+    pub fn patch_seq_num(
+        &self,
+        buf: &mut [u8],
+        seq: u32,
+    ) {
+        debug_assert!(buf.len() >= self.seq_num_pos + SEQ_NUM_WIDTH);
+        // Five digit pairs, least significant first
+        let mut rest = seq;
+        let mut at = self.seq_num_pos + SEQ_NUM_WIDTH;
+        while at > self.seq_num_pos {
+            at -= 2;
+            let pair = (rest % 100) as usize;
+            rest /= 100;
+            unsafe {
+                ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(pair * 2), buf.as_mut_ptr().add(at), 2);
+            }
+        }
+    }
+
+    /// Offset of the MsgSeqNum(34) slot in a stamped buffer.
+    pub fn seq_num_pos(&self) -> usize {
+        self.seq_num_pos
+    }
+
+    /// Offset of the SendingTime(52) slot in a stamped buffer.
+    pub fn sending_time_pos(&self) -> usize {
+        self.sending_time_pos
+    }
+}
+
+// patch_seq_num writes whole digit pairs
+const _: () = assert!(SEQ_NUM_WIDTH.is_multiple_of(2));
+
+/// Most slots a [`MessageTemplate`] can record, including MsgSeqNum and SendingTime.
+pub const MAX_TEMPLATE_SLOTS: usize = 16;
+
+/// Widest [`MessageTemplate`] slot.
+pub const MAX_SLOT_WIDTH: usize = 32;
+
+/// Width of a SendingTime-style timestamp slot (`YYYYMMDD-HH:MM:SS.sss`).
+const TIMESTAMP_WIDTH: usize = 21;
+
+/// Initial contents of a timestamp slot.
+const TIMESTAMP_FILL: &[u8] = b"00000000-00:00:00.000";
+
+/// Handle to a variable field of a [`MessageTemplate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot(u8);
+
+impl Slot {
+    /// The MsgSeqNum(34) slot of every template.
+    pub const MSG_SEQ_NUM: Slot = Slot(0);
+    /// The SendingTime(52) slot of every template.
+    pub const SENDING_TIME: Slot = Slot(1);
+}
+
+/// A value that does not fit its [`MessageTemplate`] slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotError {
+    /// Value is wider than the slot
+    Overflow {
+        /// Slot name
+        name: &'static str,
+        /// Slot width in bytes
+        width: usize,
+        /// Length the value needed
+        len: usize,
+    },
+    /// Value given to [`set_bytes`](MessageTemplate::set_bytes) is narrower
+    /// than the slot; raw bytes are never padded
+    TooShort {
+        /// Slot name
+        name: &'static str,
+        /// Slot width in bytes
+        width: usize,
+        /// Length of the value
+        len: usize,
+    },
+}
+
+impl fmt::Display for SlotError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            SlotError::Overflow { name, width, len } => {
+                write!(
+                    f,
+                    "value of {len} bytes does not fit slot {name} of width {width}"
+                )
+            }
+            SlotError::TooShort { name, width, len } => {
+                write!(
+                    f,
+                    "value of {len} bytes does not fill slot {name} of width {width}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for SlotError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SlotInfo {
+    name: &'static str,
+    offset: u16,
+    width: u8,
+    /// Byte sum of the current contents, so patches never re-read them
+    sum: u8,
+}
+
+/// A complete message rendered once, with fixed-width slots for the fields
+/// that change between sends. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct MessageTemplate {
+    buf: [u8; FORGE_BUFFER_SIZE],
+    /// Length before build, total length after
+    len: usize,
+    slots: [SlotInfo; MAX_TEMPLATE_SLOTS],
+    slot_count: usize,
+    /// Byte sum of everything before CheckSum(10)
+    sum: u8,
+}
+
+/// Renders a [`MessageTemplate`] field by field; see [`MessageTemplate::builder`].
+#[derive(Debug, Clone)]
+pub struct MessageTemplateBuilder {
+    template: MessageTemplate,
+}
+
+impl MessageTemplate {
+    /// Start a template with `header`; its MsgSeqNum and SendingTime become
+    /// [`Slot::MSG_SEQ_NUM`] and [`Slot::SENDING_TIME`].
+    pub fn builder(header: HeaderTemplate) -> MessageTemplateBuilder {
+        let empty = SlotInfo {
+            name: "",
+            offset: 0,
+            width: 0,
+            sum: 0,
+        };
+        let mut template = MessageTemplate {
+            buf: [0; FORGE_BUFFER_SIZE],
+            len: 0,
+            slots: [empty; MAX_TEMPLATE_SLOTS],
+            slot_count: 2,
+            sum: 0,
+        };
+        let bytes = header.as_bytes();
+        template.buf[..bytes.len()].copy_from_slice(bytes);
+        template.len = bytes.len();
+        template.slots[0] = SlotInfo {
+            name: "MsgSeqNum",
+            offset: header.seq_num_pos() as u16,
+            width: SEQ_NUM_WIDTH as u8,
+            sum: checksum(&[b'0'; SEQ_NUM_WIDTH]),
+        };
+        template.slots[1] = SlotInfo {
+            name: "SendingTime",
+            offset: header.sending_time_pos() as u16,
+            width: TIMESTAMP_WIDTH as u8,
+            sum: checksum(TIMESTAMP_FILL),
+        };
+        MessageTemplateBuilder { template }
+    }
+
+    /// The current message, ready to send.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Look up a slot by the name it was created with.
+    pub fn slot(
+        &self,
+        name: &str,
+    ) -> Option<Slot> {
+        self.slots[..self.slot_count]
+            .iter()
+            .position(|info| info.name == name)
+            .map(|i| Slot(i as u8))
+    }
+
+    /// Byte range of `slot` in [`as_bytes`](MessageTemplate::as_bytes).
+    pub fn slot_range(
+        &self,
+        slot: Slot,
+    ) -> core::ops::Range<usize> {
+        let info = self.slots[slot.0 as usize];
+        info.offset as usize..info.offset as usize + info.width as usize
+    }
+
+    /// Set MsgSeqNum(34) to `seq` and SendingTime(52) to `now`.
+    ///
+    /// Requires the `clock` feature; see [`stamp_at`](MessageTemplate::stamp_at)
+    /// for the clock-free form.
+    #[cfg(feature = "clock")]
+    #[inline]
+    pub fn stamp(
+        &mut self,
+        seq: u32,
+        now: &libc::timespec,
+    ) {
+        self.stamp_at(seq, now.tv_sec as u64, now.tv_nsec as u32);
+    }
+
+    /// Set MsgSeqNum(34) to `seq` and SendingTime(52) from seconds and
+    /// nanoseconds since the Unix epoch.
+    #[inline]
+    pub fn stamp_at(
+        &mut self,
+        seq: u32,
+        secs: u64,
+        nanos: u32,
+    ) {
+        self.set_u64(Slot::MSG_SEQ_NUM, seq as u64)
+            .expect("MsgSeqNum slot holds any u32");
+        self.set_timestamp(Slot::SENDING_TIME, secs, nanos);
+    }
+
+    /// Set a slot to exactly `value`, which must be as wide as the slot.
+    ///
+    /// Raw bytes are never padded, since that would change the value (a
+    /// ClOrdID of `ORD7` is not `0000ORD7`): narrower values fail with
+    /// [`SlotError::TooShort`], wider ones with [`SlotError::Overflow`].
+    #[inline]
+    pub fn set_bytes(
+        &mut self,
+        slot: Slot,
+        value: &[u8],
+    ) -> Result<(), SlotError> {
+        let info = self.slots[slot.0 as usize];
+        let (name, width, len) = (info.name, info.width as usize, value.len());
+        if len > width {
+            return Err(SlotError::Overflow { name, width, len });
+        }
+        if len < width {
+            return Err(SlotError::TooShort { name, width, len });
+        }
+        self.patch(slot, value);
+        Ok(())
+    }
+
+    /// Set a slot to `value`, zero-padded on the left.
+    #[inline]
+    pub fn set_u64(
+        &mut self,
+        slot: Slot,
+        value: u64,
+    ) -> Result<(), SlotError> {
+        self.set_decimal_parts(slot, false, value, 0)
+    }
+
+    /// Set a slot to the fixed-point number `value / 10^scale`, zero-padded on
+    /// the left (e.g. `12345, 2` in a width-8 slot gives `00123.45`).
+    #[inline]
+    pub fn set_decimal(
+        &mut self,
+        slot: Slot,
+        value: i64,
+        scale: u8,
+    ) -> Result<(), SlotError> {
+        self.set_decimal_parts(slot, value < 0, value.unsigned_abs(), scale)
+    }
+
+    /// Set a timestamp slot (created with
+    /// [`timestamp_slot`](MessageTemplateBuilder::timestamp_slot)) from seconds
+    /// and nanoseconds since the Unix epoch.
+    #[inline]
+    pub fn set_timestamp(
+        &mut self,
+        slot: Slot,
+        secs: u64,
+        nanos: u32,
+    ) {
+        let info = self.slots[slot.0 as usize];
+        debug_assert_eq!(info.width as usize, TIMESTAMP_WIDTH, "not a timestamp slot");
+        let mut value = [0u8; TIMESTAMP_WIDTH + 1];
+        format_timestamp(&mut value, 0, b"", secs, nanos);
+        self.patch(slot, &value[..TIMESTAMP_WIDTH]);
+    }
+
+    #[inline]
+    fn set_decimal_parts(
+        &mut self,
+        slot: Slot,
+        negative: bool,
+        magnitude: u64,
+        scale: u8,
+    ) -> Result<(), SlotError> {
+        let info = self.slots[slot.0 as usize];
+        let width = info.width as usize;
+        let scale = scale as usize;
+        let count = magnitude.checked_ilog10().map_or(1, |log| log as usize + 1);
+        // At least one integer digit; fraction digits are zero-padded
+        let needed = negative as usize + count.saturating_sub(scale).max(1) + scale + (scale > 0) as usize;
+        if needed > width {
+            return Err(SlotError::Overflow {
+                name: info.name,
+                width,
+                len: needed,
+            });
+        }
+        // Zero-fill the slot, then render the digits right-aligned over it
+        let offset = info.offset as usize;
+        let value = &mut self.buf[offset..offset + width];
+        value.fill(b'0');
+        if scale > 0 {
+            let (int, frac) = match 10u64.checked_pow(scale as u32) {
+                Some(pow) => (magnitude / pow, magnitude % pow),
+                None => (0, magnitude),
+            };
+            write_digits_back(value, width, frac);
+            value[width - scale - 1] = b'.';
+            write_digits_back(value, width - scale - 1, int);
+        } else {
+            write_digits_back(value, width, magnitude);
+        }
+        if negative {
+            value[0] = b'-';
+        }
+        let sum = checksum(value);
+        self.update_checksum(slot, sum);
+        Ok(())
+    }
+
+    /// Overwrite a slot and adjust CheckSum(10) by the delta.
+    #[inline(always)]
+    fn patch(
+        &mut self,
+        slot: Slot,
+        value: &[u8],
+    ) {
+        let offset = self.slots[slot.0 as usize].offset as usize;
+        self.buf[offset..offset + value.len()].copy_from_slice(value);
+        self.update_checksum(slot, checksum(value));
+    }
+
+    /// Record the byte sum of a rewritten slot and rewrite the CheckSum(10) digits.
+    #[inline(always)]
+    fn update_checksum(
+        &mut self,
+        slot: Slot,
+        slot_sum: u8,
+    ) {
+        let info = &mut self.slots[slot.0 as usize];
+        let sum = self
+            .sum
+            .wrapping_add(slot_sum)
+            .wrapping_sub(info.sum);
+        info.sum = slot_sum;
+        self.sum = sum;
+        write_checksum_digits(&mut self.buf, self.len - CHECKSUM_FIELD_LEN, sum);
+    }
+}
+
+impl MessageTemplateBuilder {
+    fn push(
+        &mut self,
+        bytes: &[u8],
+    ) {
+        let t = &mut self.template;
+        let end = t.len + bytes.len();
+        // Leave room for CheckSum(10)
+        assert!(end + CHECKSUM_FIELD_LEN <= FORGE_BUFFER_SIZE, "message template too long");
+        t.buf[t.len..end].copy_from_slice(bytes);
+        t.len = end;
+    }
+
+    fn add_slot(
+        &mut self,
+        name: &'static str,
+        tag_and_eq: &[u8],
+        width: usize,
+        fill: &[u8],
+    ) -> Slot {
+        assert!(width <= MAX_SLOT_WIDTH, "slot {name} wider than MAX_SLOT_WIDTH");
+        assert!(self.template.slot_count < MAX_TEMPLATE_SLOTS, "too many template slots");
+        self.push(tag_and_eq);
+        let offset = self.template.len;
+        self.push(fill);
+        self.push(b"\x01");
+        let t = &mut self.template;
+        t.slots[t.slot_count] = SlotInfo {
+            name,
+            offset: offset as u16,
+            width: width as u8,
+            sum: checksum(fill),
+        };
+        t.slot_count += 1;
+        Slot(t.slot_count as u8 - 1)
+    }
+
+    /// Append a static field, e.g. `field(b"55=", b"MSFT")`.
+    pub fn field(
+        &mut self,
+        tag_and_eq: &[u8],
+        value: &[u8],
+    ) -> &mut Self {
+        self.push(tag_and_eq);
+        self.push(value);
+        self.push(b"\x01");
+        self
+    }
+
+    /// Append a variable field of exactly `width` bytes, initially all `0`.
+    ///
+    /// # Panics
+    /// If `width` exceeds [`MAX_SLOT_WIDTH`] or the template already has
+    /// [`MAX_TEMPLATE_SLOTS`] slots.
+    pub fn slot(
+        &mut self,
+        name: &'static str,
+        tag_and_eq: &[u8],
+        width: usize,
+    ) -> Slot {
+        self.add_slot(name, tag_and_eq, width, &[b'0'; MAX_SLOT_WIDTH][..width])
+    }
+
+    /// Append a UTCTimestamp field (e.g. TransactTime(60)) with millisecond precision.
+    pub fn timestamp_slot(
+        &mut self,
+        name: &'static str,
+        tag_and_eq: &[u8],
+    ) -> Slot {
+        self.add_slot(name, tag_and_eq, TIMESTAMP_WIDTH, TIMESTAMP_FILL)
+    }
+
+    /// Write BodyLength(9) and CheckSum(10) and return the finished template.
+    pub fn build(self) -> MessageTemplate {
+        let mut t = self.template;
+        let body_end = t.len;
+        t.len = finalize_message(&mut t.buf, body_end);
+        t.sum = checksum(&t.buf[..body_end]);
+        t
+    }
+}
+
+/// Write `n` so that its last digit lands at `buf[end - 1]`, leaving the bytes
+/// before it alone. The caller guarantees the digits fit.
+#[inline(always)]
+fn write_digits_back(
+    buf: &mut [u8],
+    end: usize,
+    mut n: u64,
+) {
+    debug_assert!(end <= buf.len());
+    let mut at = end;
+    while n >= 100 {
+        at -= 2;
+        let pair = (n % 100) as usize;
+        n /= 100;
+        unsafe {
+            ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(pair * 2), buf.as_mut_ptr().add(at), 2);
+        }
+    }
+    if n >= 10 {
+        unsafe {
+            ptr::copy_nonoverlapping(DIGIT_PAIRS.as_ptr().add(n as usize * 2), buf.as_mut_ptr().add(at - 2), 2);
+        }
+    } else {
+        buf[at - 1] = b'0' + n as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{FORGE_BUFFER_SIZE, FORGE_WRITE_START, finalize_message, forge_out_buffer};
+    use crate::fields::MessageHeader;
+    use crate::test_util::xorshift;
+    use crate::writing::{write_tag_and_str, write_tag_and_u32};
+
+    #[test]
+    fn test_template_layout() {
+        let header = HeaderTemplate::new("FIX.4.4", b"AE", "SND", "TGT");
+        assert_eq!(
+            header.as_bytes(),
+            b"8=FIX.4.4\x019=0000\x0135=AE\x0149=SND\x0156=TGT\x0134=0000000000\x01\
+              52=00000000-00:00:00.000\x01"
+        );
+        assert_eq!(&header.as_bytes()[header.seq_num_pos() - 3..header.seq_num_pos()], b"34=");
+        assert_eq!(
+            &header.as_bytes()[header.sending_time_pos() - 3..header.sending_time_pos()],
+            b"52="
+        );
+    }
+
+    #[test]
+    fn test_stamp_matches_field_writers() {
+        let header = HeaderTemplate::new("FIX.4.4", b"D", "CLIENT", "BROKER");
+        for (seq, padded) in [(1, "0000000001"), (123_456, "0000123456"), (u32::MAX, "4294967295")] {
+            let mut stamped = [0u8; FORGE_BUFFER_SIZE];
+            let pos = header.stamp_at(&mut stamped, seq, 1_700_000_000, 987_000_000);
+
+            let mut expected = forge_out_buffer("FIX.4.4");
+            let mut end = FORGE_WRITE_START;
+            expected[end] = b'D';
+            expected[end + 1] = 0x01;
+            end += 2;
+            end += write_tag_and_str(&mut expected, end, b"49=", "CLIENT");
+            end += write_tag_and_str(&mut expected, end, b"56=", "BROKER");
+            end += write_tag_and_str(&mut expected, end, b"34=", padded);
+            end += format_timestamp(&mut expected, end, b"52=", 1_700_000_000, 987_000_000);
+            assert_eq!(stamped[..pos], expected[..end], "seq {seq}");
+
+            let len = finalize_message(&mut stamped, pos);
+            assert_eq!(MessageHeader::parse(&stamped[..len]).msg_seq_num, seq);
+        }
+    }
+
+    #[test]
+    fn test_restamp_and_patch() {
+        let header = HeaderTemplate::new("FIX.4.4", b"0", "A", "B");
+        let mut buf = [0u8; 128];
+        let pos = header.stamp_at(&mut buf, 99_999, 1_800_000_000, 0);
+        let pos2 = header.stamp_at(&mut buf, 7, 1_700_000_000, 5_000_000);
+        assert_eq!(pos, pos2);
+        let mut end = pos;
+        end += write_tag_and_u32(&mut buf, end, b"112=", 1);
+        header.patch_seq_num(&mut buf, 8);
+        assert_eq!(
+            &buf[..end],
+            b"8=FIX.4.4\x019=0000\x0135=0\x0149=A\x0156=B\x0134=0000000008\x01\
+              52=20231114-22:13:20.005\x01112=1\x01"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "header template too long")]
+    fn test_template_capacity() {
+        let long = "X".repeat(HEADER_TEMPLATE_CAPACITY);
+        HeaderTemplate::new("FIX.4.4", b"D", &long, "B");
+    }
+
+    fn order_template() -> (MessageTemplate, [Slot; 4]) {
+        let mut builder = MessageTemplate::builder(HeaderTemplate::new("FIX.4.4", b"D", "CLIENT", "BROKER"));
+        let cl_ord_id = builder.slot("ClOrdID", b"11=", 13);
+        builder.field(b"21=", b"1").field(b"55=", b"MSFT").field(b"54=", b"1");
+        let transact_time = builder.timestamp_slot("TransactTime", b"60=");
+        let qty = builder.slot("OrderQty", b"38=", 9);
+        builder.field(b"40=", b"2");
+        let price = builder.slot("Price", b"44=", 12);
+        (builder.build(), [cl_ord_id, transact_time, qty, price])
+    }
+
+    fn assert_consistent(template: &MessageTemplate) {
+        let msg = template.as_bytes();
+        let body_len = crate::reading::read_u32(&msg[12..16]) as usize;
+        assert_eq!(body_len, msg.len() - 17 - CHECKSUM_FIELD_LEN);
+        let sum = crate::reading::read_u32(&msg[msg.len() - 4..msg.len() - 1]);
+        assert_eq!(sum, checksum(&msg[..msg.len() - CHECKSUM_FIELD_LEN]) as u32);
+    }
+
+    #[test]
+    fn test_message_template_matches_forge() {
+        let (mut template, [cl_ord_id, transact_time, qty, price]) = order_template();
+        assert_consistent(&template);
+        template.stamp_at(5, 1_700_000_000, 250_000_000);
+        template.set_bytes(cl_ord_id, b"0000000000ABC").unwrap();
+        template.set_timestamp(transact_time, 1_700_000_000, 249_000_000);
+        template.set_u64(qty, 1_500).unwrap();
+        template.set_decimal(price, -4_205, 3).unwrap();
+
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'D';
+        buffer[pos + 1] = 0x01;
+        pos += 2;
+        for (tag, value) in [
+            (&b"49="[..], "CLIENT"),
+            (b"56=", "BROKER"),
+            (b"34=", "0000000005"),
+            (b"52=", "20231114-22:13:20.250"),
+            (b"11=", "0000000000ABC"),
+            (b"21=", "1"),
+            (b"55=", "MSFT"),
+            (b"54=", "1"),
+            (b"60=", "20231114-22:13:20.249"),
+            (b"38=", "000001500"),
+            (b"40=", "2"),
+            (b"44=", "-0000004.205"),
+        ] {
+            pos += write_tag_and_str(&mut buffer, pos, tag, value);
+        }
+        let len = finalize_message(&mut buffer, pos);
+        assert_eq!(template.as_bytes(), &buffer[..len]);
+    }
+
+    #[cfg(feature = "clock")]
+    #[test]
+    fn test_message_template_stamp_timespec() {
+        let (mut template, _) = order_template();
+        let now = libc::timespec {
+            tv_sec: 1_700_000_000,
+            tv_nsec: 250_000_000,
+        };
+        template.stamp(9, &now);
+        let stamped = template.as_bytes().to_vec();
+        template.stamp_at(1, 0, 0);
+        template.stamp_at(9, 1_700_000_000, 250_000_000);
+        assert_eq!(template.as_bytes(), &stamped[..]);
+        assert_consistent(&template);
+    }
+
+    #[test]
+    fn test_message_template_random_fills() {
+        let (mut template, [cl_ord_id, transact_time, qty, price]) = order_template();
+        let mut next = xorshift();
+        for _ in 0..1_000 {
+            let r = next();
+            template.stamp_at(r as u32, 1_600_000_000 + r % 400_000_000, (r % 1_000_000_000) as u32);
+            let mut id = [0u8; 13];
+            for b in &mut id {
+                *b = b'A' + (next() % 26) as u8;
+            }
+            template.set_bytes(cl_ord_id, &id).unwrap();
+            template.set_timestamp(transact_time, r % 4_000_000_000, 0);
+            template.set_u64(qty, next() % 1_000_000_000).unwrap();
+            template.set_decimal(price, (next() % 10_000_000_000) as i64 - 5_000_000_000, 4).unwrap();
+            assert_consistent(&template);
+        }
+    }
+
+    #[test]
+    fn test_message_template_slots() {
+        let (mut template, [cl_ord_id, _, qty, price]) = order_template();
+        assert_eq!(template.slot("Price"), Some(price));
+        assert_eq!(template.slot("MsgSeqNum"), Some(Slot::MSG_SEQ_NUM));
+        assert_eq!(template.slot("Side"), None);
+        assert_eq!(&template.as_bytes()[template.slot_range(qty)], b"000000000");
+
+        let before = template.as_bytes().to_vec();
+        assert_eq!(
+            template.set_bytes(cl_ord_id, b"SHORT"),
+            Err(SlotError::TooShort {
+                name: "ClOrdID",
+                width: 13,
+                len: 5,
+            })
+        );
+        assert_eq!(
+            template.set_bytes(cl_ord_id, b"FOURTEEN-BYTES"),
+            Err(SlotError::Overflow {
+                name: "ClOrdID",
+                width: 13,
+                len: 14,
+            })
+        );
+        assert_eq!(
+            template.set_u64(qty, 1_000_000_000),
+            Err(SlotError::Overflow {
+                name: "OrderQty",
+                width: 9,
+                len: 10,
+            })
+        );
+        assert!(template.set_decimal(price, -123_456_789_012, 2).is_err());
+        assert_eq!(template.as_bytes(), &before[..]);
+
+        template.set_decimal(price, 5, 2).unwrap();
+        assert_eq!(&template.as_bytes()[template.slot_range(price)], b"000000000.05");
+        template.set_decimal(price, -1_234_567_801, 2).unwrap();
+        assert_eq!(&template.as_bytes()[template.slot_range(price)], b"-12345678.01");
+    }
+}
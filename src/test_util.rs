@@ -0,0 +1,40 @@
+//! Fixtures shared by the unit tests.
+
+use crate::buffer::{FORGE_BUFFER_SIZE, FORGE_WRITE_START, finalize_message, forge_out_buffer};
+use crate::writing::{write_tag_and_str, write_tag_and_u32};
+
+/// Forge a NewOrderSingle from CLIENT to BROKER with MsgSeqNum(34) 12, with
+/// the body written by `body(buffer, offset)` (which returns the bytes written).
+pub(crate) fn forge(body: impl FnOnce(&mut [u8], usize) -> usize) -> ([u8; FORGE_BUFFER_SIZE], usize) {
+    let (mut buffer, pos) = forge_unfinished(body);
+    let len = finalize_message(&mut buffer, pos);
+    (buffer, len)
+}
+
+/// [`forge`] without `finalize_message`: BodyLength(9) keeps its placeholder
+/// and the returned offset is where CheckSum(10) would go.
+pub(crate) fn forge_unfinished(
+    body: impl FnOnce(&mut [u8], usize) -> usize,
+) -> ([u8; FORGE_BUFFER_SIZE], usize) {
+    let mut buffer = forge_out_buffer("FIX.4.4");
+    let mut pos = FORGE_WRITE_START;
+    buffer[pos] = b'D';
+    buffer[pos + 1] = 0x01;
+    pos += 2;
+    pos += write_tag_and_str(&mut buffer, pos, b"49=", "CLIENT");
+    pos += write_tag_and_str(&mut buffer, pos, b"56=", "BROKER");
+    pos += write_tag_and_u32(&mut buffer, pos, b"34=", 12);
+    pos += body(&mut buffer, pos);
+    (buffer, pos)
+}
+
+/// Deterministic xorshift64 generator for randomized tests.
+pub(crate) fn xorshift() -> impl FnMut() -> u64 {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
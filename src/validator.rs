@@ -0,0 +1,791 @@
+//! Runtime validation of raw messages against a [`Dictionary`].
+//!
+//! A [`Validator`] flattens the dictionary once into per-message tag tables and
+//! then checks complete wire messages (`8=...` through `10=...`):
+//!
+//! - framing, via [`TagValueIter::try_next`]
+//! - MsgType(35) is defined in the dictionary
+//! - header/body/trailer ordering: BeginString, BodyLength and MsgType first,
+//!   header before body before trailer, CheckSum last
+//! - tags are defined, allowed for the message type and not repeated
+//! - values are non-empty, match their [`FieldType`] and, for enumerated
+//!   fields, one of the listed values
+//! - repeating groups start each entry with the delimiter (first) tag and
+//!   match their NumInGroup count
+//! - required fields of the header, body, trailer and group entries are present
+//!
+//! Problems are reported as [`ReadError`]s, so each maps onto a
+//! SessionRejectReason(373) via [`ReadError::session_reject_reason`].
+//!
+//! Requires the `alloc` feature.
+
+use crate::dictionary::{Dictionary, FieldDef, FieldType, Member};
+use crate::errors::{MemberKind, MissingMask, ReadError};
+use crate::fields::TagValueIter;
+use crate::reading::{
+    read_multi_char, read_multi_str, read_u32, try_read_bool, try_read_f64, try_read_i64,
+    try_read_str, try_read_u64,
+};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// First tag of the user-defined range (5000 and above).
+pub const USER_DEFINED_TAG_MIN: u32 = 5000;
+
+/// Checks performed by a [`Validator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidatorSettings {
+    /// Accept tags the dictionary does not define, or does not define for the message type
+    pub ignore_unknown_tags: bool,
+    /// Accept user-defined tags (see [`USER_DEFINED_TAG_MIN`]) without checking them
+    pub ignore_user_defined_fields: bool,
+    /// Check header/body/trailer ordering
+    pub check_field_order: bool,
+    /// Check values against their field type and enumerated values
+    pub check_field_values: bool,
+}
+
+impl Default for ValidatorSettings {
+    fn default() -> Self {
+        ValidatorSettings {
+            ignore_unknown_tags: false,
+            ignore_user_defined_fields: false,
+            check_field_order: true,
+            check_field_values: true,
+        }
+    }
+}
+
+/// Validates raw messages against a [`Dictionary`].
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// let dict = Dictionary::parse(r#"
+/// <fix major="4" minor="4">
+///   <header>
+///     <field name="BeginString" required="Y"/>
+///     <field name="BodyLength" required="Y"/>
+///     <field name="MsgType" required="Y"/>
+///   </header>
+///   <trailer><field name="CheckSum" required="Y"/></trailer>
+///   <messages>
+///     <message name="TestRequest" msgtype="1" msgcat="admin">
+///       <field name="TestReqID" required="Y"/>
+///     </message>
+///   </messages>
+///   <fields>
+///     <field number="8" name="BeginString" type="STRING"/>
+///     <field number="9" name="BodyLength" type="LENGTH"/>
+///     <field number="10" name="CheckSum" type="STRING"/>
+///     <field number="35" name="MsgType" type="STRING"/>
+///     <field number="112" name="TestReqID" type="STRING"/>
+///   </fields>
+/// </fix>"#).unwrap();
+/// let validator = Validator::new(&dict);
+///
+/// assert_eq!(validator.validate(b"8=FIX.4.4\x019=5\x0135=1\x01112=X\x0110=000\x01"), Ok(()));
+/// assert_eq!(
+///     validator.validate(b"8=FIX.4.4\x019=5\x0135=1\x0110=000\x01"),
+///     Err(ReadError::MissingRequiredFields {
+///         missing_mask: MissingMask::new(),
+///         meta: &[],
+///         tag: Some(112),
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Validator<'d> {
+    dict: &'d Dictionary,
+    settings: ValidatorSettings,
+    header: Scope,
+    trailer: Scope,
+    /// Body scopes by MsgType
+    messages: BTreeMap<String, Scope>,
+}
+
+impl<'d> Validator<'d> {
+    /// Create a validator with the default [`ValidatorSettings`].
+    pub fn new(dict: &'d Dictionary) -> Self {
+        Self::with_settings(dict, ValidatorSettings::default())
+    }
+
+    /// Create a validator with custom settings.
+    pub fn with_settings(
+        dict: &'d Dictionary,
+        settings: ValidatorSettings,
+    ) -> Self {
+        let messages = dict
+            .messages()
+            .iter()
+            .map(|message| {
+                (
+                    message.msg_type.clone(),
+                    Scope::build(dict, &message.members),
+                )
+            })
+            .collect();
+        Validator {
+            dict,
+            settings,
+            header: Scope::build(dict, dict.header()),
+            trailer: Scope::build(dict, dict.trailer()),
+            messages,
+        }
+    }
+
+    /// The settings in effect.
+    pub fn settings(&self) -> &ValidatorSettings {
+        &self.settings
+    }
+
+    /// Validate `msg`, returning the first problem found.
+    ///
+    /// # Errors
+    /// The first [`ReadError`] in wire order; missing required fields are
+    /// reported after the fields that are present.
+    pub fn validate(
+        &self,
+        msg: &[u8],
+    ) -> Result<(), ReadError> {
+        let mut report = Report {
+            errors: Vec::new(),
+            first_only: true,
+        };
+        let _ = self.run(msg, &mut report);
+        match report.errors.pop() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Validate `msg`, returning every problem found (empty if valid).
+    ///
+    /// Framing errors and an unknown MsgType end validation early.
+    pub fn validate_all(
+        &self,
+        msg: &[u8],
+    ) -> Vec<ReadError> {
+        let mut report = Report {
+            errors: Vec::new(),
+            first_only: false,
+        };
+        let _ = self.run(msg, &mut report);
+        report.errors
+    }
+
+    fn run(
+        &self,
+        msg: &[u8],
+        report: &mut Report,
+    ) -> Result<(), Stop> {
+        // Surface framing errors and locate MsgType before walking the sections
+        let mut it = TagValueIter::new(msg);
+        let mut msg_type = None;
+        loop {
+            let offset = it.position();
+            match it.try_next() {
+                Ok(Some((35, value))) if msg_type.is_none() => msg_type = Some((offset, value)),
+                Ok(Some(_)) => {}
+                Ok(None) => break,
+                Err(error) => return report.fatal(error),
+            }
+        }
+        let body = match msg_type {
+            Some((offset, value)) => {
+                let scope = core::str::from_utf8(value)
+                    .ok()
+                    .and_then(|msg_type| self.messages.get(msg_type));
+                match scope {
+                    Some(scope) => scope,
+                    None => return report.fatal(ReadError::InvalidMsgType { offset }),
+                }
+            }
+            None => return report.fatal(missing_tag(35)),
+        };
+
+        let mut it = TagValueIter::new(msg);
+        let mut seen = [BTreeSet::new(), BTreeSet::new(), BTreeSet::new()];
+        let mut section = Section::Header;
+        let mut index = 0;
+        let mut after_checksum = false;
+        loop {
+            let offset = it.position();
+            let Ok(Some((tag, value))) = it.try_next() else {
+                break;
+            };
+            let current = if self.header.slots.contains_key(&tag) {
+                Section::Header
+            } else if self.trailer.slots.contains_key(&tag) {
+                Section::Trailer
+            } else {
+                Section::Body
+            };
+            if self.settings.check_field_order {
+                let expected = [8, 9, 35].get(index).copied();
+                let misplaced = expected.is_some_and(|expected| expected != tag)
+                    || (expected.is_none() && matches!(tag, 8 | 9 | 35))
+                    || current < section
+                    || after_checksum;
+                if misplaced {
+                    report.push(ReadError::TagOutOfOrder { offset, tag })?;
+                }
+            }
+            section = section.max(current);
+            after_checksum |= tag == 10;
+            index += 1;
+            let scope = match current {
+                Section::Header => &self.header,
+                Section::Body => body,
+                Section::Trailer => &self.trailer,
+            };
+            self.check_field(scope, &mut seen[current as usize], &mut it, offset, tag, value, report)?;
+        }
+
+        for (scope, seen) in [&self.header, body, &self.trailer]
+            .into_iter()
+            .zip(&seen)
+        {
+            check_required(scope, seen, report)?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn check_field(
+        &self,
+        scope: &Scope,
+        seen: &mut BTreeSet<u32>,
+        it: &mut TagValueIter<'_>,
+        offset: usize,
+        tag: u32,
+        value: &[u8],
+        report: &mut Report,
+    ) -> Result<(), Stop> {
+        if self.settings.ignore_user_defined_fields && tag >= USER_DEFINED_TAG_MIN {
+            return Ok(());
+        }
+        if !seen.insert(tag) {
+            return report.push(ReadError::DuplicateTag { offset, tag });
+        }
+        let Some(def) = self.dict.field_by_tag(tag) else {
+            if self.settings.ignore_unknown_tags {
+                return Ok(());
+            }
+            return report.push(ReadError::UnknownTag { offset, tag });
+        };
+        let Some(slot) = scope.slots.get(&tag) else {
+            if self.settings.ignore_unknown_tags {
+                return Ok(());
+            }
+            return report.push(ReadError::TagNotDefinedForMessage { offset, tag });
+        };
+        self.check_value(def, offset, value, report)?;
+        if let Slot::Group { delimiter, entry } = slot {
+            self.check_group(it, entry, *delimiter, offset, tag, read_u32(value), report)?;
+        }
+        Ok(())
+    }
+
+    fn check_value(
+        &self,
+        def: &FieldDef,
+        offset: usize,
+        value: &[u8],
+        report: &mut Report,
+    ) -> Result<(), Stop> {
+        let tag = def.number;
+        if value.is_empty() {
+            return report.push(ReadError::EmptyValue { offset, tag });
+        }
+        if !self.settings.check_field_values {
+            return Ok(());
+        }
+        if !format_ok(def.field_type, value) {
+            return report.push(ReadError::InvalidFormat {
+                name: "",
+                tag,
+                msg: "value does not parse as the field's type",
+                offset: Some(offset),
+            });
+        }
+        let allowed = if def.field_type.is_multi_value() {
+            value
+                .split(|&b| b == b' ')
+                .all(|v| def.allows(v))
+        } else {
+            def.allows(value)
+        };
+        if !allowed {
+            return report.push(ReadError::InvalidValue {
+                name: "",
+                tag,
+                msg: "value is not one of the field's enumerated values",
+                offset: Some(offset),
+            });
+        }
+        Ok(())
+    }
+
+    /// Walk the entries following a NumInGroup field; `it` is left after the last entry.
+    #[allow(clippy::too_many_arguments)]
+    fn check_group(
+        &self,
+        it: &mut TagValueIter<'_>,
+        entry: &Scope,
+        delimiter: u32,
+        count_offset: usize,
+        count_tag: u32,
+        count: u32,
+        report: &mut Report,
+    ) -> Result<(), Stop> {
+        let mut actual = 0;
+        while let Ok(Some((tag, _))) = it.clone().try_next() {
+            if tag != delimiter {
+                break;
+            }
+            actual += 1;
+            let mut seen = BTreeSet::new();
+            let mut first = true;
+            loop {
+                let mut peek = it.clone();
+                let offset = peek.position();
+                let Ok(Some((tag, value))) = peek.try_next() else {
+                    break;
+                };
+                if (tag == delimiter && !first) || !entry.slots.contains_key(&tag) {
+                    break;
+                }
+                first = false;
+                *it = peek;
+                self.check_field(entry, &mut seen, it, offset, tag, value, report)?;
+            }
+            check_required(entry, &seen, report)?;
+        }
+        if actual != count {
+            report.push(ReadError::GroupCountMismatch {
+                offset: count_offset,
+                tag: count_tag,
+                expected: count,
+                actual,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn check_required(
+    scope: &Scope,
+    seen: &BTreeSet<u32>,
+    report: &mut Report,
+) -> Result<(), Stop> {
+    for &tag in &scope.required {
+        if !seen.contains(&tag) {
+            report.push(missing_tag(tag))?;
+        }
+    }
+    Ok(())
+}
+
+/// Missing required field; the dictionary has no static member metadata.
+fn missing_tag(tag: u32) -> ReadError {
+    ReadError::MissingRequiredFields {
+        missing_mask: MissingMask::new(),
+        meta: &[],
+        tag: Some(tag),
+    }
+}
+
+/// Message section, in wire order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Header = 0,
+    Body = 1,
+    Trailer = 2,
+}
+
+/// Collected errors; `push` stops the walk when only the first is wanted.
+struct Report {
+    errors: Vec<ReadError>,
+    first_only: bool,
+}
+
+/// Marker returned to unwind the walk early.
+struct Stop;
+
+impl Report {
+    fn push(
+        &mut self,
+        error: ReadError,
+    ) -> Result<(), Stop> {
+        self.errors.push(error);
+        if self.first_only { Err(Stop) } else { Ok(()) }
+    }
+
+    fn fatal(
+        &mut self,
+        error: ReadError,
+    ) -> Result<(), Stop> {
+        self.errors.push(error);
+        Err(Stop)
+    }
+}
+
+/// Tags allowed at one level (message body, header, trailer or group entry).
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    slots: BTreeMap<u32, Slot>,
+    /// Required tags in dictionary order
+    required: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+enum Slot {
+    Field,
+    Group { delimiter: u32, entry: Box<Scope> },
+}
+
+impl Scope {
+    fn build(
+        dict: &Dictionary,
+        members: &[Member],
+    ) -> Scope {
+        let mut scope = Scope::default();
+        scope.add(dict, members, true);
+        scope
+    }
+
+    /// Flatten `members` (components inline) into this scope.
+    fn add(
+        &mut self,
+        dict: &Dictionary,
+        members: &[Member],
+        parent_required: bool,
+    ) {
+        for member in members {
+            let required = parent_required && member.required;
+            if member.kind == MemberKind::Component {
+                let component = dict
+                    .component(&member.name)
+                    .expect("dictionary checks component references");
+                self.add(dict, &component.members, required);
+                continue;
+            }
+            let field = dict
+                .field_by_name(&member.name)
+                .expect("dictionary checks field references");
+            let slot = match first_tag(dict, &member.members) {
+                Some(delimiter) if member.kind == MemberKind::Group => Slot::Group {
+                    delimiter,
+                    entry: Box::new(Scope::build(dict, &member.members)),
+                },
+                _ => Slot::Field,
+            };
+            self.slots.insert(field.number, slot);
+            if required {
+                self.required.push(field.number);
+            }
+        }
+    }
+}
+
+/// Tag of the first field in `members`, looking through components.
+pub(crate) fn first_tag(
+    dict: &Dictionary,
+    members: &[Member],
+) -> Option<u32> {
+    let member = members.first()?;
+    match member.kind {
+        MemberKind::Component => first_tag(dict, &dict.component(&member.name)?.members),
+        MemberKind::Field | MemberKind::Group => dict
+            .field_by_name(&member.name)
+            .map(|field| field.number),
+    }
+}
+
+/// Whether `value` is well formed for `field_type`.
+fn format_ok(
+    field_type: FieldType,
+    value: &[u8],
+) -> bool {
+    // The validator only needs a yes/no, so the readers' error details are dropped
+    match field_type {
+        FieldType::Int => try_read_i64(value, "", 0).is_ok(),
+        FieldType::Length
+        | FieldType::NumInGroup
+        | FieldType::SeqNum
+        | FieldType::TagNum
+        | FieldType::DayOfMonth => try_read_u64(value, "", 0).is_ok(),
+        t if t.is_float() => try_read_f64(value, "", 0).is_ok(),
+        FieldType::Char => value.len() == 1 && value[0].is_ascii_graphic(),
+        FieldType::Boolean => try_read_bool(value, "", 0).is_ok(),
+        FieldType::MultipleCharValue => {
+            let mut chars = read_multi_char(value);
+            is_space_separated(value)
+                && chars.all(|c| c.is_ascii_graphic())
+                && chars.remainder().is_empty()
+        }
+        FieldType::MultipleValueString | FieldType::MultipleStringValue => {
+            let mut strs = read_multi_str(value);
+            strs.by_ref().for_each(drop);
+            is_space_separated(value) && strs.remainder().is_empty()
+        }
+        FieldType::UtcTimestamp => is_utc_timestamp(value),
+        FieldType::UtcTimeOnly => is_time(value),
+        FieldType::UtcDateOnly | FieldType::LocalMktDate => is_date(value),
+        FieldType::MonthYear => is_month_year(value),
+        FieldType::Currency => value.len() == 3 && value.iter().all(u8::is_ascii_alphanumeric),
+        FieldType::Data | FieldType::XmlData => true,
+        _ => try_read_str(value, "", 0).is_ok(),
+    }
+}
+
+fn is_digits(value: &[u8]) -> bool {
+    !value.is_empty() && value.iter().all(u8::is_ascii_digit)
+}
+
+/// Values separated by single spaces, with no leading or trailing space.
+///
+/// The multi-value readers skip repeated spaces; the wire format does not
+/// allow them.
+fn is_space_separated(value: &[u8]) -> bool {
+    !value
+        .split(|&b| b == b' ')
+        .any(<[u8]>::is_empty)
+}
+
+/// Two ASCII digits as a number.
+fn two_digits(value: &[u8]) -> Option<u8> {
+    match value {
+        [a @ b'0'..=b'9', b @ b'0'..=b'9'] => Some((a - b'0') * 10 + (b - b'0')),
+        _ => None,
+    }
+}
+
+/// `YYYYMMDD`
+fn is_date(value: &[u8]) -> bool {
+    value.len() == 8
+        && is_digits(&value[..4])
+        && two_digits(&value[4..6]).is_some_and(|m| (1..=12).contains(&m))
+        && two_digits(&value[6..8]).is_some_and(|d| (1..=31).contains(&d))
+}
+
+/// `YYYYMM`, `YYYYMMDD` or `YYYYMMwN` (week 1-5).
+fn is_month_year(value: &[u8]) -> bool {
+    if value.len() < 6
+        || !is_digits(&value[..4])
+        || !two_digits(&value[4..6]).is_some_and(|m| (1..=12).contains(&m))
+    {
+        return false;
+    }
+    match &value[6..] {
+        [] | [b'w', b'1'..=b'5'] => true,
+        [_, _] => is_date(value),
+        _ => false,
+    }
+}
+
+/// `HH:MM:SS` with an optional fraction of 1-9 digits.
+fn is_time(value: &[u8]) -> bool {
+    if value.len() < 8 || value[2] != b':' || value[5] != b':' {
+        return false;
+    }
+    let fraction_ok = match &value[8..] {
+        [] => true,
+        [b'.', digits @ ..] => digits.len() <= 9 && is_digits(digits),
+        _ => false,
+    };
+    two_digits(&value[0..2]).is_some_and(|h| h < 24)
+        && two_digits(&value[3..5]).is_some_and(|m| m < 60)
+        && two_digits(&value[6..8]).is_some_and(|s| s <= 60)
+        && fraction_ok
+}
+
+/// `YYYYMMDD-HH:MM:SS[.fraction]`
+fn is_utc_timestamp(value: &[u8]) -> bool {
+    value.len() >= 17 && value[8] == b'-' && is_date(&value[..8]) && is_time(&value[9..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DICT: &str = r#"<fix major="4" minor="4">
+      <header>
+        <field name="BeginString" required="Y"/>
+        <field name="BodyLength" required="Y"/>
+        <field name="MsgType" required="Y"/>
+      </header>
+      <trailer><field name="CheckSum" required="Y"/></trailer>
+      <messages>
+        <message name="Order" msgtype="D">
+          <field name="Side" required="Y"/>
+          <field name="Qty" required="N"/>
+          <group name="NoParties" required="N">
+            <field name="PartyID" required="Y"/>
+            <field name="PartyRole" required="N"/>
+          </group>
+        </message>
+      </messages>
+      <fields>
+        <field number="8" name="BeginString" type="STRING"/>
+        <field number="9" name="BodyLength" type="LENGTH"/>
+        <field number="10" name="CheckSum" type="STRING"/>
+        <field number="35" name="MsgType" type="STRING"/>
+        <field number="38" name="Qty" type="QTY"/>
+        <field number="54" name="Side" type="CHAR">
+          <value enum="1" description="BUY"/>
+          <value enum="2" description="SELL"/>
+        </field>
+        <field number="448" name="PartyID" type="STRING"/>
+        <field number="452" name="PartyRole" type="INT"/>
+        <field number="453" name="NoParties" type="NUMINGROUP"/>
+      </fields>
+    </fix>"#;
+
+    fn validate_all(
+        settings: ValidatorSettings,
+        body: &str,
+    ) -> Vec<ReadError> {
+        let dict = Dictionary::parse(DICT).unwrap();
+        let msg = alloc::format!("8=FIX.4.4\x019=10\x0135=D\x01{body}10=000\x01").replace('|', "\x01");
+        Validator::with_settings(&dict, settings).validate_all(msg.as_bytes())
+    }
+
+    #[test]
+    fn test_valid_message() {
+        let errors = validate_all(
+            ValidatorSettings::default(),
+            "54=1|38=100.5|453=2|448=A|452=1|448=B|",
+        );
+        assert_eq!(errors, []);
+    }
+
+    #[test]
+    fn test_collects_all_errors() {
+        let errors = validate_all(
+            ValidatorSettings::default(),
+            "54=7|38=1x|38=2|9000=u|453=2|452=1|448=A|",
+        );
+        assert_eq!(
+            errors,
+            [
+                ReadError::InvalidValue {
+                    name: "",
+                    tag: 54,
+                    msg: "value is not one of the field's enumerated values",
+                    offset: Some(20),
+                },
+                ReadError::InvalidFormat {
+                    name: "",
+                    tag: 38,
+                    msg: "value does not parse as the field's type",
+                    offset: Some(25),
+                },
+                ReadError::DuplicateTag { offset: 31, tag: 38 },
+                ReadError::UnknownTag { offset: 36, tag: 9000 },
+                ReadError::GroupCountMismatch {
+                    offset: 43,
+                    tag: 453,
+                    expected: 2,
+                    actual: 0,
+                },
+                ReadError::TagNotDefinedForMessage { offset: 49, tag: 452 },
+                ReadError::TagNotDefinedForMessage { offset: 55, tag: 448 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_order_and_required() {
+        let dict = Dictionary::parse(DICT).unwrap();
+        let validator = Validator::new(&dict);
+        assert_eq!(
+            validator.validate_all(b"9=10\x018=FIX.4.4\x0135=D\x0110=000\x0138=1\x01"),
+            [
+                ReadError::TagOutOfOrder { offset: 0, tag: 9 },
+                ReadError::TagOutOfOrder { offset: 5, tag: 8 },
+                ReadError::TagOutOfOrder { offset: 27, tag: 38 },
+                missing_tag(54),
+            ]
+        );
+        assert_eq!(
+            validator.validate(b"8=FIX.4.4\x019=10\x0135=Z\x0110=000\x01"),
+            Err(ReadError::InvalidMsgType { offset: 15 })
+        );
+        assert_eq!(
+            validator.validate(b"8=FIX.4.4\x019=10\x0154=1\x0110=000\x01"),
+            Err(missing_tag(35))
+        );
+        assert_eq!(
+            validator.validate(b"8=FIX.4.4\x019=10\x0135=D\x0154=\x0110=000\x01"),
+            Err(ReadError::EmptyValue { offset: 20, tag: 54 })
+        );
+    }
+
+    #[test]
+    fn test_settings() {
+        let lenient = ValidatorSettings {
+            ignore_unknown_tags: true,
+            ignore_user_defined_fields: true,
+            check_field_order: false,
+            check_field_values: false,
+        };
+        assert_eq!(validate_all(lenient, "54=7|9000=u|7777=x|"), []);
+        let user_defined_only = ValidatorSettings {
+            ignore_user_defined_fields: true,
+            ..ValidatorSettings::default()
+        };
+        assert_eq!(
+            validate_all(user_defined_only, "54=1|9000=u|4999=x|"),
+            [ReadError::UnknownTag { offset: 32, tag: 4999 }]
+        );
+    }
+
+    #[test]
+    fn test_format_checks() {
+        for ok in [&b"-1.5"[..], b"1.", b".5", b"42"] {
+            assert!(format_ok(FieldType::Price, ok));
+        }
+        for bad in [&b"."[..], b"1.2.3", b"1e5", b"-"] {
+            assert!(!format_ok(FieldType::Qty, bad));
+        }
+        assert!(format_ok(FieldType::Int, b"-12") && !format_ok(FieldType::SeqNum, b"-12"));
+        assert!(!format_ok(FieldType::Int, b""));
+        assert!(!format_ok(FieldType::Int, b"99999999999999999999"));
+        assert!(is_utc_timestamp(b"20240229-23:59:60.123456789"));
+        assert!(is_utc_timestamp(b"20240101-00:00:00"));
+        assert!(!is_utc_timestamp(b"20241301-00:00:00"));
+        assert!(!is_utc_timestamp(b"20240101-24:00:00"));
+        assert!(!is_utc_timestamp(b"20240101-00:00:00."));
+        assert!(is_date(b"20240131") && !is_date(b"2024013"));
+        assert!(format_ok(FieldType::MultipleCharValue, b"A B C"));
+        assert!(!format_ok(FieldType::MultipleCharValue, b"A BC"));
+        assert!(!format_ok(FieldType::MultipleCharValue, b"A  B"));
+        assert!(!format_ok(FieldType::MultipleStringValue, b"A \xff"));
+        assert!(!format_ok(FieldType::MultipleStringValue, b" A"));
+        assert!(format_ok(FieldType::MultipleStringValue, b"A AB"));
+        assert!(format_ok(FieldType::Boolean, b"N"));
+        assert!(!format_ok(FieldType::Boolean, b"y"));
+        assert!(!format_ok(FieldType::String, b"\xff"));
+
+        for ok in [&b"202401"[..], b"20240131", b"202412w1", b"202412w5"] {
+            assert!(format_ok(FieldType::MonthYear, ok));
+        }
+        for bad in [
+            &b"2024"[..],
+            b"202413",
+            b"202400",
+            b"2024011",
+            b"20240132",
+            b"202401w6",
+            b"202401W1",
+            b"202401xx",
+            b"202401011",
+        ] {
+            assert!(!format_ok(FieldType::MonthYear, bad));
+        }
+    }
+}
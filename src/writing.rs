@@ -98,7 +98,7 @@ fn digits_u16(n: u16) -> usize {
 
 /// Calculate the decimal digit length of a u32
 #[inline(always)]
-fn digits_u32(n: u32) -> usize {
+const fn digits_u32(n: u32) -> usize {
     if n >= 1_000_000_000 {
         10
     } else if n >= 100_000_000 {
@@ -450,6 +450,26 @@ fn write_frac15_from_u128(mut v: u128, buf: &mut [u8], pos: usize) -> usize {
     15
 }
 
+/// `x.round() as u64` for non-negative inputs, without needing `std`.
+///
+/// Rounds half away from zero; saturates like an `as` cast.
+#[inline(always)]
+fn round_non_negative_f32(x: f32) -> u64 {
+    let t = x as u64;
+    // `t as f32` is exact: either x < 2^24 or x is already integral
+    if x - t as f32 >= 0.5 { t.saturating_add(1) } else { t }
+}
+
+/// `x.round() as u128` for non-negative inputs, without needing `std`.
+///
+/// Rounds half away from zero; NaN and negative inputs yield 0.
+#[inline(always)]
+fn round_non_negative_f64(x: f64) -> u128 {
+    let t = x as u128;
+    // `t as f64` is exact: either x < 2^53 or x is already integral
+    if x - t as f64 >= 0.5 { t.saturating_add(1) } else { t }
+}
+
 /// Write an f32 with up to 6 decimal places.
 ///
 /// - Scales by 1e6, rounds to nearest-even, splits integer/fraction
@@ -472,7 +492,7 @@ pub fn write_f32(buf: &mut [u8], offset: usize, n: f32) -> usize {
         pos += 1;
     }
 
-    let scaled = (x * SCALE_F32).round() as u64;
+    let scaled = round_non_negative_f32(x * SCALE_F32);
     let int_part = scaled / 1_000_000;
     let frac = (scaled % 1_000_000) as u32;
 
@@ -522,8 +542,7 @@ pub fn write_f64(buf: &mut [u8], offset: usize, n: f64) -> usize {
         pos += 1;
     }
 
-    let scaled = (x * SCALE_F64).round();
-    let scaled_u128 = if scaled <= 0.0 { 0u128 } else { scaled as u128 };
+    let scaled_u128 = round_non_negative_f64(x * SCALE_F64);
 
     let int_part = scaled_u128 / 1_000_000_000_000_000u128;
     let frac_u128 = scaled_u128 % 1_000_000_000_000_000u128;
@@ -768,6 +787,416 @@ pub fn write_tag_and_f64(bytes: &mut [u8], offset: usize, tag_and_eq: &[u8], val
     pos + 1
 }
 
+/// Write a FIX tag, equals sign, single-character value, and SOH delimiter.
+///
+/// For `char` fields such as Side(54), OrdType(40) and TimeInForce(59).
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::write_tag_and_char;
+/// let mut buf = [0u8; 20];
+/// let written = write_tag_and_char(&mut buf, 0, b"54=", b'1');
+/// assert_eq!(&buf[..written], b"54=1\x01");
+/// ```
+#[inline(always)]
+pub fn write_tag_and_char(bytes: &mut [u8], offset: usize, tag_and_eq: &[u8], value: u8) -> usize {
+    unsafe {
+        ptr::copy_nonoverlapping(
+            tag_and_eq.as_ptr(),
+            bytes.as_mut_ptr().add(offset),
+            tag_and_eq.len(),
+        );
+    }
+    let pos = tag_and_eq.len();
+    unsafe {
+        let ptr = bytes.as_mut_ptr().add(offset + pos);
+        *ptr = value;
+        *ptr.add(1) = 0x01;
+    }
+    pos + 2
+}
+
+/// Write space-separated characters starting at offset, returns bytes written.
+#[inline(always)]
+fn write_multi_char(bytes: &mut [u8], offset: usize, values: &[u8]) -> usize {
+    let mut pos = 0;
+    for (i, &c) in values.iter().enumerate() {
+        if i != 0 {
+            unsafe {
+                *bytes.get_unchecked_mut(offset + pos) = b' ';
+            }
+            pos += 1;
+        }
+        unsafe {
+            *bytes.get_unchecked_mut(offset + pos) = c;
+        }
+        pos += 1;
+    }
+    pos
+}
+
+/// Write space-separated strings starting at offset, returns bytes written.
+#[inline(always)]
+fn write_multi_str(bytes: &mut [u8], offset: usize, values: &[&str]) -> usize {
+    let mut pos = 0;
+    for (i, v) in values.iter().enumerate() {
+        if i != 0 {
+            unsafe {
+                *bytes.get_unchecked_mut(offset + pos) = b' ';
+            }
+            pos += 1;
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(v.as_ptr(), bytes.as_mut_ptr().add(offset + pos), v.len());
+        }
+        pos += v.len();
+    }
+    pos
+}
+
+/// Write a FIX tag, equals sign, space-separated characters, and SOH delimiter.
+///
+/// For `MultipleCharValue` fields such as ExecInst(18).
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::write_tag_and_multi_char;
+/// let mut buf = [0u8; 20];
+/// let written = write_tag_and_multi_char(&mut buf, 0, b"18=", b"1G");
+/// assert_eq!(&buf[..written], b"18=1 G\x01");
+/// ```
+#[inline(always)]
+pub fn write_tag_and_multi_char(
+    bytes: &mut [u8],
+    offset: usize,
+    tag_and_eq: &[u8],
+    values: &[u8],
+) -> usize {
+    unsafe {
+        ptr::copy_nonoverlapping(
+            tag_and_eq.as_ptr(),
+            bytes.as_mut_ptr().add(offset),
+            tag_and_eq.len(),
+        );
+    }
+    let mut pos = tag_and_eq.len();
+    pos += write_multi_char(bytes, offset + pos, values);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a FIX tag, equals sign, space-separated strings, and SOH delimiter.
+///
+/// For `MultipleStringValue` fields such as QuoteCondition(276).
+/// Values must not contain spaces or SOH.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::write_tag_and_multi_str;
+/// let mut buf = [0u8; 20];
+/// let written = write_tag_and_multi_str(&mut buf, 0, b"276=", &["A", "AB"]);
+/// assert_eq!(&buf[..written], b"276=A AB\x01");
+/// ```
+#[inline(always)]
+pub fn write_tag_and_multi_str(
+    bytes: &mut [u8],
+    offset: usize,
+    tag_and_eq: &[u8],
+    values: &[&str],
+) -> usize {
+    unsafe {
+        ptr::copy_nonoverlapping(
+            tag_and_eq.as_ptr(),
+            bytes.as_mut_ptr().add(offset),
+            tag_and_eq.len(),
+        );
+    }
+    let mut pos = tag_and_eq.len();
+    pos += write_multi_str(bytes, offset + pos, values);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a length-prefixed `data` field pair: `len_tag=<len>\x01data_tag=<data>\x01`.
+///
+/// For `data` fields such as RawData(96), XmlData(213) and EncodedText(355), whose
+/// value may contain SOH and must be preceded by its length field. Both fields are
+/// emitted together so the length always matches the payload.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::write_tag_and_data;
+/// let mut buf = [0u8; 32];
+/// let written = write_tag_and_data(&mut buf, 0, b"95=", b"96=", b"a\x01b");
+/// assert_eq!(&buf[..written], b"95=3\x0196=a\x01b\x01");
+/// ```
+#[inline(always)]
+pub fn write_tag_and_data(
+    bytes: &mut [u8],
+    offset: usize,
+    len_tag_and_eq: &[u8],
+    data_tag_and_eq: &[u8],
+    data: &[u8],
+) -> usize {
+    let pos = write_tag_and_u32(bytes, offset, len_tag_and_eq, data.len() as u32);
+    pos + write_tag_and_bytes(bytes, offset + pos, data_tag_and_eq, data)
+}
+
+/// Length of the rendered `tag=` prefix for a numeric tag (e.g. 5001 -> 5 for `"5001="`).
+///
+/// Intended for sizing the array returned by [`tag_prefix`] in a `const` context.
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::tag_prefix_len;
+/// assert_eq!(tag_prefix_len(1), 2);
+/// assert_eq!(tag_prefix_len(5001), 5);
+/// ```
+#[inline(always)]
+pub const fn tag_prefix_len(tag: u32) -> usize {
+    digits_u32(tag) + 1
+}
+
+/// Render a numeric tag into a `tag=` prefix at compile time.
+///
+/// `N` must equal [`tag_prefix_len(tag)`](tag_prefix_len); a mismatch fails const
+/// evaluation (or panics when called at runtime).
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::*;
+/// const MY_TAG: [u8; tag_prefix_len(5001)] = tag_prefix(5001);
+/// assert_eq!(&MY_TAG, b"5001=");
+///
+/// let mut buf = [0u8; 20];
+/// let written = write_tag_and_u32(&mut buf, 0, &MY_TAG, 42);
+/// assert_eq!(&buf[..written], b"5001=42\x01");
+/// ```
+pub const fn tag_prefix<const N: usize>(tag: u32) -> [u8; N] {
+    assert!(
+        N == tag_prefix_len(tag),
+        "tag_prefix: N must equal tag_prefix_len(tag)"
+    );
+    let mut out = [0u8; N];
+    out[N - 1] = b'=';
+    let mut n = tag;
+    let mut i = N - 1;
+    while i > 0 {
+        i -= 1;
+        out[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+    out
+}
+
+/// Write a numeric tag followed by '=' at offset, returns bytes written.
+#[inline(always)]
+fn write_tag_num(bytes: &mut [u8], offset: usize, tag: u32) -> usize {
+    let len = write_u32(bytes, offset, tag);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + len) = b'=';
+    }
+    len + 1
+}
+
+/// Write a numeric FIX tag, equals sign, boolean value, and SOH delimiter.
+///
+/// Same output as [`write_tag_and_bool`], for tags only known at runtime
+/// (dictionary-driven or user-defined tags).
+///
+/// # Example
+/// ```
+/// # use fix44_forge_helpers::write_tag_num_and_bool;
+/// let mut buf = [0u8; 20];
+/// let written = write_tag_num_and_bool(&mut buf, 0, 5001, true);
+/// assert_eq!(&buf[..written], b"5001=Y\x01");
+/// ```
+#[inline(always)]
+pub fn write_tag_num_and_bool(bytes: &mut [u8], offset: usize, tag: u32, value: bool) -> usize {
+    let pos = write_tag_num(bytes, offset, tag);
+    unsafe {
+        let ptr = bytes.as_mut_ptr().add(offset + pos);
+        *ptr = if value { b'Y' } else { b'N' };
+        *ptr.add(1) = 0x01;
+    }
+    pos + 2
+}
+
+/// Write a numeric FIX tag, equals sign, byte slice value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_bytes(bytes: &mut [u8], offset: usize, tag: u32, value: &[u8]) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    unsafe {
+        ptr::copy_nonoverlapping(
+            value.as_ptr(),
+            bytes.as_mut_ptr().add(offset + pos),
+            value.len(),
+        );
+    }
+    pos += value.len();
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, string value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_str(bytes: &mut [u8], offset: usize, tag: u32, value: &str) -> usize {
+    write_tag_num_and_bytes(bytes, offset, tag, value.as_bytes())
+}
+
+/// Write a numeric FIX tag, equals sign, single-character value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_char(bytes: &mut [u8], offset: usize, tag: u32, value: u8) -> usize {
+    let pos = write_tag_num(bytes, offset, tag);
+    unsafe {
+        let ptr = bytes.as_mut_ptr().add(offset + pos);
+        *ptr = value;
+        *ptr.add(1) = 0x01;
+    }
+    pos + 2
+}
+
+/// Write a numeric FIX tag, equals sign, space-separated characters, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_multi_char(
+    bytes: &mut [u8],
+    offset: usize,
+    tag: u32,
+    values: &[u8],
+) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_multi_char(bytes, offset + pos, values);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, space-separated strings, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_multi_str(
+    bytes: &mut [u8],
+    offset: usize,
+    tag: u32,
+    values: &[&str],
+) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_multi_str(bytes, offset + pos, values);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a length-prefixed `data` field pair using numeric tags.
+///
+/// Same output as [`write_tag_and_data`].
+#[inline(always)]
+pub fn write_tag_num_and_data(
+    bytes: &mut [u8],
+    offset: usize,
+    len_tag: u32,
+    data_tag: u32,
+    data: &[u8],
+) -> usize {
+    let pos = write_tag_num_and_u32(bytes, offset, len_tag, data.len() as u32);
+    pos + write_tag_num_and_bytes(bytes, offset + pos, data_tag, data)
+}
+
+/// Write a numeric FIX tag, equals sign, u16 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_u16(bytes: &mut [u8], offset: usize, tag: u32, value: u16) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_u16(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, u32 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_u32(bytes: &mut [u8], offset: usize, tag: u32, value: u32) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_u32(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, u64 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_u64(bytes: &mut [u8], offset: usize, tag: u32, value: u64) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_u64(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, i16 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_i16(bytes: &mut [u8], offset: usize, tag: u32, value: i16) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_i16(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, i32 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_i32(bytes: &mut [u8], offset: usize, tag: u32, value: i32) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_i32(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, i64 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_i64(bytes: &mut [u8], offset: usize, tag: u32, value: i64) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_i64(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, f32 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_f32(bytes: &mut [u8], offset: usize, tag: u32, value: f32) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_f32(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
+/// Write a numeric FIX tag, equals sign, f64 value, and SOH delimiter.
+#[inline(always)]
+pub fn write_tag_num_and_f64(bytes: &mut [u8], offset: usize, tag: u32, value: f64) -> usize {
+    let mut pos = write_tag_num(bytes, offset, tag);
+    pos += write_f64(bytes, offset + pos, value);
+    unsafe {
+        *bytes.get_unchecked_mut(offset + pos) = 0x01;
+    }
+    pos + 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -837,4 +1266,83 @@ mod tests {
         let written = write_tag_and_u32(&mut buf, 0, b"34=", 12345);
         assert_eq!(&buf[..written], b"34=12345\x01");
     }
+
+    #[test]
+    fn test_tag_prefix() {
+        const P1: [u8; tag_prefix_len(1)] = tag_prefix(1);
+        const P34: [u8; tag_prefix_len(34)] = tag_prefix(34);
+        const P5001: [u8; tag_prefix_len(5001)] = tag_prefix(5001);
+        assert_eq!(&P1, b"1=");
+        assert_eq!(&P34, b"34=");
+        assert_eq!(&P5001, b"5001=");
+        assert_eq!(tag_prefix_len(u32::MAX), 11);
+        assert_eq!(&tag_prefix::<11>(u32::MAX), b"4294967295=");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tag_prefix_wrong_len() {
+        let _ = tag_prefix::<3>(5001);
+    }
+
+    #[test]
+    fn test_write_tag_num_matches_static_prefix() {
+        let mut a = [0u8; 64];
+        let mut b = [0u8; 64];
+
+        let wa = write_tag_num_and_u32(&mut a, 0, 34, 12345);
+        let wb = write_tag_and_u32(&mut b, 0, b"34=", 12345);
+        assert_eq!(&a[..wa], &b[..wb]);
+
+        let wa = write_tag_num_and_i64(&mut a, 0, 5001, -42);
+        assert_eq!(&a[..wa], b"5001=-42\x01");
+
+        let wa = write_tag_num_and_f64(&mut a, 0, 44, 123.45);
+        assert_eq!(&a[..wa], b"44=123.45\x01");
+
+        let wa = write_tag_num_and_str(&mut a, 0, 9, "X");
+        assert_eq!(&a[..wa], b"9=X\x01");
+
+        let wa = write_tag_num_and_bool(&mut a, 0, 43, false);
+        assert_eq!(&a[..wa], b"43=N\x01");
+    }
+
+    #[test]
+    fn test_write_tag_and_char() {
+        let mut buf = [0u8; 20];
+        let written = write_tag_and_char(&mut buf, 0, b"40=", b'2');
+        assert_eq!(&buf[..written], b"40=2\x01");
+
+        let written = write_tag_num_and_char(&mut buf, 0, 59, b'3');
+        assert_eq!(&buf[..written], b"59=3\x01");
+    }
+
+    #[test]
+    fn test_write_tag_and_multi_values() {
+        let mut buf = [0u8; 32];
+        let written = write_tag_and_multi_char(&mut buf, 0, b"18=", b"12G");
+        assert_eq!(&buf[..written], b"18=1 2 G\x01");
+
+        let written = write_tag_and_multi_char(&mut buf, 0, b"18=", b"");
+        assert_eq!(&buf[..written], b"18=\x01");
+
+        let written = write_tag_and_multi_str(&mut buf, 0, b"276=", &["A", "B", "AB"]);
+        assert_eq!(&buf[..written], b"276=A B AB\x01");
+
+        let written = write_tag_num_and_multi_str(&mut buf, 0, 276, &["C"]);
+        assert_eq!(&buf[..written], b"276=C\x01");
+
+        let written = write_tag_num_and_multi_char(&mut buf, 0, 18, b"6E");
+        assert_eq!(&buf[..written], b"18=6 E\x01");
+    }
+
+    #[test]
+    fn test_write_tag_and_data() {
+        let mut buf = [0u8; 64];
+        let written = write_tag_and_data(&mut buf, 0, b"212=", b"213=", b"<x/>");
+        assert_eq!(&buf[..written], b"212=4\x01213=<x/>\x01");
+
+        let written = write_tag_num_and_data(&mut buf, 0, 354, 355, b"\x01\x01");
+        assert_eq!(&buf[..written], b"354=2\x01355=\x01\x01\x01");
+    }
 }
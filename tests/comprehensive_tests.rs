@@ -16,6 +16,7 @@ mod reading {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn read_bool_variants() {
         assert_eq!(read_bool(b"Y"), true);
         assert_eq!(read_bool(b"N"), false);
@@ -24,6 +25,23 @@ mod reading {
         assert_eq!(read_bool(b"1"), false); // Only Y is true
     }
 
+    #[test]
+    fn try_read_bool_strict_and_lenient() {
+        assert!(try_read_bool(b"Y", "GapFillFlag", 123).unwrap());
+        assert!(!try_read_bool(b"N", "GapFillFlag", 123).unwrap());
+        assert!(try_read_bool(b"1", "GapFillFlag", 123).is_err());
+        assert!(try_read_bool(b"", "GapFillFlag", 123).is_err());
+
+        assert!(try_read_bool_lenient(b"1", "GapFillFlag", 123).unwrap());
+        assert!(!try_read_bool_lenient(b"0", "GapFillFlag", 123).unwrap());
+
+        let err = try_read_bool(b"Z", "GapFillFlag", 123).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid value for GapFillFlag (tag=123): expected Y or N"
+        );
+    }
+
     #[test]
     fn read_str_basic() {
         assert_eq!(read_str(b"hello"), "hello");
@@ -31,6 +49,16 @@ mod reading {
         assert_eq!(read_str(b"FIX.4.4"), "FIX.4.4");
     }
 
+    #[test]
+    fn validated_str_reading() {
+        assert_eq!(read_ascii_str(b"FIX.4.4", "BeginString", 8).unwrap(), "FIX.4.4");
+        assert!(read_ascii_str(b"FIX\xa04.4", "BeginString", 8).is_err());
+
+        assert_eq!(try_read_str("naïve".as_bytes(), "Text", 58).unwrap(), "naïve");
+        let err = try_read_str(b"bad\xc0\xaf", "Text", 58).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid format for Text (tag=58): invalid UTF-8");
+    }
+
     #[test]
     fn read_u16_variants() {
         assert_eq!(read_u16(b"0"), 0);
@@ -85,6 +113,7 @@ mod reading {
     }
 
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn read_f32_variants() {
         assert_eq!(read_f32(b"0"), 0.0);
         assert_eq!(read_f32(b"1.5"), 1.5);
@@ -98,6 +127,7 @@ mod reading {
     }
 
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn read_f64_variants() {
         assert_eq!(read_f64(b"0"), 0.0);
         assert_eq!(read_f64(b"1.5"), 1.5);
@@ -462,6 +492,68 @@ mod tag_writing {
             assert!(!content.contains('e') && !content.contains('E'));
         }
     }
+
+    #[test]
+    fn write_tag_num_variants_match_prefix_writers() {
+        let mut a = [0u8; 64];
+        let mut b = [0u8; 64];
+
+        let la = write_tag_num_and_u16(&mut a, 0, 34, 65535);
+        let lb = write_tag_and_u16(&mut b, 0, b"34=", 65535);
+        assert_eq!(&a[..la], &b[..lb]);
+
+        let la = write_tag_num_and_u64(&mut a, 0, 38, u64::MAX);
+        let lb = write_tag_and_u64(&mut b, 0, b"38=", u64::MAX);
+        assert_eq!(&a[..la], &b[..lb]);
+
+        let la = write_tag_num_and_i16(&mut a, 0, 5001, i16::MIN);
+        assert_eq!(&a[..la], b"5001=-32768\x01");
+
+        let la = write_tag_num_and_i32(&mut a, 0, 20000, -1);
+        assert_eq!(&a[..la], b"20000=-1\x01");
+
+        let la = write_tag_num_and_f32(&mut a, 0, 44, 1.5);
+        assert_eq!(&a[..la], b"44=1.5\x01");
+
+        let la = write_tag_num_and_bytes(&mut a, 0, 128, b"HELLO");
+        assert_eq!(&a[..la], b"128=HELLO\x01");
+    }
+
+    #[test]
+    fn write_tag_num_at_offset() {
+        let mut buf = [0u8; 64];
+        let mut pos = 0;
+        pos += write_tag_num_and_str(&mut buf, pos, 55, "EURUSD");
+        pos += write_tag_num_and_u32(&mut buf, pos, 5001, 7);
+        assert_eq!(&buf[..pos], b"55=EURUSD\x015001=7\x01");
+    }
+
+    #[test]
+    fn tag_prefix_const() {
+        const USER_TAG: [u8; tag_prefix_len(9876)] = tag_prefix(9876);
+        let mut buf = [0u8; 32];
+        let len = write_tag_and_str(&mut buf, 0, &USER_TAG, "X");
+        assert_eq!(&buf[..len], b"9876=X\x01");
+    }
+
+    #[test]
+    fn char_and_multi_value_roundtrip() {
+        let mut buf = [0u8; 64];
+
+        let len = write_tag_and_char(&mut buf, 0, b"54=", b'2');
+        assert_eq!(&buf[..len], b"54=2\x01");
+        assert_eq!(read_char(&buf[3..len - 1]), b'2');
+
+        let exec_inst = b"1G6";
+        let len = write_tag_and_multi_char(&mut buf, 0, b"18=", exec_inst);
+        assert_eq!(&buf[..len], b"18=1 G 6\x01");
+        assert!(read_multi_char(&buf[3..len - 1]).eq(exec_inst.iter().copied()));
+
+        let quote_cond = ["A", "B", "AB"];
+        let len = write_tag_and_multi_str(&mut buf, 0, b"276=", &quote_cond);
+        assert_eq!(&buf[..len], b"276=A B AB\x01");
+        assert!(read_multi_str(&buf[4..len - 1]).eq(quote_cond));
+    }
 }
 
 /// Test module for special functions
@@ -495,6 +587,7 @@ mod special_functions {
     }
 
     #[test]
+    #[allow(clippy::manual_is_ascii_check)]
     fn encode_base36_fixed13_format() {
         let mut buf = [0u8; 32];
 
@@ -519,6 +612,7 @@ mod special_functions {
     }
 
     #[test]
+    #[allow(clippy::manual_is_ascii_check)]
     fn write_tag_and_clordid_format() {
         let mut buf = [0u8; 32];
         let len = write_tag_and_ClOrdID(&mut buf, 0, b"11=");
@@ -594,6 +688,7 @@ mod edge_cases {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn empty_buffer_handling() {
         // Test read functions with empty input
         assert_eq!(read_u16(b""), 0);
@@ -681,6 +776,24 @@ mod edge_cases {
         assert_eq!(read_u32(&buf[20..20 + len2]), 456);
     }
 
+    #[test]
+    fn malformed_wire_data_reports_offset() {
+        let msg = b"8=FIX.4.4\x019=5\x0135=0\x0134x1\x01";
+        let mut iter = TagValueIter::new(msg);
+        let mut fields = 0;
+        let err = loop {
+            match iter.try_next() {
+                Ok(Some(_)) => fields += 1,
+                Ok(None) => panic!("expected an error"),
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(fields, 3);
+        assert_eq!(err, ReadError::MalformedTag { offset: 21, tag: 34 });
+        assert_eq!(err.offset(), Some(21));
+        assert_eq!(&msg[iter.position()..err.offset().unwrap()], b"34");
+    }
+
     #[test]
     fn tag_writing_with_large_values() {
         let mut buf = [0u8; 128];
@@ -693,6 +806,537 @@ mod edge_cases {
     }
 }
 
+/// Test module for the XML data dictionary loader
+mod dictionary {
+    use super::*;
+
+    const FIX44_SUBSET: &str = include_str!("fixtures/FIX44-subset.xml");
+
+    #[test]
+    fn load_fix44_subset() {
+        let dict = Dictionary::parse(FIX44_SUBSET).expect("fixture parses");
+        assert_eq!(dict.begin_string(), "FIX.4.4");
+        assert_eq!(dict.messages().len(), 9);
+        assert_eq!(dict.header().len(), 10);
+        assert_eq!(
+            dict.trailer()
+                .last()
+                .map(|m| m.name.as_str()),
+            Some("CheckSum")
+        );
+
+        let logon = dict.message_by_type("A").unwrap();
+        assert_eq!(logon.category, MessageCategory::Admin);
+        let required: Vec<&str> = logon
+            .members
+            .iter()
+            .filter(|m| m.required)
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(required, ["EncryptMethod", "HeartBtInt"]);
+
+        // Parties -> NoPartyIDs group -> PtysSubGrp component -> NoPartySubIDs group
+        let parties = dict.component("Parties").unwrap();
+        let party_ids = &parties.members[0];
+        assert_eq!(party_ids.kind, MemberKind::Group);
+        let sub = party_ids
+            .members
+            .iter()
+            .find(|m| m.kind == MemberKind::Component)
+            .unwrap();
+        let sub_group = &dict
+            .component(&sub.name)
+            .unwrap()
+            .members[0];
+        assert_eq!(dict.field_by_name(&sub_group.name).unwrap().number, 802);
+
+        let raw_data = dict.field_by_tag(96).unwrap();
+        assert!(raw_data.field_type.is_data());
+        assert_eq!(
+            data_tag_for_length_tag(dict.field_by_name("RawDataLength").unwrap().number),
+            Some(raw_data.number)
+        );
+        assert!(dict.field_by_tag(40).unwrap().allows(b"2"));
+        assert!(!dict.field_by_tag(40).unwrap().allows(b"Z"));
+    }
+
+    #[test]
+    fn rejects_undefined_reference() {
+        let broken = FIX44_SUBSET.replace(
+            r#"<field number="55" name="Symbol" type="STRING"/>"#,
+            "",
+        );
+        assert_eq!(
+            Dictionary::parse(&broken),
+            Err(DictionaryError::UndefinedField {
+                name: "Symbol".into(),
+                parent: "Instrument".into(),
+            })
+        );
+    }
+}
+
+/// Golden-file tests for the dictionary-driven code generator.
+///
+/// `generated/fix44_subset.rs` is the generator output for the fixture dictionary;
+/// run with `UPDATE_GOLDEN=1` to refresh it after changing the generator.
+mod codegen {
+    use super::*;
+
+    #[allow(dead_code)]
+    mod fix44 {
+        include!("generated/fix44_subset.rs");
+    }
+    use fix44::*;
+
+    const FIX44_SUBSET: &str = include_str!("fixtures/FIX44-subset.xml");
+    const GOLDEN: &str = include_str!("generated/fix44_subset.rs");
+
+    /// Fields named after Rust keywords; compiling the golden file is the test.
+    #[allow(dead_code)]
+    mod keywords {
+        include!("generated/keywords.rs");
+    }
+
+    const KEYWORDS: &str = include_str!("fixtures/keywords.xml");
+    const KEYWORDS_GOLDEN: &str = include_str!("generated/keywords.rs");
+
+    fn check_golden(
+        fixture: &str,
+        golden: &str,
+        file: &str,
+    ) {
+        let dict = Dictionary::parse(fixture).expect("fixture parses");
+        let code = generate(&dict, &CodegenOptions::default()).expect("fixture generates");
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            let path = format!("{}/tests/generated/{file}", env!("CARGO_MANIFEST_DIR"));
+            std::fs::write(path, &code).expect("golden file written");
+            return;
+        }
+        assert!(
+            code == golden,
+            "tests/generated/{file} is stale; rerun with UPDATE_GOLDEN=1"
+        );
+    }
+
+    #[test]
+    fn golden_file_is_up_to_date() {
+        check_golden(FIX44_SUBSET, GOLDEN, "fix44_subset.rs");
+    }
+
+    #[test]
+    fn keyword_golden_file_is_up_to_date() {
+        check_golden(KEYWORDS, KEYWORDS_GOLDEN, "keywords.rs");
+    }
+
+    #[test]
+    fn keyword_fields_roundtrip() {
+        let msg = keywords::Keywords {
+            r#yield: Some(4.25),
+            self_: Some("me"),
+            super_: Some(2),
+            crate_: Some(true),
+            r#type: Some(b'T'),
+            r#gen: Some("g"),
+            r#try: Some(3),
+            ..Default::default()
+        };
+        let mut buf = [0u8; 256];
+        let len = msg.write(&mut buf, 0);
+        assert_eq!(keywords::Keywords::read(&buf[..len]), Ok(msg));
+    }
+
+    #[test]
+    fn new_order_single_roundtrip() {
+        let order = NewOrderSingle {
+            cl_ord_id: "ORD-1",
+            no_party_ids: vec![
+                NoPartyIDsEntry {
+                    party_id: Some("FIRM"),
+                    party_id_source: Some(b'D'),
+                    party_role: Some(1),
+                    no_party_sub_ids: vec![NoPartySubIDsEntry {
+                        party_sub_id: Some("DESK"),
+                        party_sub_id_type: Some(4),
+                    }],
+                },
+                NoPartyIDsEntry {
+                    party_id: Some("TRADER"),
+                    party_role: Some(11),
+                    ..Default::default()
+                },
+            ],
+            symbol: "EUR/USD",
+            side: b'1',
+            transact_time: "20240101-12:00:00.000",
+            order_qty: Some(1000.0),
+            ord_type: b'2',
+            price: Some(1.0825),
+            ..Default::default()
+        };
+        let mut buf = [0u8; 512];
+        let len = order.write(&mut buf, 0);
+        assert!(buf[..len].starts_with(b"11=ORD-1\x01453=2\x01448=FIRM\x01447=D\x01452=1\x01802=1\x01"));
+        assert_eq!(NewOrderSingle::read(&buf[..len]), Ok(order));
+    }
+
+    #[test]
+    fn logon_data_field_roundtrip() {
+        let logon = Logon {
+            encrypt_method: 0,
+            heart_bt_int: 30,
+            raw_data: Some(b"a\x01b"),
+            reset_seq_num_flag: Some(true),
+            ..Default::default()
+        };
+        let mut buf = [0u8; 128];
+        let len = logon.write(&mut buf, 0);
+        assert_eq!(&buf[..len], b"98=0\x01108=30\x0195=3\x0196=a\x01b\x01141=Y\x01");
+        assert_eq!(Logon::read(&buf[..len]), Ok(logon));
+    }
+
+    #[test]
+    fn read_skips_header_and_trailer() {
+        let msg = b"8=FIX.4.4\x019=20\x0135=1\x0134=2\x01112=PING\x0110=000\x01";
+        assert_eq!(TestRequest::read(msg), Ok(TestRequest { test_req_id: "PING" }));
+    }
+
+    #[test]
+    fn read_reports_missing_members() {
+        let err = NewOrderSingle::read(b"11=A\x0154=1\x0160=20240101-12:00:00\x01").unwrap_err();
+        let missing: Vec<_> = err
+            .missing_members()
+            .map(|m| (m.name, m.tag, m.kind))
+            .collect();
+        assert_eq!(
+            missing,
+            [
+                ("Instrument", 55, MemberKind::Component),
+                ("OrdType", 40, MemberKind::Field),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_reports_group_and_duplicate_errors() {
+        assert_eq!(
+            NewOrderSingle::read(b"11=A\x01453=2\x01448=X\x0155=S\x01"),
+            Err(ReadError::GroupCountMismatch {
+                offset: 5,
+                tag: 453,
+                expected: 2,
+                actual: 1,
+            })
+        );
+        assert_eq!(
+            Heartbeat::read(b"112=A\x01112=B\x01"),
+            Err(ReadError::DuplicateTag { offset: 6, tag: 112 })
+        );
+    }
+}
+
+mod validator {
+    use super::*;
+
+    const FIX44_SUBSET: &str = include_str!("fixtures/FIX44-subset.xml");
+
+    /// Forge a NewOrderSingle, letting `body` append or override body fields.
+    fn new_order_single(body: impl FnOnce(&mut [u8], usize) -> usize) -> ([u8; FORGE_BUFFER_SIZE], usize) {
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'D';
+        pos += 1;
+        buffer[pos] = 0x01;
+        pos += 1;
+        pos += write_tag_and_str(&mut buffer, pos, b"49=", "CLIENT");
+        pos += write_tag_and_str(&mut buffer, pos, b"56=", "BROKER");
+        pos += write_tag_and_u32(&mut buffer, pos, b"34=", 7);
+        pos += format_timestamp(&mut buffer, pos, b"52=", 1_700_000_000, 0);
+        pos += body(&mut buffer, pos);
+        let len = finalize_message(&mut buffer, pos);
+        (buffer, len)
+    }
+
+    #[test]
+    fn accepts_valid_new_order_single() {
+        let dict = Dictionary::parse(FIX44_SUBSET).unwrap();
+        let validator = Validator::new(&dict);
+        let (buffer, len) = new_order_single(|buf, offset| {
+            let mut pos = offset;
+            pos += write_tag_and_str(buf, pos, b"11=", "ORD-1");
+            pos += write_tag_and_u32(buf, pos, b"453=", 1);
+            pos += write_tag_and_str(buf, pos, b"448=", "FIRM");
+            pos += write_tag_and_u32(buf, pos, b"802=", 1);
+            pos += write_tag_and_str(buf, pos, b"523=", "DESK");
+            pos += write_tag_and_str(buf, pos, b"55=", "EUR/USD");
+            pos += write_tag_and_char(buf, pos, b"54=", b'1');
+            pos += format_timestamp(buf, pos, b"60=", 1_700_000_000, 5_000_000);
+            pos += write_tag_and_char(buf, pos, b"40=", b'2');
+            pos += write_tag_and_f64(buf, pos, b"44=", 1.0825);
+            pos - offset
+        });
+        assert_eq!(validator.validate(&buffer[..len]), Ok(()));
+    }
+
+    #[test]
+    fn reports_session_level_problems() {
+        let dict = Dictionary::parse(FIX44_SUBSET).unwrap();
+        let validator = Validator::new(&dict);
+        let (buffer, len) = new_order_single(|buf, offset| {
+            let mut pos = offset;
+            pos += write_tag_and_str(buf, pos, b"11=", "ORD-1");
+            pos += write_tag_and_str(buf, pos, b"55=", "EUR/USD");
+            pos += write_tag_and_char(buf, pos, b"54=", b'9');
+            pos += write_tag_and_str(buf, pos, b"60=", "2023-11-14");
+            pos += write_tag_and_u32(buf, pos, b"108=", 30);
+            pos - offset
+        });
+        let reasons: Vec<(u16, Option<u32>)> = validator
+            .validate_all(&buffer[..len])
+            .iter()
+            .map(|e| (e.session_reject_reason(), e.ref_tag_id()))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                (session_reject_reason::VALUE_IS_INCORRECT, Some(54)),
+                (session_reject_reason::INCORRECT_DATA_FORMAT, Some(60)),
+                (session_reject_reason::TAG_NOT_DEFINED_FOR_MESSAGE_TYPE, Some(108)),
+                (session_reject_reason::REQUIRED_TAG_MISSING, Some(40)),
+            ]
+        );
+    }
+}
+
+mod store {
+    use super::*;
+
+    /// Send `count` orders through `session`, storing each one in `store`.
+    fn send_orders(
+        session: &mut Session<'_>,
+        store: &mut impl MessageStore,
+        count: u32,
+    ) -> Vec<Vec<u8>> {
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        (0..count)
+            .map(|i| {
+                let seq = session.next_sender_seq();
+                let mut pos = session.begin_message(&mut buffer, b"D", 1_700_000_000 + i as u64, 0);
+                pos += write_tag_and_str(&mut buffer, pos, b"11=", &format!("ORD-{seq}"));
+                let len = finalize_message(&mut buffer, pos);
+                store.store(seq, &buffer[..len]).unwrap();
+                buffer[..len].to_vec()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn session_resumes_from_file_store_after_restart() {
+        let dir = std::env::temp_dir().join(format!("fix44-it-store-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Initiator);
+
+        let sent = {
+            let mut store = FileStore::open(&dir, FsyncPolicy::Every(16)).unwrap();
+            let mut session = Session::with_sequences(config, store.sequences(), 1_700_000_000, 0);
+            let sent = send_orders(&mut session, &mut store, 3);
+            store.set_next_target_seq(12).unwrap();
+            store.sync().unwrap();
+            sent
+        };
+
+        let store = FileStore::open(&dir, FsyncPolicy::Never).unwrap();
+        for (seq, msg) in (1..).zip(&sent) {
+            let stored = store.get(seq).unwrap();
+            assert_eq!(stored, &msg[..]);
+            assert_eq!(MessageHeader::parse(stored).msg_seq_num, seq);
+        }
+        let session = Session::with_sequences(config, store.sequences(), 1_700_000_100, 0);
+        assert_eq!(session.next_sender_seq(), 4);
+        assert_eq!(session.next_target_seq(), 12);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn memory_store_keeps_exact_bytes() {
+        let config = SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Acceptor);
+        let mut session = Session::new(config, 1_700_000_000, 0);
+        let mut store = MemoryStore::new();
+        let sent = send_orders(&mut session, &mut store, 5);
+        assert_eq!(store.get(5), Some(&sent[4][..]));
+        assert_eq!(store.sequences().next_outbound(), session.next_sender_seq());
+    }
+
+    #[test]
+    fn resent_messages_fill_the_gap() {
+        let t0 = 1_700_000_000;
+        let mut initiator = Session::new(
+            SessionConfig::new("FIX.4.4", "CLIENT", "BROKER", SessionRole::Initiator),
+            t0,
+            0,
+        );
+        let mut acceptor = Session::new(
+            SessionConfig::new("FIX.4.4", "BROKER", "CLIENT", SessionRole::Acceptor),
+            t0,
+            0,
+        );
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let len = initiator.poll_transmit(&mut buffer, t0, 0).unwrap();
+        acceptor.handle_message(&buffer[..len], t0, 0);
+        let len = acceptor.poll_transmit(&mut buffer, t0, 0).unwrap();
+        initiator.handle_message(&buffer[..len], t0, 0);
+
+        // Orders 2 and 3 are lost; 4 reveals the gap
+        let mut store = MemoryStore::new();
+        let sent = send_orders(&mut initiator, &mut store, 3);
+        assert_eq!(acceptor.handle_message(&sent[2], t0 + 2, 0), Inbound::Ignored);
+        let len = acceptor.poll_transmit(&mut buffer, t0 + 2, 0).unwrap();
+        assert_eq!(MessageHeader::parse(&buffer[..len]).msg_type, b"2");
+
+        // Resend 2..=4 from the store with PossDup rewriting
+        let now = libc::timespec {
+            tv_sec: t0 as i64 + 3,
+            tv_nsec: 0,
+        };
+        let mut out = [0u8; FORGE_BUFFER_SIZE + RESEND_EXTRA_LEN];
+        for seq in 2..=4 {
+            let stored = store.get(seq).unwrap();
+            let n = rewrite_for_resend(stored, &mut out, &now);
+            let fields: Vec<(u32, &[u8])> = TagValueIter::new(&out[..n]).collect();
+            assert!(fields.contains(&(43, &b"Y"[..])));
+            assert!(fields.contains(&(52, &b"20231114-22:13:23.000"[..])));
+            let original = TagValueIter::new(stored).find(|&(tag, _)| tag == 52).unwrap().1;
+            assert!(fields.contains(&(122, original)));
+            assert_eq!(acceptor.handle_message(&out[..n], t0 + 3, 0), Inbound::Application);
+        }
+        assert_eq!(acceptor.next_target_seq(), 5);
+        assert_eq!(acceptor.sequences().resend_pending(), None);
+    }
+}
+
+mod display {
+    use super::*;
+
+    const FIX44_SUBSET: &str = include_str!("fixtures/FIX44-subset.xml");
+
+    #[test]
+    fn names_and_descriptions_from_dictionary() {
+        let dict = Dictionary::parse(FIX44_SUBSET).unwrap();
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'D';
+        buffer[pos + 1] = 0x01;
+        pos += 2;
+        pos += write_tag_and_char(&mut buffer, pos, b"54=", b'5');
+        pos += write_tag_and_str(&mut buffer, pos, b"554=", "secret");
+        pos += write_tag_and_str(&mut buffer, pos, b"5001=", "custom");
+
+        let options = DisplayOptions {
+            delimiter: "\n",
+            tag_names: true,
+            value_descriptions: true,
+            dictionary: Some(&dict),
+            ..DisplayOptions::default()
+        };
+        // Partially built: BodyLength still the placeholder, no CheckSum yet
+        assert_eq!(
+            FixDisplay::with_options(&buffer[..pos], options).to_string(),
+            "BeginString(8)=FIX.4.4\nBodyLength(9)=0000\nMsgType(35)=D (ORDER_SINGLE)\n\
+             Side(54)=5 (SELL_SHORT)\nPassword(554)=***\n5001=custom\n"
+        );
+
+        let len = finalize_message(&mut buffer, pos);
+        let text = format!("{}", FixDisplay::new(&buffer[..len]));
+        assert!(text.starts_with("8=FIX.4.4|9=0033|35=D|54=5|554=***|5001=custom|10="));
+    }
+}
+
+mod frames {
+    use super::*;
+
+    #[test]
+    fn split_and_validate_mixed_log() {
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'D';
+        buffer[pos + 1] = 0x01;
+        pos += 2;
+        pos += write_tag_and_u32(&mut buffer, pos, b"34=", 12);
+        pos += write_tag_and_data(&mut buffer, pos, b"95=", b"96=", b"8=FIX\x0110=000\x01");
+        pos += write_tag_and_str(&mut buffer, pos, b"55=", "EUR/USD");
+        let len = finalize_message(&mut buffer, pos);
+
+        let mut log = b"2024-01-02 03:04:05.678 IN ".to_vec();
+        log.extend_from_slice(&buffer[..len]);
+        log.extend_from_slice(b"\n2024-01-02 03:04:06.000 OUT ");
+        log.extend(
+            buffer[..len]
+                .iter()
+                .map(|&b| if b == 0x01 { b'|' } else { b }),
+        );
+        log.extend_from_slice(b"\n");
+        let corrupt = log.len() - 10;
+        log[corrupt] = b'X';
+
+        let frames: Vec<Frame> = FrameSplitter::new(&log).collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].bytes, &buffer[..len]);
+        assert_eq!(frames[0].validate(), Ok(()));
+        assert_eq!(frames[1].delimiter, b'|');
+        assert!(matches!(
+            frames[1].validate(),
+            Err(FrameError::CheckSum { .. })
+        ));
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use super::*;
+
+    const FIX44_SUBSET: &str = include_str!("fixtures/FIX44-subset.xml");
+
+    #[test]
+    fn dictionary_groups_round_trip() {
+        let dict = Dictionary::parse(FIX44_SUBSET).unwrap();
+        let mut buffer = forge_out_buffer("FIX.4.4");
+        let mut pos = FORGE_WRITE_START;
+        buffer[pos] = b'D';
+        buffer[pos + 1] = 0x01;
+        pos += 2;
+        pos += write_tag_and_str(&mut buffer, pos, b"11=", "ORD-1");
+        pos += write_tag_and_u32(&mut buffer, pos, b"453=", 2);
+        pos += write_tag_and_str(&mut buffer, pos, b"448=", "FIRM");
+        pos += write_tag_and_u32(&mut buffer, pos, b"802=", 2);
+        pos += write_tag_and_str(&mut buffer, pos, b"523=", "DESK");
+        pos += write_tag_and_str(&mut buffer, pos, b"523=", "BOOK");
+        pos += write_tag_and_str(&mut buffer, pos, b"448=", "TRADER");
+        pos += write_tag_and_str(&mut buffer, pos, b"55=", "EUR/USD");
+        pos += write_tag_and_char(&mut buffer, pos, b"54=", b'1');
+        let len = finalize_message(&mut buffer, pos);
+
+        let options = JsonOptions {
+            tag_names: true,
+            dictionary: Some(&dict),
+            ..JsonOptions::default()
+        };
+        let json = fix_to_json(&buffer[..len], &options).unwrap();
+        let parties = json["NoPartyIDs"].as_array().unwrap();
+        assert_eq!(parties.len(), 2);
+        assert_eq!(parties[0]["PartyID"], "FIRM");
+        assert_eq!(parties[0]["NoPartySubIDs"][1]["PartySubID"], "BOOK");
+        assert_eq!(parties[1]["PartyID"], "TRADER");
+        assert_eq!(json["Symbol"], "EUR/USD");
+
+        // Through text, as a JSON service would see it
+        let text = json.to_string();
+        let parsed: JsonValue = text.parse().unwrap();
+        let mut out = [0u8; FORGE_BUFFER_SIZE];
+        let n = json_to_fix(&parsed, &mut out, &options).unwrap();
+        assert_eq!(out[..n], buffer[..len]);
+    }
+}
+
 // Performance-related tests (basic smoke tests, not benchmarks)
 // mod performance_smoke {
 //     use super::*;
@@ -739,89 +1383,144 @@ mod edge_cases {
 //     }
 // }
 
-// /// Integration tests that combine multiple helper functions
-// mod integration {
-//     use super::*;
+/// Integration tests that combine multiple helper functions
+mod integration {
+    use super::*;
 
-//     #[test]
-//     fn simple_tag_value_roundtrip() {
-//         // Test a simple roundtrip of writing and parsing basic FIX fields
-//         let mut buf = [0u8; 128];
-//         let mut pos = 0;
-
-//         // Write a few basic fields
-//         pos += write_tag_and_str(
-//             &mut buf, pos, b"8=", "FIX.4.4",
-//         );
-//         pos += write_tag_and_u16(&mut buf, pos, b"34=", 123);
-//         pos += write_tag_and_str(&mut buf, pos, b"35=", "D");
-
-//         // Parse them back
-//         let mut iter = TagValueIter::new(&buf[..pos]);
-
-//         // Check first field
-//         let (tag, value) = iter
-//             .next()
-//             .expect("Should have first field");
-//         assert_eq!(tag, 8);
-//         assert_eq!(read_str(value), "FIX.4.4");
-
-//         // Check second field
-//         let (tag, value) = iter
-//             .next()
-//             .expect("Should have second field");
-//         assert_eq!(tag, 34);
-//         assert_eq!(read_u16(value), 123);
-
-//         // Check third field
-//         let (tag, value) = iter
-//             .next()
-//             .expect("Should have third field");
-//         assert_eq!(tag, 35);
-//         assert_eq!(read_str(value), "D");
-
-//         // No more fields
-//         assert!(iter.next().is_none());
-//     }
+    #[test]
+    fn simple_tag_value_roundtrip() {
+        // Test a simple roundtrip of writing and parsing basic FIX fields
+        let mut buf = [0u8; 128];
+        let mut pos = 0;
+
+        // Write a few basic fields
+        pos += write_tag_and_str(
+            &mut buf, pos, b"8=", "FIX.4.4",
+        );
+        pos += write_tag_and_u16(&mut buf, pos, b"34=", 123);
+        pos += write_tag_and_str(&mut buf, pos, b"35=", "D");
+
+        // Parse them back
+        let mut iter = TagValueIter::new(&buf[..pos]);
+
+        // Check first field
+        let (tag, value) = iter
+            .next()
+            .expect("Should have first field");
+        assert_eq!(tag, 8);
+        assert_eq!(read_str(value), "FIX.4.4");
+
+        // Check second field
+        let (tag, value) = iter
+            .next()
+            .expect("Should have second field");
+        assert_eq!(tag, 34);
+        assert_eq!(read_u16(value), 123);
+
+        // Check third field
+        let (tag, value) = iter
+            .next()
+            .expect("Should have third field");
+        assert_eq!(tag, 35);
+        assert_eq!(read_str(value), "D");
+
+        // No more fields
+        assert!(iter.next().is_none());
+    }
 
-//     #[test]
-//     fn message_iterator_with_tag_parsing() {
-//         // Create multiple complete messages
-//         let mut messages = Vec::new();
-
-//         for i in 1..=3 {
-//             let mut buf = [0u8; 256];
-//             let mut pos = 0;
-
-//             pos += write_tag_and_str(
-//                 &mut buf, pos, b"8=", "FIX.4.4",
-//             );
-//             pos += write_tag_and_u16(&mut buf, pos, b"9=", 50);
-//             pos += write_tag_and_str(&mut buf, pos, b"35=", "D");
-//             pos += write_tag_and_u32(&mut buf, pos, b"34=", i);
-//             pos += write_tag_and_str(&mut buf, pos, b"10=", "123");
-
-//             messages.extend_from_slice(&buf[..pos]);
-//         }
+    #[test]
+    fn data_field_with_embedded_soh_roundtrip() {
+        let mut buf = [0u8; 128];
+        let mut pos = 0;
+        let xml = b"<a>\x01</a>";
+
+        pos += write_tag_and_str(&mut buf, pos, b"35=", "n");
+        pos += write_tag_and_data(&mut buf, pos, b"212=", b"213=", xml);
+        pos += write_tag_and_u32(&mut buf, pos, b"34=", 9);
+
+        let fields: Vec<(u32, &[u8])> = TagValueIter::new(&buf[..pos]).collect();
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[1], (212, &b"8"[..]));
+        assert_eq!(fields[2], (213, &xml[..]));
+        assert_eq!(fields[3], (34, &b"9"[..]));
+    }
+
+    #[test]
+    fn repeating_group_roundtrip() {
+        const NO_PARTY_IDS: GroupSpec<'static> = GroupSpec {
+            count_tag: 453,
+            delimiter: 448,
+            members: &[447, 452],
+        };
+        let parties = [("FIRM", b'D', 1u32), ("TRADER", b'C', 11)];
+
+        let mut buf = [0u8; 256];
+        let mut pos = write_tag_and_str(&mut buf, 0, b"11=", "ORD-1");
+        pos += write_tag_and_u32(&mut buf, pos, b"453=", parties.len() as u32);
+        for (id, source, role) in parties {
+            pos += write_tag_and_str(&mut buf, pos, b"448=", id);
+            pos += write_tag_and_char(&mut buf, pos, b"447=", source);
+            pos += write_tag_and_u32(&mut buf, pos, b"452=", role);
+        }
+        pos += write_tag_and_str(&mut buf, pos, b"55=", "EUR/USD");
+
+        let mut it = TagValueIter::new(&buf[..pos]);
+        let mut decoded = Vec::new();
+        let mut symbol = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next().unwrap() else {
+                break;
+            };
+            match tag {
+                453 => {
+                    for entry in GroupIter::new(&mut it, NO_PARTY_IDS, offset, read_u32(value)) {
+                        let fields: Vec<(u32, &[u8])> = TagValueIter::new(entry.unwrap()).collect();
+                        decoded.push((read_str(fields[0].1), read_char(fields[1].1), read_u32(fields[2].1)));
+                    }
+                }
+                55 => symbol = Some(read_str(value)),
+                _ => {}
+            }
+        }
+        assert_eq!(decoded, parties);
+        assert_eq!(symbol, Some("EUR/USD"));
+    }
+
+    #[test]
+    fn message_iterator_with_tag_parsing() {
+        // Create multiple complete messages
+        let mut messages = Vec::new();
+
+        for i in 1..=3 {
+            let mut buf = forge_out_buffer("FIX.4.4");
+            let mut pos = FORGE_WRITE_START;
+            buf[pos] = b'D';
+            buf[pos + 1] = 0x01;
+            pos += 2;
+            pos += write_tag_and_u32(&mut buf, pos, b"34=", i);
+            let len = finalize_message(&mut buf, pos);
+
+            messages.extend_from_slice(&buf[..len]);
+        }
 
-//         // Parse all messages
-//         let mut message_iter = FixMessageIter::new(&messages);
-//         let mut parsed_messages = Vec::new();
+        // Parse all messages
+        let mut parsed_messages = Vec::new();
 
-//         while let Some(msg) = message_iter.next() {
-//             let mut tag_iter = TagValueIter::new(msg);
-//             let mut seq_num = 0;
+        for frame in FrameSplitter::new(&messages) {
+            assert_eq!(frame.validate(), Ok(()));
+            let mut seq_num = 0;
 
-//             while let Some((tag, value)) = tag_iter.next() {
-//                 if tag == 34 {
-//                     seq_num = read_u32(value);
-//                     break;
-//                 }
-//             }
+            for (tag, value) in TagValueIter::new(frame.bytes) {
+                if tag == 34 {
+                    seq_num = read_u32(value);
+                    break;
+                }
+            }
 
-//             parsed_messages.push(seq_num);
-//         }
+            parsed_messages.push(seq_num);
+        }
 
-//         assert_eq!(parsed_messages, vec![1, 2, 3]);
-//     }
-// }
+        assert_eq!(parsed_messages, vec![1, 2, 3]);
+    }
+}
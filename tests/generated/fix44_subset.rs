@@ -0,0 +1,1406 @@
+// @generated by fix44_forge_helpers::generate. Do not edit.
+
+#[allow(unused_imports)]
+use fix44_forge_helpers::{
+    MemberKind, MissingMask, ReadError, TagValueIter, read_char, read_f64, read_i64, read_u32,
+    try_read_bool, try_read_str, write_tag_and_bool, write_tag_and_bytes, write_tag_and_char,
+    write_tag_and_data, write_tag_and_f64, write_tag_and_i64, write_tag_and_str,
+    write_tag_and_u32,
+};
+
+/// Heartbeat (MsgType = 0).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Heartbeat<'a> {
+    /// TestReqID(112)
+    pub test_req_id: Option<&'a str>,
+}
+
+impl<'a> Heartbeat<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "0";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        if let Some(value) = self.test_req_id {
+            pos += write_tag_and_str(buf, pos, b"112=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut test_req_id = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            #[allow(clippy::single_match)]
+            match tag {
+                112 => {
+                    if test_req_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    test_req_id = Some(try_read_str(value, "TestReqID", 112)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            test_req_id,
+        })
+    }
+}
+
+/// TestRequest (MsgType = 1).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TestRequest<'a> {
+    /// TestReqID(112)
+    pub test_req_id: &'a str,
+}
+
+impl<'a> TestRequest<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "1";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[
+        ("TestReqID", 112, MemberKind::Field),
+    ];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        pos += write_tag_and_str(buf, pos, b"112=", self.test_req_id);
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut test_req_id = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            #[allow(clippy::single_match)]
+            match tag {
+                112 => {
+                    if test_req_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    test_req_id = Some(try_read_str(value, "TestReqID", 112)?);
+                }
+                _ => {}
+            }
+        }
+        let mut missing = MissingMask::new();
+        if test_req_id.is_none() {
+            missing.set(0);
+        }
+        if !missing.is_empty() {
+            return Err(ReadError::MissingRequiredFields {
+                missing_mask: missing,
+                meta: Self::META,
+                tag: None,
+            });
+        }
+        Ok(Self {
+            test_req_id: test_req_id.unwrap_or_default(),
+        })
+    }
+}
+
+/// ResendRequest (MsgType = 2).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResendRequest {
+    /// BeginSeqNo(7)
+    pub begin_seq_no: u32,
+    /// EndSeqNo(16)
+    pub end_seq_no: u32,
+}
+
+impl ResendRequest {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "2";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[
+        ("BeginSeqNo", 7, MemberKind::Field),
+        ("EndSeqNo", 16, MemberKind::Field),
+    ];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        pos += write_tag_and_u32(buf, pos, b"7=", self.begin_seq_no);
+        pos += write_tag_and_u32(buf, pos, b"16=", self.end_seq_no);
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &[u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut begin_seq_no = None;
+        let mut end_seq_no = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            match tag {
+                7 => {
+                    if begin_seq_no.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    begin_seq_no = Some(read_u32(value));
+                }
+                16 => {
+                    if end_seq_no.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    end_seq_no = Some(read_u32(value));
+                }
+                _ => {}
+            }
+        }
+        let mut missing = MissingMask::new();
+        if begin_seq_no.is_none() {
+            missing.set(0);
+        }
+        if end_seq_no.is_none() {
+            missing.set(1);
+        }
+        if !missing.is_empty() {
+            return Err(ReadError::MissingRequiredFields {
+                missing_mask: missing,
+                meta: Self::META,
+                tag: None,
+            });
+        }
+        Ok(Self {
+            begin_seq_no: begin_seq_no.unwrap_or_default(),
+            end_seq_no: end_seq_no.unwrap_or_default(),
+        })
+    }
+}
+
+/// Reject (MsgType = 3).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Reject<'a> {
+    /// RefSeqNum(45)
+    pub ref_seq_num: u32,
+    /// RefTagID(371)
+    pub ref_tag_id: Option<i64>,
+    /// RefMsgType(372)
+    pub ref_msg_type: Option<&'a str>,
+    /// SessionRejectReason(373)
+    pub session_reject_reason: Option<i64>,
+    /// Text(58)
+    pub text: Option<&'a str>,
+}
+
+impl<'a> Reject<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "3";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[
+        ("RefSeqNum", 45, MemberKind::Field),
+    ];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        pos += write_tag_and_u32(buf, pos, b"45=", self.ref_seq_num);
+        if let Some(value) = self.ref_tag_id {
+            pos += write_tag_and_i64(buf, pos, b"371=", value);
+        }
+        if let Some(value) = self.ref_msg_type {
+            pos += write_tag_and_str(buf, pos, b"372=", value);
+        }
+        if let Some(value) = self.session_reject_reason {
+            pos += write_tag_and_i64(buf, pos, b"373=", value);
+        }
+        if let Some(value) = self.text {
+            pos += write_tag_and_str(buf, pos, b"58=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut ref_seq_num = None;
+        let mut ref_tag_id = None;
+        let mut ref_msg_type = None;
+        let mut session_reject_reason = None;
+        let mut text = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            match tag {
+                45 => {
+                    if ref_seq_num.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    ref_seq_num = Some(read_u32(value));
+                }
+                371 => {
+                    if ref_tag_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    ref_tag_id = Some(read_i64(value));
+                }
+                372 => {
+                    if ref_msg_type.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    ref_msg_type = Some(try_read_str(value, "RefMsgType", 372)?);
+                }
+                373 => {
+                    if session_reject_reason.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    session_reject_reason = Some(read_i64(value));
+                }
+                58 => {
+                    if text.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    text = Some(try_read_str(value, "Text", 58)?);
+                }
+                _ => {}
+            }
+        }
+        let mut missing = MissingMask::new();
+        if ref_seq_num.is_none() {
+            missing.set(0);
+        }
+        if !missing.is_empty() {
+            return Err(ReadError::MissingRequiredFields {
+                missing_mask: missing,
+                meta: Self::META,
+                tag: None,
+            });
+        }
+        Ok(Self {
+            ref_seq_num: ref_seq_num.unwrap_or_default(),
+            ref_tag_id,
+            ref_msg_type,
+            session_reject_reason,
+            text,
+        })
+    }
+}
+
+/// SequenceReset (MsgType = 4).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SequenceReset {
+    /// GapFillFlag(123)
+    pub gap_fill_flag: Option<bool>,
+    /// NewSeqNo(36)
+    pub new_seq_no: u32,
+}
+
+impl SequenceReset {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "4";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[
+        ("NewSeqNo", 36, MemberKind::Field),
+    ];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        if let Some(value) = self.gap_fill_flag {
+            pos += write_tag_and_bool(buf, pos, b"123=", value);
+        }
+        pos += write_tag_and_u32(buf, pos, b"36=", self.new_seq_no);
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &[u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut gap_fill_flag = None;
+        let mut new_seq_no = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            match tag {
+                123 => {
+                    if gap_fill_flag.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    gap_fill_flag = Some(try_read_bool(value, "GapFillFlag", 123)?);
+                }
+                36 => {
+                    if new_seq_no.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    new_seq_no = Some(read_u32(value));
+                }
+                _ => {}
+            }
+        }
+        let mut missing = MissingMask::new();
+        if new_seq_no.is_none() {
+            missing.set(0);
+        }
+        if !missing.is_empty() {
+            return Err(ReadError::MissingRequiredFields {
+                missing_mask: missing,
+                meta: Self::META,
+                tag: None,
+            });
+        }
+        Ok(Self {
+            gap_fill_flag,
+            new_seq_no: new_seq_no.unwrap_or_default(),
+        })
+    }
+}
+
+/// Logout (MsgType = 5).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Logout<'a> {
+    /// Text(58)
+    pub text: Option<&'a str>,
+}
+
+impl<'a> Logout<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "5";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        if let Some(value) = self.text {
+            pos += write_tag_and_str(buf, pos, b"58=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut text = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            #[allow(clippy::single_match)]
+            match tag {
+                58 => {
+                    if text.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    text = Some(try_read_str(value, "Text", 58)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            text,
+        })
+    }
+}
+
+/// Logon (MsgType = A).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Logon<'a> {
+    /// EncryptMethod(98)
+    pub encrypt_method: i64,
+    /// HeartBtInt(108)
+    pub heart_bt_int: i64,
+    /// RawData(96), length in RawDataLength(95)
+    pub raw_data: Option<&'a [u8]>,
+    /// ResetSeqNumFlag(141)
+    pub reset_seq_num_flag: Option<bool>,
+    /// Username(553)
+    pub username: Option<&'a str>,
+    /// Password(554)
+    pub password: Option<&'a str>,
+}
+
+impl<'a> Logon<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "A";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[
+        ("EncryptMethod", 98, MemberKind::Field),
+        ("HeartBtInt", 108, MemberKind::Field),
+    ];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        pos += write_tag_and_i64(buf, pos, b"98=", self.encrypt_method);
+        pos += write_tag_and_i64(buf, pos, b"108=", self.heart_bt_int);
+        if let Some(value) = self.raw_data {
+            pos += write_tag_and_data(buf, pos, b"95=", b"96=", value);
+        }
+        if let Some(value) = self.reset_seq_num_flag {
+            pos += write_tag_and_bool(buf, pos, b"141=", value);
+        }
+        if let Some(value) = self.username {
+            pos += write_tag_and_str(buf, pos, b"553=", value);
+        }
+        if let Some(value) = self.password {
+            pos += write_tag_and_str(buf, pos, b"554=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut encrypt_method = None;
+        let mut heart_bt_int = None;
+        let mut raw_data = None;
+        let mut reset_seq_num_flag = None;
+        let mut username = None;
+        let mut password = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            match tag {
+                98 => {
+                    if encrypt_method.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    encrypt_method = Some(read_i64(value));
+                }
+                108 => {
+                    if heart_bt_int.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    heart_bt_int = Some(read_i64(value));
+                }
+                96 => {
+                    if raw_data.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    raw_data = Some(value);
+                }
+                141 => {
+                    if reset_seq_num_flag.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    reset_seq_num_flag = Some(try_read_bool(value, "ResetSeqNumFlag", 141)?);
+                }
+                553 => {
+                    if username.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    username = Some(try_read_str(value, "Username", 553)?);
+                }
+                554 => {
+                    if password.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    password = Some(try_read_str(value, "Password", 554)?);
+                }
+                _ => {}
+            }
+        }
+        let mut missing = MissingMask::new();
+        if encrypt_method.is_none() {
+            missing.set(0);
+        }
+        if heart_bt_int.is_none() {
+            missing.set(1);
+        }
+        if !missing.is_empty() {
+            return Err(ReadError::MissingRequiredFields {
+                missing_mask: missing,
+                meta: Self::META,
+                tag: None,
+            });
+        }
+        Ok(Self {
+            encrypt_method: encrypt_method.unwrap_or_default(),
+            heart_bt_int: heart_bt_int.unwrap_or_default(),
+            raw_data,
+            reset_seq_num_flag,
+            username,
+            password,
+        })
+    }
+}
+
+/// NewOrderSingle (MsgType = D).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NewOrderSingle<'a> {
+    /// ClOrdID(11)
+    pub cl_ord_id: &'a str,
+    /// NoPartyIDs(453) entries
+    pub no_party_ids: Vec<NoPartyIDsEntry<'a>>,
+    /// Account(1)
+    pub account: Option<&'a str>,
+    /// Symbol(55)
+    pub symbol: &'a str,
+    /// SecurityID(48)
+    pub security_id: Option<&'a str>,
+    /// SecurityIDSource(22)
+    pub security_id_source: Option<&'a str>,
+    /// NoSecurityAltID(454) entries
+    pub no_security_alt_id: Vec<NoSecurityAltIDEntry<'a>>,
+    /// Side(54)
+    pub side: u8,
+    /// TransactTime(60)
+    pub transact_time: &'a str,
+    /// OrderQty(38)
+    pub order_qty: Option<f64>,
+    /// OrdType(40)
+    pub ord_type: u8,
+    /// Price(44)
+    pub price: Option<f64>,
+    /// TimeInForce(59)
+    pub time_in_force: Option<u8>,
+    /// Text(58)
+    pub text: Option<&'a str>,
+}
+
+impl<'a> NewOrderSingle<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "D";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[
+        ("ClOrdID", 11, MemberKind::Field),
+        ("Instrument", 55, MemberKind::Component),
+        ("Side", 54, MemberKind::Field),
+        ("TransactTime", 60, MemberKind::Field),
+        ("OrdType", 40, MemberKind::Field),
+    ];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        pos += write_tag_and_str(buf, pos, b"11=", self.cl_ord_id);
+        if !self.no_party_ids.is_empty() {
+            pos += write_tag_and_u32(buf, pos, b"453=", self.no_party_ids.len() as u32);
+            for entry in &self.no_party_ids {
+                pos += entry.write(buf, pos);
+            }
+        }
+        if let Some(value) = self.account {
+            pos += write_tag_and_str(buf, pos, b"1=", value);
+        }
+        pos += write_tag_and_str(buf, pos, b"55=", self.symbol);
+        if let Some(value) = self.security_id {
+            pos += write_tag_and_str(buf, pos, b"48=", value);
+        }
+        if let Some(value) = self.security_id_source {
+            pos += write_tag_and_str(buf, pos, b"22=", value);
+        }
+        if !self.no_security_alt_id.is_empty() {
+            pos += write_tag_and_u32(buf, pos, b"454=", self.no_security_alt_id.len() as u32);
+            for entry in &self.no_security_alt_id {
+                pos += entry.write(buf, pos);
+            }
+        }
+        pos += write_tag_and_char(buf, pos, b"54=", self.side);
+        pos += write_tag_and_str(buf, pos, b"60=", self.transact_time);
+        if let Some(value) = self.order_qty {
+            pos += write_tag_and_f64(buf, pos, b"38=", value);
+        }
+        pos += write_tag_and_char(buf, pos, b"40=", self.ord_type);
+        if let Some(value) = self.price {
+            pos += write_tag_and_f64(buf, pos, b"44=", value);
+        }
+        if let Some(value) = self.time_in_force {
+            pos += write_tag_and_char(buf, pos, b"59=", value);
+        }
+        if let Some(value) = self.text {
+            pos += write_tag_and_str(buf, pos, b"58=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut cl_ord_id = None;
+        let mut no_party_ids = None;
+        let mut account = None;
+        let mut symbol = None;
+        let mut security_id = None;
+        let mut security_id_source = None;
+        let mut no_security_alt_id = None;
+        let mut side = None;
+        let mut transact_time = None;
+        let mut order_qty = None;
+        let mut ord_type = None;
+        let mut price = None;
+        let mut time_in_force = None;
+        let mut text = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            match tag {
+                11 => {
+                    if cl_ord_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    cl_ord_id = Some(try_read_str(value, "ClOrdID", 11)?);
+                }
+                453 => {
+                    if no_party_ids.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    no_party_ids = Some(NoPartyIDsEntry::read_group(&mut it, offset, tag, read_u32(value))?);
+                }
+                1 => {
+                    if account.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    account = Some(try_read_str(value, "Account", 1)?);
+                }
+                55 => {
+                    if symbol.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    symbol = Some(try_read_str(value, "Symbol", 55)?);
+                }
+                48 => {
+                    if security_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    security_id = Some(try_read_str(value, "SecurityID", 48)?);
+                }
+                22 => {
+                    if security_id_source.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    security_id_source = Some(try_read_str(value, "SecurityIDSource", 22)?);
+                }
+                454 => {
+                    if no_security_alt_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    no_security_alt_id = Some(NoSecurityAltIDEntry::read_group(&mut it, offset, tag, read_u32(value))?);
+                }
+                54 => {
+                    if side.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    side = Some(read_char(value));
+                }
+                60 => {
+                    if transact_time.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    transact_time = Some(try_read_str(value, "TransactTime", 60)?);
+                }
+                38 => {
+                    if order_qty.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    order_qty = Some(read_f64(value));
+                }
+                40 => {
+                    if ord_type.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    ord_type = Some(read_char(value));
+                }
+                44 => {
+                    if price.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    price = Some(read_f64(value));
+                }
+                59 => {
+                    if time_in_force.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    time_in_force = Some(read_char(value));
+                }
+                58 => {
+                    if text.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    text = Some(try_read_str(value, "Text", 58)?);
+                }
+                _ => {}
+            }
+        }
+        let mut missing = MissingMask::new();
+        if cl_ord_id.is_none() {
+            missing.set(0);
+        }
+        if symbol.is_none() {
+            missing.set(1);
+        }
+        if side.is_none() {
+            missing.set(2);
+        }
+        if transact_time.is_none() {
+            missing.set(3);
+        }
+        if ord_type.is_none() {
+            missing.set(4);
+        }
+        if !missing.is_empty() {
+            return Err(ReadError::MissingRequiredFields {
+                missing_mask: missing,
+                meta: Self::META,
+                tag: None,
+            });
+        }
+        Ok(Self {
+            cl_ord_id: cl_ord_id.unwrap_or_default(),
+            no_party_ids: no_party_ids.unwrap_or_default(),
+            account,
+            symbol: symbol.unwrap_or_default(),
+            security_id,
+            security_id_source,
+            no_security_alt_id: no_security_alt_id.unwrap_or_default(),
+            side: side.unwrap_or_default(),
+            transact_time: transact_time.unwrap_or_default(),
+            order_qty,
+            ord_type: ord_type.unwrap_or_default(),
+            price,
+            time_in_force,
+            text,
+        })
+    }
+}
+
+/// ExecutionReport (MsgType = 8).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExecutionReport<'a> {
+    /// OrderID(37)
+    pub order_id: &'a str,
+    /// ClOrdID(11)
+    pub cl_ord_id: Option<&'a str>,
+    /// NoPartyIDs(453) entries
+    pub no_party_ids: Vec<NoPartyIDsEntry<'a>>,
+    /// ExecID(17)
+    pub exec_id: &'a str,
+    /// ExecType(150)
+    pub exec_type: u8,
+    /// OrdStatus(39)
+    pub ord_status: u8,
+    /// Account(1)
+    pub account: Option<&'a str>,
+    /// Symbol(55)
+    pub symbol: &'a str,
+    /// SecurityID(48)
+    pub security_id: Option<&'a str>,
+    /// SecurityIDSource(22)
+    pub security_id_source: Option<&'a str>,
+    /// NoSecurityAltID(454) entries
+    pub no_security_alt_id: Vec<NoSecurityAltIDEntry<'a>>,
+    /// Side(54)
+    pub side: u8,
+    /// OrderQty(38)
+    pub order_qty: Option<f64>,
+    /// Price(44)
+    pub price: Option<f64>,
+    /// LeavesQty(151)
+    pub leaves_qty: f64,
+    /// CumQty(14)
+    pub cum_qty: f64,
+    /// AvgPx(6)
+    pub avg_px: f64,
+    /// TransactTime(60)
+    pub transact_time: Option<&'a str>,
+    /// Text(58)
+    pub text: Option<&'a str>,
+}
+
+impl<'a> ExecutionReport<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "8";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[
+        ("OrderID", 37, MemberKind::Field),
+        ("ExecID", 17, MemberKind::Field),
+        ("ExecType", 150, MemberKind::Field),
+        ("OrdStatus", 39, MemberKind::Field),
+        ("Instrument", 55, MemberKind::Component),
+        ("Side", 54, MemberKind::Field),
+        ("LeavesQty", 151, MemberKind::Field),
+        ("CumQty", 14, MemberKind::Field),
+        ("AvgPx", 6, MemberKind::Field),
+    ];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        pos += write_tag_and_str(buf, pos, b"37=", self.order_id);
+        if let Some(value) = self.cl_ord_id {
+            pos += write_tag_and_str(buf, pos, b"11=", value);
+        }
+        if !self.no_party_ids.is_empty() {
+            pos += write_tag_and_u32(buf, pos, b"453=", self.no_party_ids.len() as u32);
+            for entry in &self.no_party_ids {
+                pos += entry.write(buf, pos);
+            }
+        }
+        pos += write_tag_and_str(buf, pos, b"17=", self.exec_id);
+        pos += write_tag_and_char(buf, pos, b"150=", self.exec_type);
+        pos += write_tag_and_char(buf, pos, b"39=", self.ord_status);
+        if let Some(value) = self.account {
+            pos += write_tag_and_str(buf, pos, b"1=", value);
+        }
+        pos += write_tag_and_str(buf, pos, b"55=", self.symbol);
+        if let Some(value) = self.security_id {
+            pos += write_tag_and_str(buf, pos, b"48=", value);
+        }
+        if let Some(value) = self.security_id_source {
+            pos += write_tag_and_str(buf, pos, b"22=", value);
+        }
+        if !self.no_security_alt_id.is_empty() {
+            pos += write_tag_and_u32(buf, pos, b"454=", self.no_security_alt_id.len() as u32);
+            for entry in &self.no_security_alt_id {
+                pos += entry.write(buf, pos);
+            }
+        }
+        pos += write_tag_and_char(buf, pos, b"54=", self.side);
+        if let Some(value) = self.order_qty {
+            pos += write_tag_and_f64(buf, pos, b"38=", value);
+        }
+        if let Some(value) = self.price {
+            pos += write_tag_and_f64(buf, pos, b"44=", value);
+        }
+        pos += write_tag_and_f64(buf, pos, b"151=", self.leaves_qty);
+        pos += write_tag_and_f64(buf, pos, b"14=", self.cum_qty);
+        pos += write_tag_and_f64(buf, pos, b"6=", self.avg_px);
+        if let Some(value) = self.transact_time {
+            pos += write_tag_and_str(buf, pos, b"60=", value);
+        }
+        if let Some(value) = self.text {
+            pos += write_tag_and_str(buf, pos, b"58=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut order_id = None;
+        let mut cl_ord_id = None;
+        let mut no_party_ids = None;
+        let mut exec_id = None;
+        let mut exec_type = None;
+        let mut ord_status = None;
+        let mut account = None;
+        let mut symbol = None;
+        let mut security_id = None;
+        let mut security_id_source = None;
+        let mut no_security_alt_id = None;
+        let mut side = None;
+        let mut order_qty = None;
+        let mut price = None;
+        let mut leaves_qty = None;
+        let mut cum_qty = None;
+        let mut avg_px = None;
+        let mut transact_time = None;
+        let mut text = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            match tag {
+                37 => {
+                    if order_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    order_id = Some(try_read_str(value, "OrderID", 37)?);
+                }
+                11 => {
+                    if cl_ord_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    cl_ord_id = Some(try_read_str(value, "ClOrdID", 11)?);
+                }
+                453 => {
+                    if no_party_ids.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    no_party_ids = Some(NoPartyIDsEntry::read_group(&mut it, offset, tag, read_u32(value))?);
+                }
+                17 => {
+                    if exec_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    exec_id = Some(try_read_str(value, "ExecID", 17)?);
+                }
+                150 => {
+                    if exec_type.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    exec_type = Some(read_char(value));
+                }
+                39 => {
+                    if ord_status.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    ord_status = Some(read_char(value));
+                }
+                1 => {
+                    if account.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    account = Some(try_read_str(value, "Account", 1)?);
+                }
+                55 => {
+                    if symbol.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    symbol = Some(try_read_str(value, "Symbol", 55)?);
+                }
+                48 => {
+                    if security_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    security_id = Some(try_read_str(value, "SecurityID", 48)?);
+                }
+                22 => {
+                    if security_id_source.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    security_id_source = Some(try_read_str(value, "SecurityIDSource", 22)?);
+                }
+                454 => {
+                    if no_security_alt_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    no_security_alt_id = Some(NoSecurityAltIDEntry::read_group(&mut it, offset, tag, read_u32(value))?);
+                }
+                54 => {
+                    if side.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    side = Some(read_char(value));
+                }
+                38 => {
+                    if order_qty.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    order_qty = Some(read_f64(value));
+                }
+                44 => {
+                    if price.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    price = Some(read_f64(value));
+                }
+                151 => {
+                    if leaves_qty.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    leaves_qty = Some(read_f64(value));
+                }
+                14 => {
+                    if cum_qty.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    cum_qty = Some(read_f64(value));
+                }
+                6 => {
+                    if avg_px.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    avg_px = Some(read_f64(value));
+                }
+                60 => {
+                    if transact_time.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    transact_time = Some(try_read_str(value, "TransactTime", 60)?);
+                }
+                58 => {
+                    if text.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    text = Some(try_read_str(value, "Text", 58)?);
+                }
+                _ => {}
+            }
+        }
+        let mut missing = MissingMask::new();
+        if order_id.is_none() {
+            missing.set(0);
+        }
+        if exec_id.is_none() {
+            missing.set(1);
+        }
+        if exec_type.is_none() {
+            missing.set(2);
+        }
+        if ord_status.is_none() {
+            missing.set(3);
+        }
+        if symbol.is_none() {
+            missing.set(4);
+        }
+        if side.is_none() {
+            missing.set(5);
+        }
+        if leaves_qty.is_none() {
+            missing.set(6);
+        }
+        if cum_qty.is_none() {
+            missing.set(7);
+        }
+        if avg_px.is_none() {
+            missing.set(8);
+        }
+        if !missing.is_empty() {
+            return Err(ReadError::MissingRequiredFields {
+                missing_mask: missing,
+                meta: Self::META,
+                tag: None,
+            });
+        }
+        Ok(Self {
+            order_id: order_id.unwrap_or_default(),
+            cl_ord_id,
+            no_party_ids: no_party_ids.unwrap_or_default(),
+            exec_id: exec_id.unwrap_or_default(),
+            exec_type: exec_type.unwrap_or_default(),
+            ord_status: ord_status.unwrap_or_default(),
+            account,
+            symbol: symbol.unwrap_or_default(),
+            security_id,
+            security_id_source,
+            no_security_alt_id: no_security_alt_id.unwrap_or_default(),
+            side: side.unwrap_or_default(),
+            order_qty,
+            price,
+            leaves_qty: leaves_qty.unwrap_or_default(),
+            cum_qty: cum_qty.unwrap_or_default(),
+            avg_px: avg_px.unwrap_or_default(),
+            transact_time,
+            text,
+        })
+    }
+}
+
+/// One entry of the NoPartyIDs repeating group.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NoPartyIDsEntry<'a> {
+    /// PartyID(448)
+    pub party_id: Option<&'a str>,
+    /// PartyIDSource(447)
+    pub party_id_source: Option<u8>,
+    /// PartyRole(452)
+    pub party_role: Option<i64>,
+    /// NoPartySubIDs(802) entries
+    pub no_party_sub_ids: Vec<NoPartySubIDsEntry<'a>>,
+}
+
+impl<'a> NoPartyIDsEntry<'a> {
+    /// First tag of every entry.
+    pub const DELIMITER: u32 = 448;
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        if let Some(value) = self.party_id {
+            pos += write_tag_and_str(buf, pos, b"448=", value);
+        }
+        if let Some(value) = self.party_id_source {
+            pos += write_tag_and_char(buf, pos, b"447=", value);
+        }
+        if let Some(value) = self.party_role {
+            pos += write_tag_and_i64(buf, pos, b"452=", value);
+        }
+        if !self.no_party_sub_ids.is_empty() {
+            pos += write_tag_and_u32(buf, pos, b"802=", self.no_party_sub_ids.len() as u32);
+            for entry in &self.no_party_sub_ids {
+                pos += entry.write(buf, pos);
+            }
+        }
+        pos - offset
+    }
+
+    /// Read the entries following a NumInGroup field at `count_offset`,
+    /// checking them against the declared `count`.
+    pub fn read_group(
+        it: &mut TagValueIter<'a>,
+        count_offset: usize,
+        count_tag: u32,
+        count: u32,
+    ) -> Result<Vec<Self>, ReadError> {
+        let mut entries = Vec::new();
+        while let Some((Self::DELIMITER, _)) = it.clone().try_next()? {
+            entries.push(Self::read_entry(it)?);
+        }
+        if entries.len() != count as usize {
+            return Err(ReadError::GroupCountMismatch {
+                offset: count_offset,
+                tag: count_tag,
+                expected: count,
+                actual: entries.len() as u32,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Read one entry, stopping before the next delimiter or a non-member tag.
+    fn read_entry(it: &mut TagValueIter<'a>) -> Result<Self, ReadError> {
+        let mut party_id = None;
+        let mut party_id_source = None;
+        let mut party_role = None;
+        let mut no_party_sub_ids = None;
+        let mut first = true;
+        loop {
+            let mut peek = it.clone();
+            let offset = peek.position();
+            let Some((tag, value)) = peek.try_next()? else {
+                break;
+            };
+            if !matches!(tag, 448 | 447 | 452 | 802) || (tag == Self::DELIMITER && !first) {
+                break;
+            }
+            *it = peek;
+            first = false;
+            match tag {
+                448 => {
+                    if party_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    party_id = Some(try_read_str(value, "PartyID", 448)?);
+                }
+                447 => {
+                    if party_id_source.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    party_id_source = Some(read_char(value));
+                }
+                452 => {
+                    if party_role.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    party_role = Some(read_i64(value));
+                }
+                802 => {
+                    if no_party_sub_ids.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    no_party_sub_ids = Some(NoPartySubIDsEntry::read_group(it, offset, tag, read_u32(value))?);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            party_id,
+            party_id_source,
+            party_role,
+            no_party_sub_ids: no_party_sub_ids.unwrap_or_default(),
+        })
+    }
+}
+
+/// One entry of the NoSecurityAltID repeating group.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NoSecurityAltIDEntry<'a> {
+    /// SecurityAltID(455)
+    pub security_alt_id: Option<&'a str>,
+    /// SecurityAltIDSource(456)
+    pub security_alt_id_source: Option<&'a str>,
+}
+
+impl<'a> NoSecurityAltIDEntry<'a> {
+    /// First tag of every entry.
+    pub const DELIMITER: u32 = 455;
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        if let Some(value) = self.security_alt_id {
+            pos += write_tag_and_str(buf, pos, b"455=", value);
+        }
+        if let Some(value) = self.security_alt_id_source {
+            pos += write_tag_and_str(buf, pos, b"456=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the entries following a NumInGroup field at `count_offset`,
+    /// checking them against the declared `count`.
+    pub fn read_group(
+        it: &mut TagValueIter<'a>,
+        count_offset: usize,
+        count_tag: u32,
+        count: u32,
+    ) -> Result<Vec<Self>, ReadError> {
+        let mut entries = Vec::new();
+        while let Some((Self::DELIMITER, _)) = it.clone().try_next()? {
+            entries.push(Self::read_entry(it)?);
+        }
+        if entries.len() != count as usize {
+            return Err(ReadError::GroupCountMismatch {
+                offset: count_offset,
+                tag: count_tag,
+                expected: count,
+                actual: entries.len() as u32,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Read one entry, stopping before the next delimiter or a non-member tag.
+    fn read_entry(it: &mut TagValueIter<'a>) -> Result<Self, ReadError> {
+        let mut security_alt_id = None;
+        let mut security_alt_id_source = None;
+        let mut first = true;
+        loop {
+            let mut peek = it.clone();
+            let offset = peek.position();
+            let Some((tag, value)) = peek.try_next()? else {
+                break;
+            };
+            if !matches!(tag, 455 | 456) || (tag == Self::DELIMITER && !first) {
+                break;
+            }
+            *it = peek;
+            first = false;
+            match tag {
+                455 => {
+                    if security_alt_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    security_alt_id = Some(try_read_str(value, "SecurityAltID", 455)?);
+                }
+                456 => {
+                    if security_alt_id_source.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    security_alt_id_source = Some(try_read_str(value, "SecurityAltIDSource", 456)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            security_alt_id,
+            security_alt_id_source,
+        })
+    }
+}
+
+/// One entry of the NoPartySubIDs repeating group.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NoPartySubIDsEntry<'a> {
+    /// PartySubID(523)
+    pub party_sub_id: Option<&'a str>,
+    /// PartySubIDType(803)
+    pub party_sub_id_type: Option<i64>,
+}
+
+impl<'a> NoPartySubIDsEntry<'a> {
+    /// First tag of every entry.
+    pub const DELIMITER: u32 = 523;
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        if let Some(value) = self.party_sub_id {
+            pos += write_tag_and_str(buf, pos, b"523=", value);
+        }
+        if let Some(value) = self.party_sub_id_type {
+            pos += write_tag_and_i64(buf, pos, b"803=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the entries following a NumInGroup field at `count_offset`,
+    /// checking them against the declared `count`.
+    pub fn read_group(
+        it: &mut TagValueIter<'a>,
+        count_offset: usize,
+        count_tag: u32,
+        count: u32,
+    ) -> Result<Vec<Self>, ReadError> {
+        let mut entries = Vec::new();
+        while let Some((Self::DELIMITER, _)) = it.clone().try_next()? {
+            entries.push(Self::read_entry(it)?);
+        }
+        if entries.len() != count as usize {
+            return Err(ReadError::GroupCountMismatch {
+                offset: count_offset,
+                tag: count_tag,
+                expected: count,
+                actual: entries.len() as u32,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Read one entry, stopping before the next delimiter or a non-member tag.
+    fn read_entry(it: &mut TagValueIter<'a>) -> Result<Self, ReadError> {
+        let mut party_sub_id = None;
+        let mut party_sub_id_type = None;
+        let mut first = true;
+        loop {
+            let mut peek = it.clone();
+            let offset = peek.position();
+            let Some((tag, value)) = peek.try_next()? else {
+                break;
+            };
+            if !matches!(tag, 523 | 803) || (tag == Self::DELIMITER && !first) {
+                break;
+            }
+            *it = peek;
+            first = false;
+            match tag {
+                523 => {
+                    if party_sub_id.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    party_sub_id = Some(try_read_str(value, "PartySubID", 523)?);
+                }
+                803 => {
+                    if party_sub_id_type.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    party_sub_id_type = Some(read_i64(value));
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            party_sub_id,
+            party_sub_id_type,
+        })
+    }
+}
@@ -0,0 +1,213 @@
+// @generated by fix44_forge_helpers::generate. Do not edit.
+
+#[allow(unused_imports)]
+use fix44_forge_helpers::{
+    MemberKind, MissingMask, ReadError, TagValueIter, read_char, read_f64, read_i64, read_u32,
+    try_read_bool, try_read_str, write_tag_and_bool, write_tag_and_bytes, write_tag_and_char,
+    write_tag_and_data, write_tag_and_f64, write_tag_and_i64, write_tag_and_str,
+    write_tag_and_u32,
+};
+
+/// Keywords (MsgType = U1).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Keywords<'a> {
+    /// YieldType(235)
+    pub yield_type: Option<&'a str>,
+    /// Yield(236)
+    pub r#yield: Option<f64>,
+    /// Self(5001)
+    pub self_: Option<&'a str>,
+    /// Super(5002)
+    pub super_: Option<i64>,
+    /// Crate(5003)
+    pub crate_: Option<bool>,
+    /// Type(5004)
+    pub r#type: Option<u8>,
+    /// Async(5005)
+    pub r#async: Option<f64>,
+    /// Gen(5006)
+    pub r#gen: Option<&'a str>,
+    /// Try(5007)
+    pub r#try: Option<i64>,
+    /// Dyn(5008)
+    pub r#dyn: Option<&'a str>,
+    /// Box(5009)
+    pub r#box: Option<&'a str>,
+    /// Abstract(5010)
+    pub r#abstract: Option<&'a str>,
+    /// Final(5011)
+    pub r#final: Option<&'a str>,
+}
+
+impl<'a> Keywords<'a> {
+    /// MsgType(35) value.
+    pub const MSG_TYPE: &'static str = "U1";
+
+    /// Required members, indexed like the `MissingRequiredFields` mask.
+    pub const META: &'static [(&'static str, u16, MemberKind)] = &[];
+
+    /// Write the fields at `offset` (after MsgType for messages); returns bytes written.
+    pub fn write(&self, buf: &mut [u8], offset: usize) -> usize {
+        let mut pos = offset;
+        if let Some(value) = self.yield_type {
+            pos += write_tag_and_str(buf, pos, b"235=", value);
+        }
+        if let Some(value) = self.r#yield {
+            pos += write_tag_and_f64(buf, pos, b"236=", value);
+        }
+        if let Some(value) = self.self_ {
+            pos += write_tag_and_str(buf, pos, b"5001=", value);
+        }
+        if let Some(value) = self.super_ {
+            pos += write_tag_and_i64(buf, pos, b"5002=", value);
+        }
+        if let Some(value) = self.crate_ {
+            pos += write_tag_and_bool(buf, pos, b"5003=", value);
+        }
+        if let Some(value) = self.r#type {
+            pos += write_tag_and_char(buf, pos, b"5004=", value);
+        }
+        if let Some(value) = self.r#async {
+            pos += write_tag_and_f64(buf, pos, b"5005=", value);
+        }
+        if let Some(value) = self.r#gen {
+            pos += write_tag_and_str(buf, pos, b"5006=", value);
+        }
+        if let Some(value) = self.r#try {
+            pos += write_tag_and_i64(buf, pos, b"5007=", value);
+        }
+        if let Some(value) = self.r#dyn {
+            pos += write_tag_and_str(buf, pos, b"5008=", value);
+        }
+        if let Some(value) = self.r#box {
+            pos += write_tag_and_str(buf, pos, b"5009=", value);
+        }
+        if let Some(value) = self.r#abstract {
+            pos += write_tag_and_str(buf, pos, b"5010=", value);
+        }
+        if let Some(value) = self.r#final {
+            pos += write_tag_and_str(buf, pos, b"5011=", value);
+        }
+        pos - offset
+    }
+
+    /// Read the message from `msg`, skipping header, trailer and unknown tags.
+    pub fn read(msg: &'a [u8]) -> Result<Self, ReadError> {
+        let mut it = TagValueIter::new(msg);
+        let mut yield_type = None;
+        let mut r#yield = None;
+        let mut self_ = None;
+        let mut super_ = None;
+        let mut crate_ = None;
+        let mut r#type = None;
+        let mut r#async = None;
+        let mut r#gen = None;
+        let mut r#try = None;
+        let mut r#dyn = None;
+        let mut r#box = None;
+        let mut r#abstract = None;
+        let mut r#final = None;
+        loop {
+            let offset = it.position();
+            let Some((tag, value)) = it.try_next()? else {
+                break;
+            };
+            match tag {
+                235 => {
+                    if yield_type.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    yield_type = Some(try_read_str(value, "YieldType", 235)?);
+                }
+                236 => {
+                    if r#yield.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#yield = Some(read_f64(value));
+                }
+                5001 => {
+                    if self_.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    self_ = Some(try_read_str(value, "Self", 5001)?);
+                }
+                5002 => {
+                    if super_.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    super_ = Some(read_i64(value));
+                }
+                5003 => {
+                    if crate_.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    crate_ = Some(try_read_bool(value, "Crate", 5003)?);
+                }
+                5004 => {
+                    if r#type.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#type = Some(read_char(value));
+                }
+                5005 => {
+                    if r#async.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#async = Some(read_f64(value));
+                }
+                5006 => {
+                    if r#gen.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#gen = Some(try_read_str(value, "Gen", 5006)?);
+                }
+                5007 => {
+                    if r#try.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#try = Some(read_i64(value));
+                }
+                5008 => {
+                    if r#dyn.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#dyn = Some(try_read_str(value, "Dyn", 5008)?);
+                }
+                5009 => {
+                    if r#box.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#box = Some(try_read_str(value, "Box", 5009)?);
+                }
+                5010 => {
+                    if r#abstract.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#abstract = Some(try_read_str(value, "Abstract", 5010)?);
+                }
+                5011 => {
+                    if r#final.is_some() {
+                        return Err(ReadError::DuplicateTag { offset, tag });
+                    }
+                    r#final = Some(try_read_str(value, "Final", 5011)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            yield_type,
+            r#yield,
+            self_,
+            super_,
+            crate_,
+            r#type,
+            r#async,
+            r#gen,
+            r#try,
+            r#dyn,
+            r#box,
+            r#abstract,
+            r#final,
+        })
+    }
+}